@@ -3,7 +3,6 @@ use prettytable::Row;
 use prettytable::Table;
 use prettytable::{cell, row};
 use shrust::{Shell, ShellIO};
-use songkick::endpoints::SkEndpoint;
 use songkick::resources::{Artist, Event};
 use songkick::SongKick;
 use std::io::Write;
@@ -43,7 +42,7 @@ fn main() {
     shell.new_command("search", "search api", 2, |io, sk, s| {
         writeln!(io, "Searching {} with {}", s[0], s[1])?;
         match s[0] {
-            "artist" => search_artist(sk, &s[1]),
+            "artist" => search_artist(sk, s[1]),
             _ => writeln!(io, "Command {} not found", s[0])?,
         }
         Ok(())
@@ -95,9 +94,9 @@ fn get_artist(sk: &SongKick, input: u64) {
         Ok(result) => {
             let artists = result.collect::<Vec<Artist>>();
             if artists.len() == 1 {
-                println!("");
+                println!();
                 let mut table = Table::new();
-                let ref artist = artists[0];
+                let artist = &artists[0];
                 table.add_row(row!["ID", artist.id]);
                 table.add_row(row!["NAME", artist.display_name]);
                 table.add_row(row!["URI", artist.uri]);
@@ -123,7 +122,7 @@ fn gigs_artist(sk: &SongKick, input: u64) {
 
             let events = result.collect::<Vec<Event>>();
 
-            println!("");
+            println!();
 
             let mut table = Table::new();
             table.add_row(row!["ID", "NAME"]);
@@ -135,12 +134,12 @@ fn gigs_artist(sk: &SongKick, input: u64) {
             }
 
             table.printstd();
-            println!("");
+            println!();
             println!(
                 "Total : {}, Page : {}, Per Page : {}",
                 total, page, per_page
             );
-            println!("");
+            println!();
         }
         Err(ex) => {
             println!("Error : {}", ex);
@@ -159,7 +158,7 @@ fn calendar_artist(sk: &SongKick, input: u64) {
 
             let events = result.collect::<Vec<Event>>();
 
-            println!("");
+            println!();
 
             let mut table = Table::new();
             table.add_row(row!["ID", "NAME"]);
@@ -171,12 +170,12 @@ fn calendar_artist(sk: &SongKick, input: u64) {
             }
 
             table.printstd();
-            println!("");
+            println!();
             println!(
                 "Total : {}, Page : {}, Per Page : {}",
                 total, page, per_page
             );
-            println!("");
+            println!();
         }
         Err(ex) => {
             println!("Error : {}", ex);
@@ -197,7 +196,7 @@ fn search_artist(sk: &SongKick, input: &str) {
 
             let mut table = Table::new();
 
-            println!("");
+            println!();
             table.add_row(row!["ID", "NAME"]);
 
             for artist in artists {
@@ -208,12 +207,12 @@ fn search_artist(sk: &SongKick, input: &str) {
             }
 
             table.printstd();
-            println!("");
+            println!();
             println!(
                 "Total : {}, Page : {}, Per Page : {}",
                 total, page, per_page
             );
-            println!("");
+            println!();
         }
         Err(ex) => {
             println!("Error : {}", ex);
@@ -5,7 +5,6 @@ extern crate shrust;
 use std::io::{stdin, stdout};
 use songkick::{SongKick};
 use songkick::resources::Artist;
-use songkick::endpoints::{SkEndpoint};
 use songkick::resources::{Event};
 use songkick::options::{OptionsBuilder};
 use std::io::Write;
@@ -30,8 +29,7 @@ fn main() {
     let sk = SongKick::new(api_key.unwrap());
 
 
-    let artists : Vec<Artist> = sk.artist.get(253846)
-        .and_then(|res| Ok(res.collect()))
+    let artists : Vec<Artist> = sk.artist.get(253846).map(|res| res.collect())
         .expect("Failed to fetch artist with id");
 
     assert_eq!(1,artists.len());
@@ -49,7 +47,7 @@ fn main() {
             let mut events = result.collect::<Vec<Event>>();
 
             println!("Fetched first page");
-            println!("");
+            println!();
             for n in 2..(pages + 1) {
                 let options = OptionsBuilder::new().paging(n, per_page).build();
                 println!("Fetching page {} of {} ", n, pages);
@@ -57,10 +55,10 @@ fn main() {
                 let mut evts = res.collect::<Vec<Event>>();
                 events.append(&mut evts);
                 println!("Fetched page {} of {} ", n, pages);
-                println!("");
+                println!();
                 sleep(Duration::from_secs(1));
             }
-            println!("");
+            println!();
             println!("Events Collected : {}", events.len());
         },
         Err(ex) => {
@@ -30,12 +30,11 @@ fn main() {
         f.artist_name("Radiohead")
             .location("clientip");
     }).build();
-    let events: Vec<Event> = sk.event.search(option)
-        .and_then(|res| Ok(res.collect()))
+    let events: Vec<Event> = sk.event.search(option).map(|res| res.collect())
         .expect("Failed to search local Radiohead concerts");
 
-    println!("");
+    println!();
     println!("Found {} Radiohead Events near you", events.len());
-    println!("");
+    println!();
 }
 
@@ -0,0 +1,122 @@
+use crate::analysis::day_number;
+use crate::resources::calendar_entry::{CalendarEntry, Reason};
+use crate::resources::event::Event;
+use crate::resources::ids::EventId;
+use std::collections::HashMap;
+
+/// User of a [`MergedEntry`]
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct Attendee<'a> {
+    /// Index of the calendar of the user in the merged calendars
+    pub user: usize,
+    /// Why the event is in the calendar of the user
+    pub reason: &'a Reason,
+}
+
+impl Attendee<'_> {
+    /// Whether the user marked the event as attended (`im_going` or `i_might_go`),
+    /// rather than it only being an event of an artist they track
+    pub fn is_attending(&self) -> bool {
+        self.reason.attendance.is_some()
+    }
+}
+
+/// Event of several calendars, with the users it comes from, see [`merge_calendars`]
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct MergedEntry<'a> {
+    pub event: &'a Event,
+    /// Users with the event in their calendar, by index
+    pub users: Vec<Attendee<'a>>,
+}
+
+impl MergedEntry<'_> {
+    /// Users marking the event as attended, by index
+    pub fn attending(&self) -> impl Iterator<Item = usize> + '_ {
+        self.users.iter().filter(|user| user.is_attending()).map(|user| user.user)
+    }
+}
+
+/// Events of the calendars of several users, each once with the users of its entries
+///
+/// Users are the indexes of their calendars in `calendars`. The events are sorted by
+/// date, the events without a date last, and keep their first version when the
+/// calendars disagree.
+///
+/// ```rust,no_run
+/// use songkick::SongKick;
+/// use songkick::analysis::merge_calendars;
+/// use songkick::endpoints::CalendarReason;
+///
+/// let sk = SongKick::new("API_KEY");
+/// let friends = ["alice", "bob", "carol"];
+/// let calendars: Vec<_> = friends
+///     .iter()
+///     .map(|user| sk.user.calendar_all(user, CalendarReason::Attendance).unwrap())
+///     .collect();
+/// for entry in merge_calendars(&calendars) {
+///     let going: Vec<&str> = entry.attending().map(|user| friends[user]).collect();
+///     println!("{}: {}", entry.event, going.join(", "));
+/// }
+/// ```
+pub fn merge_calendars(calendars: &[Vec<CalendarEntry>]) -> Vec<MergedEntry<'_>> {
+    let mut merged: Vec<MergedEntry> = Vec::new();
+    let mut index: HashMap<EventId, usize> = HashMap::new();
+    for (user, calendar) in calendars.iter().enumerate() {
+        for entry in calendar {
+            let attendee = Attendee {
+                user,
+                reason: &entry.reason,
+            };
+            match index.get(&entry.event.id) {
+                Some(&i) => {
+                    // A user listing the event twice is attending once
+                    if merged[i].users.iter().all(|known| known.user != user) {
+                        merged[i].users.push(attendee);
+                    }
+                }
+                None => {
+                    index.insert(entry.event.id, merged.len());
+                    merged.push(MergedEntry {
+                        event: &entry.event,
+                        users: vec![attendee],
+                    });
+                }
+            }
+        }
+    }
+    merged.sort_by_key(|entry| {
+        let day = entry.event.start.date.as_ref().and_then(|date| day_number(&date.to_string()));
+        (day.is_none(), day, entry.event.id)
+    });
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::fixtures;
+
+    #[test]
+    fn merge_friends_calendars() {
+        let alice: Vec<CalendarEntry> = fixtures::USER_CALENDAR.load().unwrap().collect();
+        let bob: Vec<CalendarEntry> = alice.iter().skip(1).cloned().collect();
+        let carol: Vec<CalendarEntry> = alice.iter().skip(2).chain(alice.iter().skip(2)).cloned().collect();
+        let calendars = vec![alice, bob, carol];
+        let merged = merge_calendars(&calendars);
+
+        assert_eq!(3, merged.len());
+        let mut users: Vec<(u64, Vec<usize>)> = merged
+            .iter()
+            .map(|entry| (entry.event.id.0, entry.users.iter().map(|user| user.user).collect()))
+            .collect();
+        users.sort();
+        assert_eq!(vec![(26486139, vec![0, 1]), (26486294, vec![0]), (27029514, vec![0, 1, 2])], users);
+
+        let attended = merged.iter().find(|entry| entry.event.id.0 == 27029514).unwrap();
+        assert_eq!(vec![0, 1, 2], attended.attending().collect::<Vec<_>>());
+        assert!(merged.iter().filter(|entry| entry.event.id.0 != 27029514).all(|entry| entry.attending().next().is_none()));
+        assert!(merge_calendars(&[]).is_empty());
+    }
+}
@@ -0,0 +1,217 @@
+use crate::resources::event::Event;
+use crate::util::haversine;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// Smallest cell of a [`Clustering::Grid`], in degrees
+const MIN_CELL: f64 = 1e-6;
+
+/// How [`cluster_events`] groups the events
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum Clustering {
+    /// Events in the same cell of a grid of `cell` degrees of latitude and longitude,
+    /// e.g. the tiles of a map at a zoom level
+    Grid { cell: f64 },
+    /// Events within `radius` meters of the centroid of a cluster, the nearest one
+    Distance { radius: f64 },
+}
+
+/// Events close to each other, see [`cluster_events`]
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct Cluster<'a> {
+    /// Latitude of the centroid of the events
+    pub lat: f64,
+    /// Longitude of the centroid of the events
+    pub lng: f64,
+    /// Events of the cluster, in the order of the set
+    pub events: Vec<&'a Event>,
+}
+
+impl<'a> Cluster<'a> {
+    /// Count of events in the cluster
+    pub fn count(&self) -> usize {
+        self.events.len()
+    }
+
+    fn new(event: &'a Event, (lat, lng): (f64, f64)) -> Cluster<'a> {
+        Cluster {
+            lat,
+            lng,
+            events: vec![event],
+        }
+    }
+
+    /// Add `event` and move the centroid accordingly
+    fn push(&mut self, event: &'a Event, (lat, lng): (f64, f64)) {
+        self.events.push(event);
+        let count = self.events.len() as f64;
+        self.lat += (lat - self.lat) / count;
+        self.lng += (lng - self.lng) / count;
+    }
+}
+
+/// Geographic clusters of a set of events, computed by [`cluster_events`]
+#[derive(Clone, Default)]
+#[non_exhaustive]
+pub struct Clusters<'a> {
+    /// Clusters by decreasing count of events
+    pub clusters: Vec<Cluster<'a>>,
+    /// Events without coordinates, neither for the venue nor for the location
+    pub unlocated: Vec<&'a Event>,
+}
+
+/// Group `events` by the coordinates of their venues, e.g. to aggregate the pins of a map
+///
+/// Events at venues without coordinates are located at the coordinates of their city.
+/// Every event is clustered, cancelled ones included.
+///
+/// ```rust
+/// # #[cfg(feature = "testing")]
+/// # {
+/// use songkick::analysis::{cluster_events, Clustering};
+/// use songkick::testing::fixtures;
+///
+/// let events: Vec<_> = fixtures::ARTIST_CALENDAR.load().unwrap().collect();
+/// // Clusters of about 50 km
+/// let clusters = cluster_events(&events, Clustering::Distance { radius: 50_000.0 });
+/// for cluster in &clusters.clusters {
+///     println!("{} events around {:.2}, {:.2}", cluster.count(), cluster.lat, cluster.lng);
+/// }
+/// # }
+/// ```
+pub fn cluster_events(events: &[Event], clustering: Clustering) -> Clusters<'_> {
+    let mut result = Clusters::default();
+    let located = events.iter().filter_map(|event| match coordinates(event) {
+        Some(point) => Some((event, point)),
+        None => {
+            result.unlocated.push(event);
+            None
+        }
+    });
+
+    let mut clusters = match clustering {
+        Clustering::Grid { cell } => grid(located, cell.max(MIN_CELL)),
+        Clustering::Distance { radius } => by_distance(located, radius),
+    };
+    // Stable, the clusters of a same count stay in the order of their first event
+    clusters.sort_by_key(|cluster| Reverse(cluster.count()));
+    result.clusters = clusters;
+    result
+}
+
+/// Latitude and longitude of the venue of `event`, or else of its location
+fn coordinates(event: &Event) -> Option<(f64, f64)> {
+    match (event.venue.lat, event.venue.lng) {
+        (Some(lat), Some(lng)) => Some((lat, lng)),
+        _ => {
+            let location = event.location.as_ref()?;
+            Some((location.lat?, location.lng?))
+        }
+    }
+}
+
+fn grid<'a, I>(located: I, cell: f64) -> Vec<Cluster<'a>>
+where
+    I: Iterator<Item = (&'a Event, (f64, f64))>,
+{
+    let mut clusters: Vec<Cluster<'a>> = Vec::new();
+    let mut cells: HashMap<(i64, i64), usize> = HashMap::new();
+    for (event, point) in located {
+        let key = ((point.0 / cell).floor() as i64, (point.1 / cell).floor() as i64);
+        match cells.get(&key) {
+            Some(&i) => clusters[i].push(event, point),
+            None => {
+                cells.insert(key, clusters.len());
+                clusters.push(Cluster::new(event, point));
+            }
+        }
+    }
+    clusters
+}
+
+fn by_distance<'a, I>(located: I, radius: f64) -> Vec<Cluster<'a>>
+where
+    I: Iterator<Item = (&'a Event, (f64, f64))>,
+{
+    let mut clusters: Vec<Cluster<'a>> = Vec::new();
+    for (event, point) in located {
+        let nearest = clusters
+            .iter()
+            .enumerate()
+            .map(|(i, cluster)| (i, haversine(cluster.lat, cluster.lng, point.0, point.1)))
+            .filter(|&(_, distance)| distance <= radius)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        match nearest {
+            Some((i, _)) => clusters[i].push(event, point),
+            None => clusters.push(Cluster::new(event, point)),
+        }
+    }
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{EventBuilder, VenueBuilder};
+
+    fn show(id: u64, lat: f64, lng: f64) -> Event {
+        EventBuilder::new()
+            .id(id)
+            .venue(VenueBuilder::new().id(id).coordinates(lat, lng))
+            .build()
+    }
+
+    fn ids(cluster: &Cluster) -> Vec<u64> {
+        cluster.events.iter().map(|event| event.id.0).collect()
+    }
+
+    fn events() -> Vec<Event> {
+        vec![
+            // London
+            show(1, 51.465, -0.115),
+            show(2, 51.503, 0.003),
+            // Paris
+            show(3, 48.838, 2.378),
+            show(4, 51.530, -0.105),
+            show(5, 48.884, 2.337),
+            // Tokyo
+            show(6, 35.682, 139.770),
+            EventBuilder::new().id(7).venue(VenueBuilder::new().unknown()).build(),
+        ]
+    }
+
+    #[test]
+    fn distance_clusters() {
+        let events = events();
+        let clusters = cluster_events(&events, Clustering::Distance { radius: 50_000.0 });
+
+        let found: Vec<Vec<u64>> = clusters.clusters.iter().map(ids).collect();
+        assert_eq!(vec![vec![1, 2, 4], vec![3, 5], vec![6]], found);
+        let london = &clusters.clusters[0];
+        assert_eq!(3, london.count());
+        assert!((london.lat - 51.4993).abs() < 1e-3, "{}", london.lat);
+        assert!((london.lng + 0.0723).abs() < 1e-3, "{}", london.lng);
+        assert_eq!(vec![7], clusters.unlocated.iter().map(|event| event.id.0).collect::<Vec<_>>());
+
+        let clusters = cluster_events(&events, Clustering::Distance { radius: 1_000.0 });
+        assert_eq!(6, clusters.clusters.len());
+    }
+
+    #[test]
+    fn grid_clusters() {
+        let events = events();
+        let clusters = cluster_events(&events, Clustering::Grid { cell: 1.0 });
+        // The cells of 1 degree split London on the Greenwich meridian
+        let found: Vec<Vec<u64>> = clusters.clusters.iter().map(ids).collect();
+        assert_eq!(vec![vec![1, 4], vec![3, 5], vec![2], vec![6]], found);
+        assert!((clusters.clusters[1].lat - 48.861).abs() < 1e-9);
+
+        // West and east of the meridian
+        let clusters = cluster_events(&events, Clustering::Grid { cell: 180.0 });
+        let found: Vec<Vec<u64>> = clusters.clusters.iter().map(ids).collect();
+        assert_eq!(vec![vec![2, 3, 5, 6], vec![1, 4]], found);
+        assert_eq!(1, clusters.unlocated.len());
+    }
+}
@@ -0,0 +1,154 @@
+use crate::analysis::{day_number, Date};
+use crate::resources::event::Event;
+
+/// How two events of a [`Conflict`] clash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConflictKind {
+    /// On the same night in the same city, only one of them can be attended
+    SameCity,
+    /// On the same day in different cities
+    OtherCity,
+}
+
+/// Two events of different sets taking place on a same day, found by [`conflicts`]
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct Conflict<'a> {
+    pub kind: ConflictKind,
+    /// Index of the set of the first event, and the event
+    pub first: (usize, &'a Event),
+    /// Index of the set of the second event, and the event
+    pub second: (usize, &'a Event),
+    /// First day of both events
+    pub date: Date,
+}
+
+/// Event span in days, for the sweep
+struct Span<'a> {
+    start: i64,
+    end: i64,
+    set: usize,
+    event: &'a Event,
+}
+
+/// Clashes between the events of `sets`, e.g. the calendars of several artists
+///
+/// Events clash when their days overlap, multi-day festivals included. Cancelled events
+/// and events without a date are left out, as are events listed in both sets (e.g. two
+/// tracked artists on the same bill): attending one is attending the other. Conflicts
+/// are sorted by date.
+///
+/// ```rust,no_run
+/// use songkick::SongKick;
+/// use songkick::analysis::{conflicts, ConflictKind};
+///
+/// let sk = SongKick::new("API_KEY");
+/// // RadioHead and Placebo IDs
+/// let calendars = vec![sk.artist.calendar_all(253846).unwrap(), sk.artist.calendar_all(324967).unwrap()];
+/// for conflict in conflicts(&calendars) {
+///     if conflict.kind == ConflictKind::SameCity {
+///         println!("{}: {} or {}", conflict.date, conflict.first.1, conflict.second.1);
+///     }
+/// }
+/// ```
+pub fn conflicts<S: AsRef<[Event]>>(sets: &[S]) -> Vec<Conflict<'_>> {
+    let mut spans: Vec<Span> = Vec::new();
+    for (set, events) in sets.iter().enumerate() {
+        for event in events.as_ref().iter().filter(|event| event.status != "cancelled") {
+            let start = match event.start.date.as_ref().and_then(|date| day_number(&date.to_string())) {
+                Some(start) => start,
+                None => continue,
+            };
+            let end = event
+                .end
+                .as_ref()
+                .and_then(|end| end.date.as_ref())
+                .and_then(|date| day_number(&date.to_string()))
+                .map_or(start, |end| end.max(start));
+            spans.push(Span { start, end, set, event });
+        }
+    }
+    spans.sort_by_key(|span| (span.start, span.set, span.event.id));
+
+    let mut conflicts = Vec::new();
+    for (i, first) in spans.iter().enumerate() {
+        for second in spans[i + 1..].iter().take_while(|second| second.start <= first.end) {
+            if first.set == second.set || first.event.id == second.event.id {
+                continue;
+            }
+            let kind = if same_city(first.event, second.event) {
+                ConflictKind::SameCity
+            } else {
+                ConflictKind::OtherCity
+            };
+            conflicts.push(Conflict {
+                kind,
+                first: (first.set, first.event),
+                second: (second.set, second.event),
+                date: second.event.start.date.as_ref().cloned().expect("events without a date are left out"),
+            });
+        }
+    }
+    conflicts.sort_by_key(|conflict| day_number(&conflict.date.to_string()));
+    conflicts
+}
+
+fn same_city(a: &Event, b: &Event) -> bool {
+    match (&a.venue.metro_area, &b.venue.metro_area) {
+        (Some(a), Some(b)) => a.id == b.id,
+        _ => match (&a.location, &b.location) {
+            (Some(a), Some(b)) => a.city == b.city,
+            _ => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{EventBuilder, VenueBuilder};
+
+    fn show(id: u64, date: &str, metro_area: u64) -> EventBuilder {
+        let city = if metro_area == 24426 { "London" } else { "Paris" };
+        EventBuilder::new()
+            .id(id)
+            .date(date)
+            .venue(VenueBuilder::new().id(id).metro_area(metro_area, city, "UK"))
+    }
+
+    #[test]
+    fn same_night_clashes() {
+        let radiohead = vec![
+            show(1, "2030-06-01", 24426).build(),
+            show(2, "2030-06-05", 28909).build(),
+            show(3, "2030-06-09", 24426).build(),
+            show(9, "2030-06-20", 24426).build(),
+        ];
+        let placebo = vec![
+            show(4, "2030-06-01", 24426).build(),
+            show(5, "2030-06-05", 24426).build(),
+            show(6, "2030-06-08", 28909).festival("Rock en Seine", "2030-06-10").build(),
+            show(7, "2030-06-09", 24426).status("cancelled").build(),
+            // Same bill as Radiohead
+            show(9, "2030-06-20", 24426).build(),
+        ];
+        let calendars = [radiohead, placebo];
+        let found = conflicts(&calendars);
+
+        let pairs: Vec<(u64, u64, ConflictKind, String)> = found
+            .iter()
+            .map(|conflict| (conflict.first.1.id.0, conflict.second.1.id.0, conflict.kind, conflict.date.to_string()))
+            .collect();
+        assert_eq!(
+            vec![
+                (1, 4, ConflictKind::SameCity, String::from("2030-06-01")),
+                (2, 5, ConflictKind::OtherCity, String::from("2030-06-05")),
+                (6, 3, ConflictKind::OtherCity, String::from("2030-06-09")),
+            ],
+            pairs
+        );
+        assert_eq!((1, 0), (found[2].first.0, found[2].second.0));
+        assert!(conflicts::<Vec<Event>>(&[]).is_empty());
+    }
+}
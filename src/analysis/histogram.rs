@@ -0,0 +1,214 @@
+use crate::analysis::{civil_date, day_number};
+use crate::resources::event::Event;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Length of the periods of a [`Histogram`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Bucket {
+    /// ISO 8601 weeks, from Monday to Sunday
+    Week,
+    Month,
+    Year,
+}
+
+/// Period of a [`Histogram`], ordered by time
+///
+/// Displays as `2017-W23`, `2017-06` or `2017`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Period {
+    /// ISO 8601 week `week` of the week-numbering year `year`, which can differ from the
+    /// calendar year of its first or last days
+    Week { year: i32, week: u32 },
+    Month { year: i32, month: u32 },
+    Year(i32),
+}
+
+impl Period {
+    /// Period of the `bucket` holding the date `days` from 1970-01-01
+    fn of(bucket: Bucket, days: i64) -> Period {
+        let (year, month, _) = civil_date(days);
+        match bucket {
+            Bucket::Year => Period::Year(year as i32),
+            Bucket::Month => Period::Month {
+                year: year as i32,
+                month,
+            },
+            Bucket::Week => {
+                // The week belongs to the year of its Thursday, 1970-01-01 was a Thursday
+                let thursday = days - (days + 3).rem_euclid(7) + 3;
+                let (year, _, _) = civil_date(thursday);
+                let new_year = day_number(&format!("{:04}-01-01", year)).unwrap_or(thursday);
+                Period::Week {
+                    year: year as i32,
+                    week: ((thursday - new_year) / 7 + 1) as u32,
+                }
+            }
+        }
+    }
+
+    /// The period after this one
+    pub fn next(self) -> Period {
+        match self {
+            Period::Year(year) => Period::Year(year + 1),
+            Period::Month { year, month: 12 } => Period::Month { year: year + 1, month: 1 },
+            Period::Month { year, month } => Period::Month { year, month: month + 1 },
+            Period::Week { .. } => Period::of(Bucket::Week, self.first_day() + 7),
+        }
+    }
+
+    /// Days from 1970-01-01 to the first day of the period
+    fn first_day(self) -> i64 {
+        let date = match self {
+            Period::Year(year) => format!("{:04}-01-01", year),
+            Period::Month { year, month } => format!("{:04}-{:02}-01", year, month),
+            // Week 1 holds January 4
+            Period::Week { year, week } => {
+                let january_4 = day_number(&format!("{:04}-01-04", year)).unwrap_or(0);
+                let monday = january_4 - (january_4 + 3).rem_euclid(7);
+                return monday + 7 * (i64::from(week) - 1);
+            }
+        };
+        day_number(&date).unwrap_or(0)
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Period::Week { year, week } => write!(f, "{:04}-W{:02}", year, week),
+            Period::Month { year, month } => write!(f, "{:04}-{:02}", year, month),
+            Period::Year(year) => write!(f, "{:04}", year),
+        }
+    }
+}
+
+/// Count of events per period, computed by [`histogram`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Histogram {
+    pub bucket: Bucket,
+    /// Count of events per period, for the periods with events
+    pub counts: BTreeMap<Period, usize>,
+    /// Events without a start date, or with a date that is not ISO 8601, left out of the counts
+    pub undated: usize,
+}
+
+impl Histogram {
+    /// Every period from the first to the last one with events, with their counts, 0 included
+    ///
+    /// The bars of a chart of the activity over time.
+    pub fn filled(&self) -> Vec<(Period, usize)> {
+        let (first, last) = match (self.counts.keys().next(), self.counts.keys().next_back()) {
+            (Some(&first), Some(&last)) => (first, last),
+            _ => return Vec::new(),
+        };
+        let mut filled = Vec::new();
+        let mut period = first;
+        while period <= last {
+            filled.push((period, self.counts.get(&period).copied().unwrap_or(0)));
+            period = period.next();
+        }
+        filled
+    }
+
+    /// Count of dated events
+    pub fn dated(&self) -> usize {
+        self.counts.values().sum()
+    }
+}
+
+/// Count of `events` per week, month or year of their start date
+///
+/// Every event is counted, cancelled ones included.
+///
+/// ```rust
+/// # #[cfg(feature = "testing")]
+/// # {
+/// use songkick::analysis::{histogram, Bucket};
+/// use songkick::testing::fixtures;
+///
+/// let events: Vec<_> = fixtures::ARTIST_CALENDAR.load().unwrap().collect();
+/// let histogram = histogram(&events, Bucket::Month);
+/// for (month, count) in histogram.filled() {
+///     println!("{} {}", month, "#".repeat(count));
+/// }
+/// println!("{} events without a date", histogram.undated);
+/// # }
+/// ```
+pub fn histogram(events: &[Event], bucket: Bucket) -> Histogram {
+    let mut histogram = Histogram {
+        bucket,
+        counts: BTreeMap::new(),
+        undated: 0,
+    };
+    for event in events {
+        match event.start.date.as_ref().and_then(|date| day_number(&date.to_string())) {
+            Some(days) => *histogram.counts.entry(Period::of(bucket, days)).or_insert(0) += 1,
+            None => histogram.undated += 1,
+        }
+    }
+    histogram
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::Resource;
+    use crate::testing::{fixtures, EventBuilder};
+    use serde_json::json;
+
+    fn week(date: &str) -> String {
+        Period::of(Bucket::Week, day_number(date).unwrap()).to_string()
+    }
+
+    #[test]
+    fn iso_weeks() {
+        assert_eq!("2017-W23", week("2017-06-06"));
+        // Days of the first and last weeks in the other calendar year
+        assert_eq!("2020-W53", week("2021-01-03"));
+        assert_eq!("2021-W01", week("2021-01-04"));
+        assert_eq!("2020-W01", week("2019-12-30"));
+        assert_eq!("2015-W53", week("2015-12-31"));
+
+        let last = Period::Week { year: 2020, week: 53 };
+        assert_eq!(Period::Week { year: 2021, week: 1 }, last.next());
+        assert_eq!(Period::Week { year: 2020, week: 2 }, Period::Week { year: 2020, week: 1 }.next());
+        assert_eq!(Period::Month { year: 2018, month: 1 }, Period::Month { year: 2017, month: 12 }.next());
+    }
+
+    fn undated(id: u64) -> Event {
+        let mut json = EventBuilder::new().id(id).json();
+        json["start"] = json!({"date": null, "time": null, "datetime": null});
+        Event::from_json(&json).unwrap()
+    }
+
+    #[test]
+    fn monthly_histogram() {
+        let events = vec![
+            EventBuilder::new().id(1).date("2017-06-06").build(),
+            EventBuilder::new().id(2).date("2017-06-28").status("cancelled").build(),
+            EventBuilder::new().id(3).date("2017-09-01").build(),
+            undated(4),
+        ];
+        let histogram = histogram(&events, Bucket::Month);
+        assert_eq!(1, histogram.undated);
+        assert_eq!(3, histogram.dated());
+        let filled: Vec<(String, usize)> =
+            histogram.filled().into_iter().map(|(period, count)| (period.to_string(), count)).collect();
+        let expected = [("2017-06", 2), ("2017-07", 0), ("2017-08", 0), ("2017-09", 1)];
+        assert_eq!(expected.iter().map(|&(p, c)| (String::from(p), c)).collect::<Vec<_>>(), filled);
+    }
+
+    #[test]
+    fn yearly_histogram() {
+        let events: Vec<Event> = fixtures::ARTIST_GIGOGRAPHY.load().unwrap().collect();
+        let histogram = histogram(&events, Bucket::Year);
+        assert_eq!(events.len(), histogram.dated() + histogram.undated);
+        assert!(histogram.counts.contains_key(&Period::Year(1997)));
+        assert_eq!(Some(&(Period::Year(1997), 1)), histogram.filled().first());
+        assert!(super::histogram(&[], Bucket::Week).filled().is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+//! Computations over fetched events, the numbers concert history apps show
+
+mod calendars;
+mod clusters;
+mod conflicts;
+mod histogram;
+mod places;
+mod stats;
+mod tours;
+
+pub use crate::analysis::calendars::{merge_calendars, Attendee, MergedEntry};
+pub use crate::analysis::clusters::{cluster_events, Cluster, Clustering, Clusters};
+pub use crate::analysis::conflicts::{conflicts, Conflict, ConflictKind};
+pub use crate::analysis::histogram::{histogram, Bucket, Histogram, Period};
+pub use crate::analysis::places::{top_places, Ranked, TieBreak, TopOptions, TopPlaces};
+pub use crate::analysis::stats::{stats, Date, GigographyStats};
+pub use crate::analysis::tours::{group_tours, group_tours_with, Tour, DEFAULT_TOUR_GAP};
+
+use crate::resources::event::Event;
+
+/// Statuses of the events that did not take place as listed
+const NOT_HELD: &[&str] = &["cancelled", "postponed"];
+
+/// Whether `event` took place as listed, neither cancelled nor postponed
+pub(crate) fn was_held(event: &Event) -> bool {
+    !NOT_HELD.contains(&event.status.as_str())
+}
+
+/// Days from 1970-01-01 to the ISO 8601 date `date`, e.g. `2017-06-06`
+pub(crate) fn day_number(date: &str) -> Option<i64> {
+    let mut parts = date.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.get(..2)?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    // Days from civil, counting years from March so that leap days end them
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some(era * 146_097 + day_of_era - 719_468)
+}
+
+/// Year, month and day of the date `days` from 1970-01-01, the inverse of [`day_number`]
+pub(crate) fn civil_date(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_numbers() {
+        assert_eq!(Some(0), day_number("1970-01-01"));
+        assert_eq!(Some(17_323), day_number("2017-06-06"));
+        assert_eq!(Some(1), day_number("2000-03-01").zip(day_number("2000-02-29")).map(|(a, b)| a - b));
+        assert_eq!(Some(-1), day_number("1969-12-31"));
+        assert_eq!(None, day_number("2017-13-01"));
+        assert_eq!(None, day_number("June 6, 2017"));
+    }
+
+    #[test]
+    fn civil_dates() {
+        assert_eq!((1970, 1, 1), civil_date(0));
+        assert_eq!((2017, 6, 6), civil_date(17_323));
+        assert_eq!((2000, 2, 29), civil_date(day_number("2000-02-29").unwrap()));
+        assert_eq!((1969, 12, 31), civil_date(-1));
+        for days in (-719_000..800_000).step_by(7) {
+            let (year, month, day) = civil_date(days);
+            assert_eq!(Some(days), day_number(&format!("{:04}-{:02}-{:02}", year, month, day)));
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use crate::analysis::{day_number, was_held, Date};
+use crate::resources::event::Event;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Order of the places of a same count in a [`TopPlaces`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum TieBreak {
+    /// Alphabetical order of the names
+    #[default]
+    Name,
+    /// The places of the most recent shows first
+    MostRecent,
+    /// The places of the earliest shows first
+    Earliest,
+}
+
+/// Options of [`top_places`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TopOptions {
+    min_count: usize,
+    limit: Option<usize>,
+    tie_break: TieBreak,
+}
+
+impl Default for TopOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TopOptions {
+    /// Every place with an event, without limit, ties by name
+    pub fn new() -> TopOptions {
+        TopOptions {
+            min_count: 1,
+            limit: None,
+            tie_break: TieBreak::Name,
+        }
+    }
+
+    /// Leave out the places with fewer than `min_count` events
+    pub fn min_count(mut self, min_count: usize) -> TopOptions {
+        self.min_count = min_count;
+        self
+    }
+
+    /// Keep the first `limit` places of each ranking
+    pub fn limit(mut self, limit: usize) -> TopOptions {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Order of the places of a same count, [`TieBreak::Name`] by default
+    pub fn tie_break(mut self, tie_break: TieBreak) -> TopOptions {
+        self.tie_break = tie_break;
+        self
+    }
+}
+
+/// Place of a ranking of [`TopPlaces`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Ranked {
+    /// Display name of the place
+    pub name: String,
+    /// Count of events at the place
+    pub count: usize,
+    /// Date of the first event at the place
+    pub first: Option<Date>,
+    /// Date of the last event at the place
+    pub last: Option<Date>,
+}
+
+/// Rankings of the places of a set of events, computed by [`top_places`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TopPlaces {
+    /// Venues known to SongKick
+    pub venues: Vec<Ranked>,
+    /// Cities, e.g. `London, UK`
+    pub cities: Vec<Ranked>,
+    /// Countries of the venues
+    pub countries: Vec<Ranked>,
+}
+
+/// Places of `events` by decreasing count of events, e.g. the top venues of a gigography
+///
+/// Cancelled and postponed events are left out.
+///
+/// ```rust,no_run
+/// use songkick::SongKick;
+/// use songkick::analysis::{top_places, TieBreak, TopOptions};
+///
+/// let sk = SongKick::new("API_KEY");
+/// // RadioHead ID
+/// let gigography: Vec<_> = sk.artist.gigography_pages(253846).items().collect::<Result<_, _>>().unwrap();
+/// let top = top_places(&gigography, TopOptions::new().min_count(2).limit(10).tie_break(TieBreak::MostRecent));
+/// for venue in &top.venues {
+///     println!("{} shows at {}", venue.count, venue.name);
+/// }
+/// ```
+pub fn top_places(events: &[Event], options: TopOptions) -> TopPlaces {
+    let mut venues = Counter::default();
+    let mut cities = Counter::default();
+    let mut countries = Counter::default();
+
+    for event in events.iter().filter(|event| was_held(event)) {
+        let date = event.start.date.as_ref();
+        if let (Some(id), Some(name)) = (event.venue.id, event.venue.display_name.as_ref()) {
+            venues.add(id.to_string(), name, date);
+        }
+        if let Some(ref location) = event.location {
+            cities.add(location.city.clone(), &location.city, date);
+        }
+        if let Some(ref metro_area) = event.venue.metro_area {
+            let country = &metro_area.country.display_name;
+            countries.add(country.clone(), country, date);
+        }
+    }
+
+    TopPlaces {
+        venues: venues.ranking(options),
+        cities: cities.ranking(options),
+        countries: countries.ranking(options),
+    }
+}
+
+/// Places counted by key, e.g. the ID of a venue
+#[derive(Default)]
+struct Counter {
+    places: HashMap<String, Ranked>,
+}
+
+impl Counter {
+    fn add(&mut self, key: String, name: &str, date: Option<&Date>) {
+        let place = self.places.entry(key).or_insert_with(|| Ranked {
+            name: String::from(name),
+            count: 0,
+            first: None,
+            last: None,
+        });
+        place.count += 1;
+        if let Some(date) = date {
+            let day = day_number(&date.to_string());
+            if place.first.as_ref().is_none_or(|first| day < day_number(&first.to_string())) {
+                place.first = Some(date.to_owned());
+            }
+            if place.last.as_ref().is_none_or(|last| day > day_number(&last.to_string())) {
+                place.last = Some(date.to_owned());
+            }
+        }
+    }
+
+    fn ranking(self, options: TopOptions) -> Vec<Ranked> {
+        let mut ranking: Vec<Ranked> = self.places.into_values().filter(|place| place.count >= options.min_count).collect();
+        ranking.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| tie_break(a, b, options.tie_break)));
+        if let Some(limit) = options.limit {
+            ranking.truncate(limit);
+        }
+        ranking
+    }
+}
+
+fn tie_break(a: &Ranked, b: &Ranked, tie_break: TieBreak) -> Ordering {
+    let day = |date: &Option<Date>| date.as_ref().and_then(|date| day_number(&date.to_string()));
+    let by_date = match tie_break {
+        TieBreak::Name => Ordering::Equal,
+        // Places without dates last
+        TieBreak::MostRecent => day(&b.last).cmp(&day(&a.last)),
+        TieBreak::Earliest => day(&a.first).map_or(i64::MAX, |day| day).cmp(&day(&b.first).map_or(i64::MAX, |day| day)),
+    };
+    by_date.then_with(|| a.name.cmp(&b.name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{fixtures, EventBuilder, VenueBuilder};
+
+    #[test]
+    fn gigography_places() {
+        let events: Vec<Event> = fixtures::ARTIST_GIGOGRAPHY.load().unwrap().collect();
+        let top = top_places(&events, TopOptions::new());
+
+        assert_eq!(4, top.venues.len());
+        let cities: Vec<(&str, usize)> = top.cities.iter().map(|city| (city.name.as_str(), city.count)).collect();
+        assert_eq!(vec![("Berlin, Germany", 1), ("London, UK", 1), ("Paris, France", 1), ("Reading, UK", 1)], cities);
+        assert_eq!(("UK", 2), (top.countries[0].name.as_str(), top.countries[0].count));
+
+        let top = top_places(&events, TopOptions::new().min_count(2));
+        assert!(top.venues.is_empty());
+        assert_eq!(1, top.countries.len());
+    }
+
+    #[test]
+    fn tie_breaks() {
+        let show = |id: u64, venue: u64, name: &str, date: &str| {
+            EventBuilder::new()
+                .id(id)
+                .date(date)
+                .venue(VenueBuilder::new().id(venue).name(name))
+                .build()
+        };
+        let events = vec![
+            show(1, 10, "Zenith", "2010-03-01"),
+            show(2, 10, "Zenith", "2011-03-01"),
+            show(3, 11, "Brixton Academy", "2012-03-01"),
+            show(4, 12, "Olympia", "2008-03-01"),
+            show(5, 11, "Brixton Academy", "2009-03-01"),
+            show(6, 13, "Ahoy", "2013-03-01"),
+        ];
+        let names = |tie_break, limit| -> Vec<String> {
+            let options = TopOptions::new().tie_break(tie_break).limit(limit);
+            top_places(&events, options).venues.into_iter().map(|venue| venue.name).collect()
+        };
+
+        assert_eq!(vec!["Brixton Academy", "Zenith", "Ahoy", "Olympia"], names(TieBreak::Name, 4));
+        assert_eq!(vec!["Brixton Academy", "Zenith", "Ahoy"], names(TieBreak::MostRecent, 3));
+        assert_eq!(vec!["Brixton Academy", "Zenith", "Olympia", "Ahoy"], names(TieBreak::Earliest, 4));
+        let brixton = &top_places(&events, TopOptions::new()).venues[0];
+        assert_eq!(Some("2009-03-01"), brixton.first.as_ref().map(|date| date.to_string()).as_deref());
+        assert_eq!(Some("2012-03-01"), brixton.last.as_ref().map(|date| date.to_string()).as_deref());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::analysis::was_held;
+use crate::resources::event::Event;
+use std::collections::{BTreeMap, HashSet};
+
+/// Date of an Event, as returned by SongKick (e.g. `2016-10-16`)
+pub type Date = String;
+
+/// Summary of a gigography, computed by [`stats`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GigographyStats {
+    /// Events that took place
+    pub events: usize,
+    /// Events cancelled or postponed, left out of the other numbers
+    pub not_held: usize,
+    /// Count of events per year, for the events with a date
+    pub per_year: BTreeMap<i32, usize>,
+    /// Distinct venues, for the venues known to SongKick
+    pub venues: usize,
+    /// Distinct cities (e.g. `London, UK`)
+    pub cities: usize,
+    /// Distinct countries
+    pub countries: usize,
+    /// Date of the first show
+    pub first: Option<Date>,
+    /// Date of the last show
+    pub last: Option<Date>,
+}
+
+/// Statistics of `events`, e.g. a gigography
+///
+/// ```rust,no_run
+/// use songkick::SongKick;
+/// use songkick::analysis;
+///
+/// let sk = SongKick::new("API_KEY");
+/// // RadioHead ID
+/// let gigography: Vec<_> = sk.artist.gigography_pages(253846).items().collect::<Result<_, _>>().unwrap();
+/// let stats = analysis::stats(&gigography);
+/// for (year, count) in &stats.per_year {
+///     println!("{}: {} shows", year, count);
+/// }
+/// println!("{} venues in {} countries", stats.venues, stats.countries);
+/// ```
+pub fn stats(events: &[Event]) -> GigographyStats {
+    let mut stats = GigographyStats::default();
+    let mut venues = HashSet::new();
+    let mut cities = HashSet::new();
+    let mut countries = HashSet::new();
+    let (mut first, mut last) = (None, None);
+
+    for event in events {
+        if !was_held(event) {
+            stats.not_held += 1;
+            continue;
+        }
+        stats.events += 1;
+
+        if let Some(ref date) = event.start.date {
+            if let Some(year) = year(&date.to_string()) {
+                *stats.per_year.entry(year).or_insert(0) += 1;
+            }
+            first = first.min(Some(date)).or(Some(date));
+            last = last.max(Some(date));
+        }
+        if let Some(id) = event.venue.id {
+            venues.insert(id);
+        }
+        if let Some(ref location) = event.location {
+            cities.insert(location.city.as_str());
+        }
+        if let Some(ref metro_area) = event.venue.metro_area {
+            countries.insert(metro_area.country.display_name.as_str());
+        }
+    }
+
+    stats.venues = venues.len();
+    stats.cities = cities.len();
+    stats.countries = countries.len();
+    stats.first = first.cloned();
+    stats.last = last.cloned();
+    stats
+}
+
+/// Year of an ISO 8601 date, e.g. `2017-06-06`
+fn year(date: &str) -> Option<i32> {
+    date.split('-').next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::fixtures;
+
+    #[test]
+    fn gigography_stats() {
+        let events: Vec<Event> = fixtures::ARTIST_GIGOGRAPHY.load().unwrap().collect();
+        let stats = stats(&events);
+
+        assert_eq!(4, stats.events);
+        assert_eq!(2, stats.not_held);
+        let per_year: Vec<(i32, usize)> = stats.per_year.into_iter().collect();
+        assert_eq!(vec![(1997, 1), (1999, 1), (2003, 1), (2009, 1)], per_year);
+        assert_eq!(4, stats.venues);
+        assert_eq!(4, stats.cities);
+        assert_eq!(3, stats.countries);
+        assert_eq!(Some("1997-03-11"), stats.first.map(|date| date.to_string()).as_deref());
+        assert_eq!(Some("2009-12-06"), stats.last.map(|date| date.to_string()).as_deref());
+    }
+
+    #[test]
+    fn empty_stats() {
+        assert_eq!(GigographyStats::default(), stats(&[]));
+    }
+}
@@ -0,0 +1,173 @@
+use crate::analysis::{day_number, Date};
+use crate::resources::event::Event;
+use std::collections::HashMap;
+
+/// Default longest gap between two shows of a tour leg, in days
+pub const DEFAULT_TOUR_GAP: i64 = 21;
+
+/// Consecutive shows grouped by [`group_tours`]
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct Tour<'a> {
+    /// Guessed name, a tour name found in the names of the events or the headliner and the years
+    pub name_guess: String,
+    /// Events of the leg, by date
+    pub events: Vec<&'a Event>,
+    /// First and last days of the leg
+    pub date_range: (Date, Date),
+}
+
+/// Tour legs of `events`, e.g. a gigography, split by gaps of more than [`DEFAULT_TOUR_GAP`] days
+///
+/// See [`group_tours_with`].
+pub fn group_tours(events: &[Event]) -> Vec<Tour<'_>> {
+    group_tours_with(events, DEFAULT_TOUR_GAP)
+}
+
+/// Tour legs of `events`, split by gaps of more than `max_gap` days between two shows
+///
+/// Cancelled events and events without a date are left out. A leg also ends before a
+/// show billed under another tour name (e.g. `Meds Tour` then `Battle for the Sun Tour`),
+/// festivals are never billed under one and don't split the legs.
+///
+/// ```rust
+/// # #[cfg(feature = "testing")]
+/// # {
+/// use songkick::analysis::group_tours;
+/// use songkick::resources::Event;
+/// use songkick::testing::fixtures;
+///
+/// let events: Vec<Event> = fixtures::ARTIST_GIGOGRAPHY.load().unwrap().collect();
+/// for tour in group_tours(&events) {
+///     println!("{}: {} shows from {}", tour.name_guess, tour.events.len(), tour.date_range.0);
+/// }
+/// # }
+/// ```
+pub fn group_tours_with(events: &[Event], max_gap: i64) -> Vec<Tour<'_>> {
+    let mut dated: Vec<(i64, &Event)> = events
+        .iter()
+        .filter(|event| event.status != "cancelled")
+        .filter_map(|event| Some((day_number(&event.start.date.as_ref()?.to_string())?, event)))
+        .collect();
+    dated.sort_by_key(|&(day, event)| (day, event.id));
+
+    let mut legs: Vec<Vec<(i64, &Event)>> = Vec::new();
+    let mut leg_name: Option<String> = None;
+    for (day, event) in dated {
+        let name = tour_name(event);
+        let split = match legs.last().and_then(|leg| leg.last()) {
+            Some(&(previous, _)) => {
+                day - previous > max_gap || matches!((&leg_name, &name), (Some(a), Some(b)) if a != b)
+            }
+            None => true,
+        };
+        if split {
+            legs.push(Vec::new());
+            leg_name = None;
+        }
+        if name.is_some() {
+            leg_name = name;
+        }
+        legs.last_mut().expect("a leg was just pushed").push((day, event));
+    }
+
+    legs.into_iter().map(|leg| tour(leg.into_iter().map(|(_, event)| event).collect())).collect()
+}
+
+fn tour(events: Vec<&Event>) -> Tour<'_> {
+    let date = |event: &Event| event.start.date.as_ref().cloned().expect("events without a date are filtered out");
+    let date_range = (date(events[0]), date(events[events.len() - 1]));
+
+    let mut names: HashMap<String, usize> = HashMap::new();
+    for name in events.iter().filter_map(|event| tour_name(event)) {
+        *names.entry(name).or_insert(0) += 1;
+    }
+    let name_guess = match names.into_iter().max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0))) {
+        Some((name, _)) => name,
+        None => {
+            let headliner = events
+                .iter()
+                .find_map(|event| event.performances.iter().find(|performance| performance.billing == "headline"))
+                .map_or("Tour", |performance| performance.artist.display_name.as_str());
+            let (first, last) = (year(&date_range.0), year(&date_range.1));
+            if first == last {
+                format!("{} {}", headliner, first)
+            } else {
+                format!("{} {}-{}", headliner, first, last)
+            }
+        }
+    };
+
+    Tour {
+        name_guess,
+        events,
+        date_range,
+    }
+}
+
+/// Tour in the name of a concert, e.g. `Meds Tour` in `Placebo - Meds Tour at Brixton Academy (June 3, 2006)`
+fn tour_name(event: &Event) -> Option<String> {
+    if event.event_type == "Festival" {
+        return None;
+    }
+    // Drop the venue and the date SongKick appends
+    let name = event.display_name.split(" at ").next()?;
+    name.rsplit([':', '-', '|', '\u{2013}', '\u{2014}'].as_ref())
+        .map(str::trim)
+        .find(|part| part.split_whitespace().any(|word| word.eq_ignore_ascii_case("tour")))
+        .map(String::from)
+}
+
+fn year(date: &Date) -> String {
+    date.to_string().chars().take(4).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{ArtistBuilder, EventBuilder};
+
+    fn show(id: u64, name: &str, date: &str) -> Event {
+        EventBuilder::new()
+            .id(id)
+            .display_name(name)
+            .date(date)
+            .headliner(ArtistBuilder::new().name("Placebo"))
+            .build()
+    }
+
+    #[test]
+    fn split_by_gaps_and_names() {
+        let events = vec![
+            show(4, "Placebo - Meds Tour at Brixton Academy (June 3, 2006)", "2006-06-03"),
+            show(2, "Placebo at Zenith (May 20, 2006)", "2006-05-20"),
+            show(3, "Placebo at Brixton Academy (June 2, 2006)", "2006-06-02"),
+            show(5, "Placebo: Battle for the Sun Tour at Olympia (June 10, 2006)", "2006-06-10"),
+            // Months later
+            show(6, "Placebo at Wembley Arena (December 20, 2006)", "2006-12-20"),
+            show(7, "Placebo at Ahoy (January 5, 2007)", "2007-01-05"),
+            EventBuilder::new().id(8).status("cancelled").date("2006-06-04").build(),
+        ];
+        let tours = group_tours(&events);
+
+        assert_eq!(3, tours.len());
+        assert_eq!("Meds Tour", tours[0].name_guess);
+        let ids: Vec<u64> = tours[0].events.iter().map(|event| event.id.0).collect();
+        assert_eq!(vec![2, 3, 4], ids);
+        assert_eq!("2006-05-20", tours[0].date_range.0.to_string());
+        assert_eq!("2006-06-03", tours[0].date_range.1.to_string());
+        assert_eq!("Battle for the Sun Tour", tours[1].name_guess);
+        assert_eq!(1, tours[1].events.len());
+        assert_eq!("Placebo 2006-2007", tours[2].name_guess);
+        assert_eq!(2, tours[2].events.len());
+    }
+
+    #[test]
+    fn gigography_tours() {
+        let events: Vec<Event> = crate::testing::fixtures::ARTIST_GIGOGRAPHY.load().unwrap().collect();
+        let tours = group_tours_with(&events, 3650);
+        assert_eq!(1, tours.len());
+        assert_eq!(5, tours[0].events.len());
+        assert!(group_tours(&[]).is_empty());
+    }
+}
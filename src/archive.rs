@@ -0,0 +1,346 @@
+//! Complete backups of the concert history of artists
+//!
+//! An [`ArchiveExporter`] downloads, for each of its artists, the artist record, the whole
+//! gigography and the upcoming calendar, then the full record of every venue of these
+//! events, with their address and capacity. They are written with a [`Manifest`] to a
+//! directory:
+//!
+//! ```text
+//! manifest.json
+//! artists/253846.json
+//! artists/253846/gigography.json
+//! artists/253846/calendar.json
+//! venues/17522.json
+//! ```
+//!
+//! or to a single JSON file holding the same documents, see [`ArchiveExporter::export_file`].
+//!
+//! ```rust,no_run
+//! use songkick::SongKick;
+//! use songkick::archive::ArchiveExporter;
+//!
+//! let sk = SongKick::new("API_KEY");
+//! // RadioHead and Placebo IDs
+//! let manifest = ArchiveExporter::new(&sk).artist(253846).artist(324967).export("backup").unwrap();
+//! for artist in &manifest.artists {
+//!     println!("{}: {} past events", artist.name, artist.gigography);
+//! }
+//! ```
+
+use crate::endpoints::all_pages;
+use crate::error::SkError;
+use crate::export::json::Format;
+use crate::resources::artist::Artist;
+use crate::resources::event::Event;
+use crate::resources::ids::{ArtistId, VenueId};
+use crate::resources::venue::Venue;
+use crate::{SkResult, SongKick};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Version of the layout of the archives, bumped on incompatible changes
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// Name of the manifest in an archive directory
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Content of an archive, written to `manifest.json`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct Manifest {
+    /// [`ARCHIVE_VERSION`] of the archive
+    pub version: u32,
+    /// Time of the export, in seconds since the Unix epoch
+    pub exported_at: u64,
+    /// Archived artists, in the order they were added
+    pub artists: Vec<ArchivedArtist>,
+    /// Venues archived with their full record
+    pub venues: Vec<VenueId>,
+    /// Venues of the events unknown to the API, only their summary in the events is archived
+    pub missing_venues: Vec<VenueId>,
+    /// Files of the archive directory, relative to it, the manifest excluded
+    pub files: Vec<String>,
+}
+
+/// Artist of a [`Manifest`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct ArchivedArtist {
+    pub id: ArtistId,
+    pub name: String,
+    /// Count of past events
+    pub gigography: usize,
+    /// Count of upcoming events
+    pub calendar: usize,
+}
+
+/// Downloaded records of an artist
+struct ArtistRecords {
+    artist: Artist,
+    gigography: Vec<Event>,
+    calendar: Vec<Event>,
+}
+
+/// Downloaded records of an archive
+struct Records {
+    artists: Vec<ArtistRecords>,
+    venues: BTreeMap<VenueId, Venue>,
+    missing_venues: Vec<VenueId>,
+}
+
+/// Single archive file of [`ArchiveExporter::export_file`]
+#[derive(Serialize)]
+struct ArchiveFile<'a> {
+    manifest: &'a Manifest,
+    artists: Vec<ArchiveFileArtist<'a>>,
+    venues: Vec<&'a Venue>,
+}
+
+#[derive(Serialize)]
+struct ArchiveFileArtist<'a> {
+    artist: &'a Artist,
+    gigography: &'a [Event],
+    calendar: &'a [Event],
+}
+
+/// Exporter of the complete records of artists, see the [module](self) documentation
+pub struct ArchiveExporter<'a> {
+    sk: &'a SongKick,
+    artists: Vec<ArtistId>,
+    format: Format,
+}
+
+impl<'a> ArchiveExporter<'a> {
+    /// Exporter without artists, fetching through `sk`
+    pub fn new(sk: &'a SongKick) -> ArchiveExporter<'a> {
+        ArchiveExporter {
+            sk,
+            artists: Vec::new(),
+            format: Format::Pretty,
+        }
+    }
+
+    /// Archive the artist
+    pub fn artist<I: Into<ArtistId>>(mut self, id: I) -> ArchiveExporter<'a> {
+        let id = id.into();
+        if !self.artists.contains(&id) {
+            self.artists.push(id);
+        }
+        self
+    }
+
+    /// Archive the artists
+    pub fn artists<I, T>(self, ids: I) -> ArchiveExporter<'a>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<ArtistId>,
+    {
+        ids.into_iter().fold(self, ArchiveExporter::artist)
+    }
+
+    /// Layout of the written JSON, [`Format::Pretty`] by default
+    pub fn format(mut self, format: Format) -> ArchiveExporter<'a> {
+        self.format = format;
+        self
+    }
+
+    /// Download the records and write them to the directory at `dir`, created if missing
+    ///
+    /// Files of a previous export to the same directory are replaced.
+    pub fn export<P: AsRef<Path>>(&self, dir: P) -> SkResult<Manifest> {
+        let dir = dir.as_ref();
+        let records = self.download()?;
+        let mut manifest = self.manifest(&records);
+
+        fs::create_dir_all(dir.join("artists"))?;
+        for records in &records.artists {
+            let id = records.artist.id;
+            fs::create_dir_all(dir.join("artists").join(id.to_string()))?;
+            manifest.files.push(self.write(dir, &format!("artists/{}.json", id), &records.artist)?);
+            manifest.files.push(self.write(dir, &format!("artists/{}/gigography.json", id), &records.gigography)?);
+            manifest.files.push(self.write(dir, &format!("artists/{}/calendar.json", id), &records.calendar)?);
+        }
+        if !records.venues.is_empty() {
+            fs::create_dir_all(dir.join("venues"))?;
+        }
+        for (id, venue) in &records.venues {
+            manifest.files.push(self.write(dir, &format!("venues/{}.json", id), venue)?);
+        }
+        self.write(dir, MANIFEST_FILE, &manifest)?;
+        Ok(manifest)
+    }
+
+    /// Download the records and write them to the single JSON file at `path`
+    ///
+    /// The file is an object with the `manifest`, the `artists` with their `gigography`
+    /// and `calendar`, and the `venues`.
+    pub fn export_file<P: AsRef<Path>>(&self, path: P) -> SkResult<Manifest> {
+        let records = self.download()?;
+        let manifest = self.manifest(&records);
+        let archive = ArchiveFile {
+            manifest: &manifest,
+            artists: records
+                .artists
+                .iter()
+                .map(|records| ArchiveFileArtist {
+                    artist: &records.artist,
+                    gigography: &records.gigography,
+                    calendar: &records.calendar,
+                })
+                .collect(),
+            venues: records.venues.values().collect(),
+        };
+        write_document(path.as_ref(), &archive, self.format)?;
+        Ok(manifest)
+    }
+
+    fn download(&self) -> SkResult<Records> {
+        let mut artists = Vec::with_capacity(self.artists.len());
+        for &id in &self.artists {
+            let artist = self
+                .sk
+                .artist
+                .get(id)?
+                .next()
+                .ok_or_else(|| SkError::decode(format!("No artist in the response for {}", id)))?;
+            artists.push(ArtistRecords {
+                artist,
+                gigography: all_pages(|options| self.sk.artist.gigography(id, Some(options)))?,
+                calendar: self.sk.artist.calendar_all(id)?,
+            });
+        }
+
+        let mut venues = BTreeMap::new();
+        let mut missing_venues = Vec::new();
+        let ids = artists
+            .iter()
+            .flat_map(|records| records.gigography.iter().chain(&records.calendar))
+            .filter_map(|event| event.venue.id);
+        for id in ids {
+            if venues.contains_key(&id) || missing_venues.contains(&id) {
+                continue;
+            }
+            match self.sk.venue.get(id) {
+                Ok(mut venue) => match venue.next() {
+                    Some(venue) => {
+                        venues.insert(id, venue);
+                    }
+                    None => missing_venues.push(id),
+                },
+                Err(ref err) if matches!(err.inner(), SkError::NotFound { .. }) => missing_venues.push(id),
+                Err(err) => return Err(err),
+            }
+        }
+        missing_venues.sort();
+
+        Ok(Records {
+            artists,
+            venues,
+            missing_venues,
+        })
+    }
+
+    fn manifest(&self, records: &Records) -> Manifest {
+        Manifest {
+            version: ARCHIVE_VERSION,
+            exported_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+            artists: records
+                .artists
+                .iter()
+                .map(|records| ArchivedArtist {
+                    id: records.artist.id,
+                    name: records.artist.display_name.clone(),
+                    gigography: records.gigography.len(),
+                    calendar: records.calendar.len(),
+                })
+                .collect(),
+            venues: records.venues.keys().copied().collect(),
+            missing_venues: records.missing_venues.clone(),
+            files: Vec::new(),
+        }
+    }
+
+    /// Write `value` to `name` in `dir`, returns `name`
+    fn write<T: Serialize + ?Sized>(&self, dir: &Path, name: &str, value: &T) -> SkResult<String> {
+        write_document(&dir.join(name), value, self.format)?;
+        Ok(String::from(name))
+    }
+}
+
+fn write_document<T: Serialize + ?Sized>(path: &Path, value: &T, format: Format) -> SkResult<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    match format {
+        Format::Pretty => serde_json::to_writer_pretty(&mut writer, value),
+        Format::Compact => serde_json::to_writer(&mut writer, value),
+    }
+    .map_err(io::Error::from)?;
+    writer.write_all(b"\n")?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockSongKick;
+    use crate::transport::HttpResponse;
+    use serde_json::Value;
+    use std::env;
+
+    fn read(path: &Path) -> Value {
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn export_directory() {
+        let server = MockSongKick::start();
+        let sk = server.client();
+        let dir = env::temp_dir().join(format!("songkick-archive-{}", std::process::id()));
+
+        let manifest = ArchiveExporter::new(&sk).artists(vec![324967, 324967]).export(&dir).unwrap();
+        assert_eq!(1, manifest.artists.len());
+        assert_eq!("Placebo", manifest.artists[0].name);
+        assert_eq!(33, manifest.artists[0].gigography);
+        assert_eq!(33, manifest.artists[0].calendar);
+        assert!(!manifest.venues.is_empty());
+        assert!(manifest.missing_venues.is_empty());
+        assert_eq!(3 + manifest.venues.len(), manifest.files.len());
+
+        let written = read(&dir.join(MANIFEST_FILE));
+        assert_eq!(ARCHIVE_VERSION as u64, written["version"]);
+        assert_eq!(manifest.files.len(), written["files"].as_array().unwrap().len());
+        for file in &manifest.files {
+            assert!(dir.join(file).is_file(), "{}", file);
+        }
+        assert_eq!(33, read(&dir.join("artists/324967/gigography.json")).as_array().unwrap().len());
+        // The server answers every venue with the Brixton Academy
+        let venue = read(&dir.join(format!("venues/{}.json", manifest.venues[0])));
+        assert_eq!(4921, venue["capacity"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn export_single_file() {
+        let server = MockSongKick::start();
+        server.on("venues/*.json", HttpResponse::new(404, ""));
+        let sk = server.client();
+        let path = env::temp_dir().join(format!("songkick-archive-{}.json", std::process::id()));
+
+        let manifest = ArchiveExporter::new(&sk).artist(324967).format(Format::Compact).export_file(&path).unwrap();
+        assert!(manifest.venues.is_empty());
+        assert!(!manifest.missing_venues.is_empty());
+        assert!(manifest.files.is_empty());
+
+        let written = read(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!("Placebo", written["manifest"]["artists"][0]["name"]);
+        assert_eq!(33, written["artists"][0]["calendar"].as_array().unwrap().len());
+        assert_eq!(0, written["venues"].as_array().unwrap().len());
+    }
+}
@@ -0,0 +1,99 @@
+//! Defaults of the command line options, read from a TOML file
+//!
+//! The file is `$XDG_CONFIG_HOME/songkick/config.toml`, falling back to
+//! `~/.config/songkick/config.toml`, unless another path is given with `--config`:
+//!
+//! ```toml
+//! api_key = "API_KEY"
+//! # London ID
+//! metro_area = 24426
+//! page_size = 25
+//! output = "jsonl"
+//! ```
+//!
+//! Options given on the command line come first, then the environment variables,
+//! then the file.
+
+use crate::Output;
+use serde::Deserialize;
+use songkick::{SkError, SkResult};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Content of the configuration file, every entry is optional
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// SongKick API key
+    pub api_key: Option<String>,
+    /// Metro area of the commands taking one, when none is given
+    pub metro_area: Option<u64>,
+    /// Number of results fetched per page
+    pub page_size: Option<u64>,
+    /// Format of the results
+    pub output: Option<Output>,
+}
+
+impl Config {
+    /// Configuration in the file at `path`, or the default path
+    ///
+    /// A missing file at the default path is an empty configuration.
+    pub fn load(path: Option<&Path>) -> SkResult<Config> {
+        match path {
+            Some(path) => Config::read(path),
+            None => match default_path() {
+                Some(path) if path.is_file() => Config::read(&path),
+                _ => Ok(Config::default()),
+            },
+        }
+    }
+
+    fn read(path: &Path) -> SkResult<Config> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))?;
+        Config::parse(&content)
+            .map_err(|err| SkError::InvalidInput(format!("invalid configuration {}: {}", path.display(), err)))
+    }
+
+    fn parse(content: &str) -> Result<Config, toml::de::Error> {
+        toml::from_str(content)
+    }
+}
+
+/// `songkick/config.toml` in the configuration directory of the user
+fn default_path() -> Option<PathBuf> {
+    let dir = match env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(dir.join("songkick").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_config() {
+        let config = Config::parse("api_key = \"KEY\"\nmetro_area = 24426\npage_size = 25\noutput = \"jsonl\"\n").unwrap();
+        assert_eq!(
+            Config {
+                api_key: Some(String::from("KEY")),
+                metro_area: Some(24426),
+                page_size: Some(25),
+                output: Some(Output::Jsonl),
+            },
+            config
+        );
+        assert_eq!(Config::default(), Config::parse("").unwrap());
+    }
+
+    #[test]
+    fn reject_invalid_config() {
+        assert!(Config::parse("output = \"yaml\"").is_err());
+        assert!(Config::parse("apikey = \"KEY\"").is_err());
+        assert!(Config::parse("metro_area = \"London\"").is_err());
+    }
+}
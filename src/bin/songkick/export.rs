@@ -0,0 +1,191 @@
+//! `songkick export`, writing the events of many artists to a directory
+//!
+//! Each artist gets an `<id>.jsonl` file of events and an `<id>.progress.json` file
+//! recording the pages written so far. Running the same export again skips the
+//! completed artists and resumes the others after their last written page.
+
+use crate::watch::parse_interval;
+use clap::{Args, Subcommand, ValueHint};
+use serde::{Deserialize, Serialize};
+use songkick::endpoints::PageState;
+use songkick::export::jsonl;
+use songkick::resources::ArtistId;
+use songkick::{SkError, SkResult, SongKick};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+/// Wait after a rate-limited request without `Retry-After`
+const RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Subcommand)]
+pub enum ExportCommand {
+    /// Past events of many artists, one file per artist
+    Gigography(GigographyArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct GigographyArgs {
+    /// File of artist IDs or songkick.com artist URLs, one per line, `#` starting a comment
+    #[arg(long, value_hint = ValueHint::FilePath)]
+    artist_ids: PathBuf,
+    /// Directory of the exported files, created if missing
+    #[arg(long, value_hint = ValueHint::DirPath)]
+    out: PathBuf,
+    /// Time between two requests
+    #[arg(long, default_value = "1s", value_parser = parse_interval)]
+    pause: Duration,
+}
+
+/// Content of the progress file of an artist
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Progress {
+    /// Pages written so far, none before the first one
+    state: Option<PageState>,
+    /// Length of the events file after the last written page
+    bytes: u64,
+}
+
+/// Export the gigographies of the artists of `args`
+///
+/// Artists failing with an error other than a rate limit are reported and skipped,
+/// the first such error is returned once the other artists are exported.
+pub fn gigography(sk: &SongKick, args: &GigographyArgs, page_size: Option<u64>) -> SkResult<()> {
+    let ids = read_ids(&args.artist_ids)?;
+    fs::create_dir_all(&args.out)?;
+    let mut first_error = None;
+    for id in ids {
+        match export_artist(sk, id, args, page_size) {
+            Ok(Some(events)) => eprintln!("artist {}: {} events", id, events),
+            Ok(None) => eprintln!("artist {}: already exported", id),
+            Err(err) => {
+                eprintln!("artist {}: {}", id, crate::report(&err));
+                first_error.get_or_insert(err);
+            }
+        }
+    }
+    first_error.map_or(Ok(()), Err)
+}
+
+/// Export the gigography of artist `id`, returns its number of events or none when it was complete
+fn export_artist(sk: &SongKick, id: ArtistId, args: &GigographyArgs, page_size: Option<u64>) -> SkResult<Option<u64>> {
+    let events_path = args.out.join(format!("{}.jsonl", id));
+    let progress_path = args.out.join(format!("{}.progress.json", id));
+    let mut progress = read_progress(&progress_path)?;
+    if progress.state.is_some_and(|state| state.complete) {
+        return Ok(None);
+    }
+
+    // Drop what was written after the last recorded page
+    let mut file = OpenOptions::new().create(true).write(true).truncate(false).open(&events_path)?;
+    file.set_len(progress.bytes)?;
+    file.seek(SeekFrom::End(0))?;
+
+    let mut first = true;
+    loop {
+        let mut pages = sk.artist.gigography_pages(id);
+        if let Some(page_size) = page_size {
+            pages = pages.per_page(page_size);
+        }
+        if let Some(state) = progress.state {
+            pages = pages.resume(state);
+        }
+        loop {
+            if !first {
+                thread::sleep(args.pause);
+            }
+            first = false;
+            match pages.next() {
+                None => return Ok(Some(pages.state().fetched)),
+                Some(Ok(page)) => {
+                    jsonl::write_jsonl(BufWriter::new(&file), page)?;
+                    file.sync_data()?;
+                    progress = Progress {
+                        state: Some(pages.state()),
+                        bytes: file.stream_position()?,
+                    };
+                    write_progress(&progress_path, &progress)?;
+                }
+                Some(Err(err)) => match *err.inner() {
+                    SkError::RateLimited { retry_after, .. } => {
+                        let wait = retry_after.unwrap_or(RATE_LIMIT_WAIT);
+                        eprintln!("artist {}: rate limited, retrying in {}s", id, wait.as_secs());
+                        thread::sleep(wait);
+                        break;
+                    }
+                    _ => return Err(err),
+                },
+            }
+        }
+    }
+}
+
+fn read_progress(path: &Path) -> SkResult<Progress> {
+    if !path.is_file() {
+        return Ok(Progress { state: None, bytes: 0 });
+    }
+    serde_json::from_reader(File::open(path)?)
+        .map_err(|err| SkError::InvalidInput(format!("invalid progress file {}: {}", path.display(), err)))
+}
+
+/// Replace the progress file at `path`, through a temporary file so that it is never half written
+fn write_progress(path: &Path, progress: &Progress) -> SkResult<()> {
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec(progress)?)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+fn read_ids(path: &Path) -> SkResult<Vec<ArtistId>> {
+    parse_ids(&fs::read_to_string(path)?)
+        .map_err(|err| SkError::InvalidInput(format!("{}:{}", path.display(), err)))
+}
+
+/// Artist IDs of the lines of `content`, the error is the first invalid line and its number
+fn parse_ids(content: &str) -> Result<Vec<ArtistId>, String> {
+    let mut ids = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let id = line
+            .parse()
+            .ok()
+            .map(ArtistId)
+            .or_else(|| ArtistId::from_songkick_url(line))
+            .ok_or_else(|| format!("{}: expected an artist ID or URL, found `{}`", number + 1, line))?;
+        ids.push(id);
+    }
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_artist_ids() {
+        let ids = parse_ids("253846\n\n# Placebo\nhttps://www.songkick.com/artists/324967-placebo # URL\n").unwrap();
+        assert_eq!(vec![ArtistId(253846), ArtistId(324967)], ids);
+        assert_eq!(Err(String::from("2: expected an artist ID or URL, found `Radiohead`")), parse_ids("1\nRadiohead"));
+    }
+
+    #[test]
+    fn progress_round_trip() {
+        let path = std::env::temp_dir().join(format!("songkick-progress-{}.json", std::process::id()));
+        assert_eq!(Progress { state: None, bytes: 0 }, read_progress(&path).unwrap());
+
+        let state = serde_json::from_str(r#"{"page": 3, "per_page": 50, "fetched": 100, "complete": false}"#).unwrap();
+        let progress = Progress {
+            state: Some(state),
+            bytes: 4096,
+        };
+        write_progress(&path, &progress).unwrap();
+        let read = read_progress(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(progress, read);
+    }
+}
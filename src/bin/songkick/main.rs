@@ -0,0 +1,353 @@
+//! Command line client of the SongKick API
+//!
+//! Each subcommand is a thin layer over a call of the library, e.g. `songkick artist calendar 253846`
+//! iterates `sk.artist.calendar_pages(253846).items()`.
+
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum, ValueHint};
+use clap_complete::Shell;
+use config::Config;
+use table::Tabular;
+use serde::{Deserialize, Serialize};
+use songkick::endpoints::{CalendarReason, Pages};
+use songkick::export::json::{self, Format};
+use songkick::export::jsonl;
+use songkick::resources::{CalendarEntry, TopLevelResource};
+use songkick::{SkError, SkResult, SongKick};
+use std::error::Error;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+mod config;
+mod export;
+mod table;
+mod watch;
+
+/// Environment variable holding the API key
+const API_KEY_ENV: &str = "SONGKICK_API_KEY";
+/// Environment variable holding the default metro area
+const METRO_AREA_ENV: &str = "SONGKICK_METRO_AREA";
+
+#[derive(Parser)]
+#[command(name = "songkick", version, about = "Query the SongKick API")]
+struct Cli {
+    /// SongKick API key
+    #[arg(long, env = API_KEY_ENV, hide_env_values = true, global = true)]
+    api_key: Option<String>,
+    /// Format of the results [default: text]
+    #[arg(long, short, value_enum, env = "SONGKICK_OUTPUT", global = true)]
+    output: Option<Output>,
+    /// Number of results fetched per page
+    #[arg(long, env = "SONGKICK_PAGE_SIZE", global = true)]
+    page_size: Option<u64>,
+    /// Configuration file, instead of ~/.config/songkick/config.toml
+    #[arg(long, env = "SONGKICK_CONFIG", global = true, value_hint = ValueHint::FilePath)]
+    config: Option<PathBuf>,
+    /// Maximum number of results, all of them by default
+    #[arg(long, global = true)]
+    limit: Option<usize>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Output {
+    /// One line per result
+    Text,
+    /// Aligned columns, colored on terminals
+    Table,
+    /// Indented JSON array
+    Json,
+    /// One JSON object per line
+    Jsonl,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Artists
+    #[command(subcommand)]
+    Artist(ArtistCommand),
+    /// Metro areas
+    #[command(subcommand)]
+    Metro(MetroCommand),
+    /// Venues
+    #[command(subcommand)]
+    Venue(VenueCommand),
+    /// Users
+    #[command(subcommand)]
+    User(UserCommand),
+    /// Upcoming events in the metro area of your IP address
+    NearMe {
+        /// Number of days ahead, today included
+        #[arg(long, default_value_t = 14)]
+        days: u32,
+        /// Location searched instead of your IP address, e.g. `ip:94.228.36.39` or `geo:51.5078,-0.128`
+        #[arg(long, default_value = "clientip")]
+        location: String,
+    },
+    /// Report the new, cancelled and rescheduled events of calendars as they happen
+    Watch(watch::WatchArgs),
+    /// Write the results of many calls to files, resuming an interrupted export
+    #[command(subcommand)]
+    Export(export::ExportCommand),
+    /// Print the completion script of a shell
+    ///
+    /// For instance with bash: `songkick completions bash > ~/.local/share/bash-completion/completions/songkick`
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ArtistCommand {
+    /// Artists matching a name
+    Search { name: String },
+    /// Upcoming events of an artist
+    Calendar { id: u64 },
+    /// Past events of an artist
+    Gigography { id: u64 },
+}
+
+#[derive(Debug, Subcommand)]
+enum MetroCommand {
+    /// Upcoming events in a metro area
+    Calendar {
+        /// ID of the metro area, the configured one by default
+        #[arg(env = METRO_AREA_ENV)]
+        id: Option<u64>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum VenueCommand {
+    /// Upcoming events at a venue
+    Events { id: u64 },
+}
+
+#[derive(Debug, Subcommand)]
+enum UserCommand {
+    /// Upcoming events in the calendar of a user
+    Calendar {
+        username: String,
+        /// Why the events are in the calendar
+        #[arg(long, value_enum, default_value_t = Reason::TrackedArtist)]
+        reason: Reason,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Reason {
+    /// Events of the artists tracked by the user
+    TrackedArtist,
+    /// Events the user is going to
+    Attendance,
+}
+
+impl From<Reason> for CalendarReason {
+    fn from(reason: Reason) -> CalendarReason {
+        match reason {
+            Reason::TrackedArtist => CalendarReason::TrackedArtist,
+            Reason::Attendance => CalendarReason::Attendance,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(&cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("songkick: {}", report(&err));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// `err` followed by its sources, e.g. `artists.calendar request failed (...): Not found`
+fn report(err: &SkError) -> String {
+    let mut report = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        report.push_str(": ");
+        report.push_str(&err.to_string());
+        source = err.source();
+    }
+    report
+}
+
+fn run(cli: &Cli) -> SkResult<()> {
+    if let Command::Completions { shell } = cli.command {
+        clap_complete::generate(shell, &mut Cli::command(), "songkick", &mut io::stdout());
+        return Ok(());
+    }
+    let config = Config::load(cli.config.as_deref())?;
+    let api_key = cli.api_key.as_ref().or(config.api_key.as_ref()).ok_or_else(|| {
+        SkError::InvalidInput(format!(
+            "missing API key, pass --api-key, set {} or api_key in the configuration",
+            API_KEY_ENV
+        ))
+    })?;
+    let sk = SongKick::new(api_key.as_str());
+    let out = Printer {
+        output: cli.output.or(config.output).unwrap_or(Output::Text),
+        limit: cli.limit.unwrap_or(usize::MAX),
+        page_size: cli.page_size.or(config.page_size),
+    };
+    match &cli.command {
+        Command::Artist(ArtistCommand::Search { name }) => {
+            out.print(sk.artist.search_by_name(name.as_str())?.map(Ok), ToString::to_string)
+        }
+        Command::Artist(ArtistCommand::Calendar { id }) => {
+            out.print(out.pages(sk.artist.calendar_pages(*id)).items(), ToString::to_string)
+        }
+        Command::Artist(ArtistCommand::Gigography { id }) => {
+            out.print(out.pages(sk.artist.gigography_pages(*id)).items(), ToString::to_string)
+        }
+        Command::Metro(MetroCommand::Calendar { id }) => {
+            let id = id.or(config.metro_area).ok_or_else(|| {
+                SkError::InvalidInput(format!(
+                    "missing metro area, pass its ID, set {} or metro_area in the configuration",
+                    METRO_AREA_ENV
+                ))
+            })?;
+            out.print(out.pages(sk.metro_area.calendar_pages(id)).items(), ToString::to_string)
+        }
+        Command::Venue(VenueCommand::Events { id }) => {
+            out.print(out.pages(sk.venue.calendar_pages(*id)).items(), ToString::to_string)
+        }
+        Command::User(UserCommand::Calendar { username, reason }) => {
+            out.print(out.pages(sk.user.calendar_pages(username, (*reason).into())).items(), entry_text)
+        }
+        Command::NearMe { days, location } => near_me(&sk, &out, *days, location),
+        Command::Watch(args) => watch::run(&sk, args, out.output),
+        Command::Export(export::ExportCommand::Gigography(args)) => export::gigography(&sk, args, out.page_size),
+        Command::Completions { .. } => unreachable!("completions are generated without a client"),
+    }
+}
+
+/// Print the events of the next `days` days in the metro area of `location`
+fn near_me(sk: &SongKick, out: &Printer, days: u32, location: &str) -> SkResult<()> {
+    let area = sk
+        .metro_area
+        .locate(location)?
+        .next()
+        .map(|found| found.metro_area)
+        .ok_or_else(|| SkError::NotFound {
+            body: format!("no metro area for location {}", location),
+        })?;
+    eprintln!("{}, {} ({})", area.display_name, area.country.display_name, area.id);
+
+    let today = chrono::Local::now().date_naive();
+    let last = today + chrono::Duration::days(i64::from(days.max(1)) - 1);
+    let pages = out.pages(sk.metro_area.calendar_pages(area.id)).filter(move |filter| {
+        filter
+            .min_date(today.format("%Y-%m-%d").to_string())
+            .max_date(last.format("%Y-%m-%d").to_string());
+    });
+    out.print(pages.items(), ToString::to_string)
+}
+
+/// Writer of the results on the standard output
+struct Printer {
+    output: Output,
+    limit: usize,
+    page_size: Option<u64>,
+}
+
+impl Printer {
+    /// `pages` with the configured page size
+    fn pages<'a, M: TopLevelResource>(&self, pages: Pages<'a, M>) -> Pages<'a, M> {
+        match self.page_size {
+            Some(page_size) => pages.per_page(page_size),
+            None => pages,
+        }
+    }
+
+    fn print<T, I, F>(&self, results: I, text: F) -> SkResult<()>
+    where
+        T: Serialize + Tabular,
+        I: Iterator<Item = SkResult<T>>,
+        F: Fn(&T) -> String,
+    {
+        let stdout = io::stdout();
+        let mut out = stdout.lock();
+        let results = results.take(self.limit);
+        match self.output {
+            Output::Text => {
+                for item in results {
+                    writeln!(out, "{}", text(&item?))?;
+                }
+            }
+            Output::Table => {
+                let items = results.collect::<SkResult<Vec<T>>>()?;
+                table::write_table(out, &items, table::colored())?
+            }
+            Output::Json => json::write_json(out, &results.collect::<SkResult<Vec<T>>>()?, Format::Pretty)?,
+            Output::Jsonl => {
+                jsonl::write_results(out, results)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn entry_text(entry: &CalendarEntry) -> String {
+    match entry.reason.attendance {
+        Some(ref attendance) => format!("{} ({})", entry.event, attendance),
+        None => {
+            let tracked: Vec<&str> = entry
+                .reason
+                .tracked_artist
+                .iter()
+                .map(|artist| artist.display_name.as_str())
+                .collect();
+            format!("{} (tracking {})", entry.event, tracked.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_cli() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn parse_subcommands() {
+        let cli = Cli::try_parse_from(["songkick", "artist", "search", "Radiohead", "--limit", "3"]).unwrap();
+        assert!(matches!(cli.command, Command::Artist(ArtistCommand::Search { ref name }) if name == "Radiohead"));
+        assert_eq!(Some(3), cli.limit);
+        assert_eq!(None, cli.output);
+
+        let cli = Cli::try_parse_from(["songkick", "-o", "jsonl", "user", "calendar", "fan", "--reason", "attendance"])
+            .unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::User(UserCommand::Calendar { reason: Reason::Attendance, .. })
+        ));
+        assert_eq!(Some(Output::Jsonl), cli.output);
+
+        assert!(Cli::try_parse_from(["songkick", "venue", "events", "not-an-id"]).is_err());
+
+        let cli = Cli::try_parse_from(["songkick", "watch", "--artist", "253846", "--metro", "24426"]).unwrap();
+        assert!(matches!(cli.command, Command::Watch(_)));
+
+        let cli = Cli::try_parse_from(["songkick", "near-me", "--days", "7"]).unwrap();
+        assert!(matches!(cli.command, Command::NearMe { days: 7, ref location } if location == "clientip"));
+        assert!(Cli::try_parse_from(["songkick", "watch", "--interval", "1h"]).is_err());
+    }
+
+    #[test]
+    fn generate_completions() {
+        let mut script = Vec::new();
+        clap_complete::generate(Shell::Bash, &mut Cli::command(), "songkick", &mut script);
+        let script = String::from_utf8(script).unwrap();
+        assert!(script.contains("gigography"));
+        assert!(script.contains("text table json jsonl"));
+    }
+}
@@ -0,0 +1,259 @@
+//! Aligned terminal tables of the results, for `--output table`
+//!
+//! Columns are as wide as their content up to a limit, longer values are truncated
+//! with an ellipsis. Colors are only used on terminals, and never with `NO_COLOR` set.
+
+use songkick::resources::{Artist, CalendarEntry, Event};
+use std::env;
+use std::io::{self, IsTerminal, Write};
+
+/// Space between two columns
+const GAP: &str = "  ";
+
+/// Header and largest width of a column
+pub struct Column {
+    name: &'static str,
+    max_width: usize,
+}
+
+const fn column(name: &'static str, max_width: usize) -> Column {
+    Column { name, max_width }
+}
+
+/// Display of a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Plain,
+    Bold,
+    Green,
+    Yellow,
+    Red,
+}
+
+impl Style {
+    fn code(self) -> Option<&'static str> {
+        match self {
+            Style::Plain => None,
+            Style::Bold => Some("\x1b[1m"),
+            Style::Green => Some("\x1b[32m"),
+            Style::Yellow => Some("\x1b[33m"),
+            Style::Red => Some("\x1b[31m"),
+        }
+    }
+}
+
+/// A value of a row
+pub struct Cell {
+    text: String,
+    style: Style,
+}
+
+impl Cell {
+    fn new<T: Into<String>>(text: T) -> Cell {
+        Cell {
+            text: text.into(),
+            style: Style::Plain,
+        }
+    }
+
+    fn styled<T: Into<String>>(text: T, style: Style) -> Cell {
+        Cell {
+            text: text.into(),
+            style,
+        }
+    }
+}
+
+/// Results printed as rows of a table
+pub trait Tabular {
+    fn columns() -> &'static [Column];
+    /// Cells of the row, one per column
+    fn cells(&self) -> Vec<Cell>;
+}
+
+const EVENT_COLUMNS: &[Column] = &[
+    column("Date", 10),
+    column("Artist", 28),
+    column("Venue", 28),
+    column("City", 24),
+    column("Status", 10),
+];
+
+impl Tabular for Event {
+    fn columns() -> &'static [Column] {
+        EVENT_COLUMNS
+    }
+
+    fn cells(&self) -> Vec<Cell> {
+        let date = self.start.date.as_ref().map(ToString::to_string).unwrap_or_default();
+        let artist = match self.performances.first() {
+            Some(headliner) => headliner.display_name.clone(),
+            None => self.display_name.clone(),
+        };
+        let city = self.location.as_ref().map(|location| location.city.clone()).unwrap_or_default();
+        let status = match self.status.as_str() {
+            "ok" => Style::Green,
+            "cancelled" => Style::Red,
+            _ => Style::Yellow,
+        };
+        vec![
+            Cell::styled(date, Style::Bold),
+            Cell::new(artist),
+            Cell::new(self.venue.display_name.as_deref().unwrap_or_default()),
+            Cell::new(city),
+            Cell::styled(self.status.as_str(), status),
+        ]
+    }
+}
+
+const ENTRY_COLUMNS: &[Column] = &[
+    column("Date", 10),
+    column("Artist", 28),
+    column("Venue", 28),
+    column("City", 24),
+    column("Status", 10),
+    column("Reason", 16),
+];
+
+impl Tabular for CalendarEntry {
+    fn columns() -> &'static [Column] {
+        ENTRY_COLUMNS
+    }
+
+    fn cells(&self) -> Vec<Cell> {
+        let mut cells = self.event.cells();
+        cells.push(Cell::new(self.reason.attendance.as_deref().unwrap_or("tracked artist")));
+        cells
+    }
+}
+
+const ARTIST_COLUMNS: &[Column] = &[column("ID", 10), column("Artist", 40), column("On tour until", 13)];
+
+impl Tabular for Artist {
+    fn columns() -> &'static [Column] {
+        ARTIST_COLUMNS
+    }
+
+    fn cells(&self) -> Vec<Cell> {
+        vec![
+            Cell::new(self.id.to_string()),
+            Cell::styled(self.display_name.as_str(), Style::Bold),
+            Cell::new(self.on_tour_until.as_deref().unwrap_or_default()),
+        ]
+    }
+}
+
+/// Whether the standard output shows colors
+pub fn colored() -> bool {
+    io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+}
+
+/// Write `items` as a table with a header
+pub fn write_table<W: Write, T: Tabular>(mut out: W, items: &[T], colored: bool) -> io::Result<()> {
+    let columns = T::columns();
+    let rows: Vec<Vec<Cell>> = items.iter().map(Tabular::cells).collect();
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.text.chars().count())
+                .chain(Some(column.name.len()))
+                .max()
+                .unwrap_or_default()
+                .min(column.max_width)
+        })
+        .collect();
+
+    let header: Vec<Cell> = columns.iter().map(|column| Cell::styled(column.name, Style::Bold)).collect();
+    write_row(&mut out, &header, &widths, colored)?;
+    for row in &rows {
+        write_row(&mut out, row, &widths, colored)?;
+    }
+    out.flush()
+}
+
+fn write_row<W: Write>(out: &mut W, cells: &[Cell], widths: &[usize], colored: bool) -> io::Result<()> {
+    let mut line = String::new();
+    for (i, (cell, width)) in cells.iter().zip(widths).enumerate() {
+        if i > 0 {
+            line.push_str(GAP);
+        }
+        let text = truncate(&cell.text, *width);
+        let padding = width - text.chars().count();
+        match cell.style.code().filter(|_| colored) {
+            Some(code) => {
+                line.push_str(code);
+                line.push_str(&text);
+                line.push_str("\x1b[0m");
+            }
+            None => line.push_str(&text),
+        }
+        if i + 1 < cells.len() {
+            line.extend(std::iter::repeat_n(' ', padding));
+        }
+    }
+    writeln!(out, "{}", line)
+}
+
+/// `text` cut to `width` characters, the last one being an ellipsis when cut
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return String::from(text);
+    }
+    let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use songkick::SkResultSet;
+
+    fn load_events() -> Vec<Event> {
+        let data: Value = serde_json::from_str(
+            &std::fs::read_to_string("fixtures/event/artist-324967-calendar.json").unwrap(),
+        )
+        .unwrap();
+        SkResultSet::<Event>::from_json(&data).unwrap().collect()
+    }
+
+    #[test]
+    fn events_table() {
+        let events = load_events();
+        let mut out = Vec::new();
+        write_table(&mut out, &events[..3], false).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+
+        assert_eq!(4, lines.len());
+        assert!(lines[0].starts_with("Date        Artist"));
+        assert!(lines[1].starts_with(&format!("{}  Placebo", events[0].start.date.as_ref().unwrap())));
+        // Columns are aligned
+        let venue = lines[0].find("Venue").unwrap();
+        assert!(lines[1..]
+            .iter()
+            .all(|line| line.chars().skip(venue - 2).take(2).all(|c| c == ' ')));
+        assert!(!out.contains('\x1b'));
+    }
+
+    #[test]
+    fn colored_table() {
+        let events = load_events();
+        let mut out = Vec::new();
+        write_table(&mut out, &events[..1], true).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("\x1b[1mDate\x1b[0m"));
+        assert!(out.contains("\x1b[32mok\x1b[0m"));
+    }
+
+    #[test]
+    fn truncate_long_values() {
+        assert_eq!("Radiohead", truncate("Radiohead", 9));
+        assert_eq!("Radioh…", truncate("Radiohead", 7));
+        assert_eq!("Ünïcø…", truncate("Ünïcødé", 6));
+    }
+}
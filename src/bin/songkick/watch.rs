@@ -0,0 +1,184 @@
+//! `songkick watch`, reporting the changes of calendars as they happen
+
+use crate::Output;
+use clap::{ArgGroup, Args};
+use serde::Serialize;
+use songkick::diff::Change;
+use songkick::resources::Event;
+use songkick::watch::{Target, Watcher};
+use songkick::{SkResult, SongKick};
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+#[derive(Debug, Args)]
+#[command(group(ArgGroup::new("targets").required(true).multiple(true).args(["artist", "metro", "user"])))]
+pub struct WatchArgs {
+    /// ID of an artist whose calendar is watched, can be repeated
+    #[arg(long)]
+    artist: Vec<u64>,
+    /// ID of a metro area whose calendar is watched, can be repeated
+    #[arg(long)]
+    metro: Vec<u64>,
+    /// Username whose calendar of tracked artists is watched, can be repeated
+    #[arg(long)]
+    user: Vec<String>,
+    /// Time between two refreshes of a calendar, e.g. `90s`, `30m`, `1h`, `1d`
+    #[arg(long, default_value = "1h", value_parser = parse_interval)]
+    interval: Duration,
+    /// Shell command run for each change instead of printing it
+    ///
+    /// The change is described by the `SONGKICK_CHANGE` (new, cancelled or rescheduled),
+    /// `SONGKICK_TARGET`, `SONGKICK_EVENT_ID` and `SONGKICK_EVENT` environment variables,
+    /// and written as JSON on the standard input of the command.
+    #[arg(long)]
+    exec: Option<String>,
+}
+
+/// A change as printed or passed to the command
+#[derive(Serialize)]
+struct Record<'a> {
+    change: &'static str,
+    target: String,
+    event: &'a Event,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    before: Option<&'a Event>,
+}
+
+impl<'a> Record<'a> {
+    /// Record of `change`, events removed from the calendar have none
+    fn new(target: &Target, change: &Change<'a>) -> Option<Record<'a>> {
+        let (kind, before) = match *change {
+            Change::Added(_) => ("new", None),
+            Change::Cancelled(_) => ("cancelled", None),
+            Change::Rescheduled { before, .. } => ("rescheduled", Some(before)),
+            Change::Removed(_) => return None,
+        };
+        Some(Record {
+            change: kind,
+            target: target.to_string(),
+            event: change.event(),
+            before,
+        })
+    }
+
+    fn text(&self) -> String {
+        match self.before {
+            Some(before) => format!("{} {}: {} (was {})", self.change, self.target, self.event, before),
+            None => format!("{} {}: {}", self.change, self.target, self.event),
+        }
+    }
+}
+
+/// Watch the targets of `args` until an error that is not retriable
+pub fn run(sk: &SongKick, args: &WatchArgs, output: Output) -> SkResult<()> {
+    let mut watcher = Watcher::new(sk, args.interval);
+    for id in &args.artist {
+        watcher = watcher.artist(*id);
+    }
+    for id in &args.metro {
+        watcher = watcher.metro_area(*id);
+    }
+    for username in &args.user {
+        watcher = watcher.user(username.as_str());
+    }
+    watcher.run(|target, changes| {
+        for record in changes.iter().filter_map(|change| Record::new(target, change)) {
+            let reported = match args.exec {
+                Some(ref command) => execute(command, &record),
+                None => print(&record, output),
+            };
+            if let Err(err) = reported {
+                eprintln!("songkick: failed to report {}: {}", record.text(), err);
+            }
+        }
+    })
+}
+
+fn print(record: &Record, output: Output) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    match output {
+        Output::Text | Output::Table => writeln!(out, "{}", record.text())?,
+        Output::Json | Output::Jsonl => {
+            serde_json::to_writer(&mut out, record)?;
+            writeln!(out)?;
+        }
+    }
+    out.flush()
+}
+
+fn execute(command: &str, record: &Record) -> io::Result<()> {
+    let mut child = shell(command)
+        .env("SONGKICK_CHANGE", record.change)
+        .env("SONGKICK_TARGET", &record.target)
+        .env("SONGKICK_EVENT_ID", record.event.id.to_string())
+        .env("SONGKICK_EVENT", record.event.to_string())
+        .stdin(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // Commands are free to ignore their input
+        match serde_json::to_writer(&mut stdin, record).map_err(io::Error::from) {
+            Err(err) if err.kind() != io::ErrorKind::BrokenPipe => return Err(err),
+            _ => {}
+        }
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("`{}` exited with {}", command, status)));
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+/// Duration of a number followed by a unit among `s`, `m`, `h` and `d`, seconds without unit
+pub(crate) fn parse_interval(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let (amount, unit) = text.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("expected a number followed by s, m, h or d, found `{}`", text))?;
+    let seconds = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return Err(format!("unknown unit `{}`, expected s, m, h or d", unit)),
+    };
+    match amount.checked_mul(seconds) {
+        Some(0) => Err(String::from("the interval must not be zero")),
+        Some(total) => Ok(Duration::from_secs(total)),
+        None => Err(format!("interval `{}` is too long", text)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_intervals() {
+        assert_eq!(Ok(Duration::from_secs(90)), parse_interval("90"));
+        assert_eq!(Ok(Duration::from_secs(90)), parse_interval("90s"));
+        assert_eq!(Ok(Duration::from_secs(30 * 60)), parse_interval("30m"));
+        assert_eq!(Ok(Duration::from_secs(3600)), parse_interval("1h"));
+        assert_eq!(Ok(Duration::from_secs(2 * 86400)), parse_interval("2d"));
+        assert!(parse_interval("0h").is_err());
+        assert!(parse_interval("1w").is_err());
+        assert!(parse_interval("h").is_err());
+    }
+}
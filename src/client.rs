@@ -1,60 +1,559 @@
-use crate::endpoints::{ArtistEndpoint, EventEndpoint, SkEndpoint};
+use crate::endpoints::{ArtistEndpoint, EventEndpoint, MetroAreaEndpoint, SkEndpoint, UserEndpoint, VenueEndpoint};
+use crate::error::SkError;
+use crate::quota::DailyQuota;
+use crate::schedule::{Priority, Scheduler};
+use crate::transport::{BufferPool, ReqwestTransport, Transport};
+use crate::SkResult;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// Root of the SongKick API
+pub const BASE_PATH: &str = "http://api.songkick.com/api/3.0";
+
+/// Requests sent at a time by the batch calls, e.g. [`ArtistEndpoint::calendars`]
+pub const DEFAULT_BATCH_CONCURRENCY: usize = 8;
+
+/// Artist fetched by [`SongKick::validate_key`] (Radiohead)
+const VALIDATION_ARTIST: u64 = 253846;
 
 /// Represent the SongKick client used to fetch the data from SongKick API
+///
+/// The client is cheap to clone: the clones share its settings, its transport with the
+/// pooled connections of [`ReqwestTransport`], its [limits](crate::schedule) and its
+/// [daily budget](SongKickBuilder::daily_quota). Being `Send` and `Sync` too, a clone can
+/// be handed to each thread or handler of a server.
+///
+/// ```rust,no_run
+/// use songkick::SongKick;
+/// use std::thread;
+///
+/// let sk = SongKick::new("API_KEY");
+/// let handlers: Vec<_> = [253846, 324967]
+///     .iter()
+///     .map(|&id| {
+///         let sk = sk.clone();
+///         thread::spawn(move || sk.artist.gigography(id, None).map(|events| events.count()))
+///     })
+///     .collect();
+/// for handler in handlers {
+///     println!("{:?}", handler.join().unwrap());
+/// }
+/// ```
 pub struct SongKick {
     /// Artist EndPoint
     pub artist: ArtistEndpoint,
     /// Event EndPoint
     pub event: EventEndpoint,
-    #[allow(dead_code)]
+    /// Metro Area EndPoint
+    pub metro_area: MetroAreaEndpoint,
+    /// User EndPoint
+    pub user: UserEndpoint,
+    /// Venue EndPoint
+    pub venue: VenueEndpoint,
     opts: Arc<SongKickOpts>,
 }
+/// How strictly API responses are decoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    /// Keep fields not modelled by the crate in the resources `extra` map
+    #[default]
+    Lenient,
+    /// Fail on any field not modelled by the crate, useful in tests to catch schema drift
+    Strict,
+}
+
 /// Struct that holds SonKick Options
+#[derive(Clone)]
 pub struct SongKickOpts {
     /// API KEY
     api_key: String,
     /// API base path
-    base_path: &'static str,
+    base_path: String,
+    /// Base paths of some endpoints
+    routes: Routes,
+    /// Decoding mode
+    parse_mode: ParseMode,
+    /// Sender of the requests
+    transport: Arc<dyn Transport>,
+    /// Largest accepted response body, in bytes
+    max_response_size: Option<u64>,
+    /// Requests sent at a time by the batch calls
+    batch_concurrency: usize,
+    /// Limits of the requests, shared by the handles of the client
+    scheduler: Arc<Scheduler>,
+    /// Priority of the requests
+    priority: Priority,
+    /// Daily budget of requests
+    quota: Option<Arc<DailyQuota>>,
+    /// Buffers the response bodies are read into, [`BufferPool::bodies`] if not set
+    buffer_pool: Option<Arc<BufferPool>>,
 }
 
 impl SongKickOpts {
-    pub fn new<T>(api_key: T, base_path: &'static str) -> SongKickOpts
+    pub fn new<T, B>(api_key: T, base_path: B) -> SongKickOpts
     where
         T: Into<String>,
+        B: Into<String>,
     {
         SongKickOpts {
             api_key: api_key.into(),
-            base_path: base_path,
+            base_path: base_path.into(),
+            routes: Routes::default(),
+            parse_mode: ParseMode::default(),
+            transport: Arc::new(ReqwestTransport::new()),
+            max_response_size: None,
+            batch_concurrency: DEFAULT_BATCH_CONCURRENCY,
+            scheduler: Arc::default(),
+            priority: Priority::default(),
+            quota: None,
+            buffer_pool: None,
         }
     }
 
     /// Return base_path
     pub fn base_path(&self) -> &str {
-        self.base_path
+        &self.base_path
     }
-    /// Return API Key
 
+    /// Return the base path of the requests of `endpoint`, e.g. `events.search`, its
+    /// [route](SongKickBuilder::route) if any
+    pub fn base_path_for(&self, endpoint: &str) -> &str {
+        self.routes.base_path(endpoint).unwrap_or(&self.base_path)
+    }
+    /// Return API Key
     pub fn api_key(&self) -> &str {
         &self.api_key
     }
+
+    /// Return the decoding mode
+    pub fn parse_mode(&self) -> ParseMode {
+        self.parse_mode
+    }
+
+    /// Return the sender of the requests
+    pub fn transport(&self) -> &dyn Transport {
+        self.transport.as_ref()
+    }
+
+    /// Return the largest accepted response body, in bytes
+    pub fn max_response_size(&self) -> Option<u64> {
+        self.max_response_size
+    }
+
+    /// Return the count of requests sent at a time by the batch calls
+    pub fn batch_concurrency(&self) -> usize {
+        self.batch_concurrency
+    }
+
+    /// Return the priority of the requests
+    pub fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    /// Return the limits of the requests
+    pub(crate) fn scheduler(&self) -> &Scheduler {
+        &self.scheduler
+    }
+
+    /// Return the daily budget of requests
+    pub(crate) fn quota(&self) -> Option<&DailyQuota> {
+        self.quota.as_deref()
+    }
+
+    /// Return the pool of the buffers the response bodies are read into
+    pub fn buffer_pool(&self) -> &BufferPool {
+        match self.buffer_pool {
+            Some(ref pool) => pool,
+            None => BufferPool::bodies(),
+        }
+    }
 }
 
-impl SongKick {
-    pub fn new<T>(api_key: T) -> SongKick
+/// Struct used for building a SongKick client
+pub struct SongKickBuilder {
+    api_key: String,
+    base_path: String,
+    routes: Routes,
+    parse_mode: ParseMode,
+    transport: Arc<dyn Transport>,
+    max_response_size: Option<u64>,
+    batch_concurrency: usize,
+    max_concurrent_requests: Option<usize>,
+    min_request_interval: Option<Duration>,
+    daily_quota: Option<(u64, PathBuf)>,
+    buffer_pool: Option<Arc<BufferPool>>,
+}
+
+impl SongKickBuilder {
+    pub fn new<T>(api_key: T) -> SongKickBuilder
     where
         T: Into<String>,
     {
-        let opts = Arc::new(SongKickOpts {
+        SongKickBuilder {
             api_key: api_key.into(),
-            base_path: "http://api.songkick.com/api/3.0",
+            base_path: String::from(BASE_PATH),
+            routes: Routes::default(),
+            parse_mode: ParseMode::default(),
+            transport: Arc::new(ReqwestTransport::new()),
+            max_response_size: None,
+            batch_concurrency: DEFAULT_BATCH_CONCURRENCY,
+            max_concurrent_requests: None,
+            min_request_interval: None,
+            daily_quota: None,
+            buffer_pool: None,
+        }
+    }
+
+    /// Send the requests to another root than [`BASE_PATH`], e.g. a mock server
+    pub fn base_path<T>(mut self, base_path: T) -> SongKickBuilder
+    where
+        T: Into<String>,
+    {
+        self.base_path = base_path.into();
+        self
+    }
+
+    /// Send the requests of some endpoints to `base_path` instead of the
+    /// [base path](SongKickBuilder::base_path) of the client
+    ///
+    /// `endpoints` is either an endpoint, as named in the errors and the metrics, e.g.
+    /// `events.search`, or a family of endpoints, e.g. `events` for all the event requests.
+    /// The families are `artists`, `events`, `locations`, `metro_areas`, `users` and
+    /// `venues`. The route of an endpoint takes precedence over the route of its family, and
+    /// the last route given for the same endpoints is kept.
+    ///
+    /// ```rust
+    /// use songkick::SongKickBuilder;
+    ///
+    /// // Event searches through a caching proxy, the rest straight to the API
+    /// let sk = SongKickBuilder::new("API_KEY")
+    ///     .route("events.search", "http://cache.example.com/api/3.0")
+    ///     .build();
+    /// ```
+    pub fn route<E, T>(mut self, endpoints: E, base_path: T) -> SongKickBuilder
+    where
+        E: Into<String>,
+        T: Into<String>,
+    {
+        self.routes.insert(endpoints.into(), base_path.into());
+        self
+    }
+
+    /// Choose between lenient (default) and strict decoding
+    pub fn parse_mode(mut self, parse_mode: ParseMode) -> SongKickBuilder {
+        self.parse_mode = parse_mode;
+        self
+    }
+
+    /// Send the requests with `transport` instead of [`ReqwestTransport`]
+    pub fn transport<T: Transport + 'static>(mut self, transport: T) -> SongKickBuilder {
+        self.transport = Arc::new(transport);
+        self
+    }
+
+    /// Fail with [`SkError::ResponseTooLarge`] on the responses larger than `bytes`,
+    /// instead of reading bodies of any size
+    ///
+    /// The pages of the API weigh well under a megabyte, a few megabytes are a safe limit.
+    pub fn max_response_size(mut self, bytes: u64) -> SongKickBuilder {
+        self.max_response_size = Some(bytes);
+        self
+    }
+
+    /// Requests sent at a time by the batch calls, [`DEFAULT_BATCH_CONCURRENCY`] by default
+    pub fn batch_concurrency(mut self, concurrency: usize) -> SongKickBuilder {
+        self.batch_concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Requests sent at a time by the client and all its handles, e.g. from several
+    /// threads, unbounded by default
+    ///
+    /// Requests over the cap wait for their turn, the [foreground](Priority::Foreground)
+    /// ones first, see the [`schedule`](crate::schedule) module.
+    pub fn max_concurrent_requests(mut self, max: usize) -> SongKickBuilder {
+        self.max_concurrent_requests = Some(max.max(1));
+        self
+    }
+
+    /// Least time between the starts of two requests of the client and all its handles,
+    /// none by default
+    ///
+    /// Requests wait for their turn, the [foreground](Priority::Foreground) ones first, see
+    /// the [`schedule`](crate::schedule) module.
+    pub fn min_request_interval(mut self, interval: Duration) -> SongKickBuilder {
+        self.min_request_interval = Some(interval);
+        self
+    }
+
+    /// Send at most `limit` requests a UTC day with the API key, counted in the file at `path`
+    ///
+    /// Once the budget of the day is spent the requests fail with
+    /// [`SkError::QuotaExhausted`] without being sent, until midnight UTC. The count
+    /// survives restarts and is shared by the clients and the processes using the same
+    /// file, which can hold the counts of several keys. A request is counted right before
+    /// it is sent, so the requests that fail, even to connect, are counted too.
+    ///
+    /// ```rust,no_run
+    /// use songkick::SongKickBuilder;
+    ///
+    /// let sk = SongKickBuilder::new("API_KEY").daily_quota(5000, "songkick-quota.json").build();
+    /// ```
+    pub fn daily_quota<P: Into<PathBuf>>(mut self, limit: u64, path: P) -> SongKickBuilder {
+        self.daily_quota = Some((limit, path.into()));
+        self
+    }
+
+    /// Read the response bodies into the buffers of `pool` instead of the
+    /// [pool shared by the clients](BufferPool::bodies)
+    ///
+    /// ```rust
+    /// use songkick::transport::BufferPool;
+    /// use songkick::SongKickBuilder;
+    /// use std::sync::Arc;
+    ///
+    /// // Large gigography pages
+    /// let pages = Arc::new(BufferPool::new(4, 512 * 1024));
+    ///
+    /// let sk = SongKickBuilder::new("API_KEY").buffer_pool(pages.clone()).build();
+    /// ```
+    pub fn buffer_pool(mut self, pool: Arc<BufferPool>) -> SongKickBuilder {
+        self.buffer_pool = Some(pool);
+        self
+    }
+
+    pub fn build(self) -> SongKick {
+        let quota = match self.daily_quota {
+            Some((limit, path)) => Some(Arc::new(DailyQuota::new(&self.api_key, limit, path))),
+            None => None,
+        };
+        let opts = Arc::new(SongKickOpts {
+            api_key: self.api_key,
+            base_path: self.base_path,
+            routes: self.routes,
+            parse_mode: self.parse_mode,
+            transport: self.transport,
+            max_response_size: self.max_response_size,
+            batch_concurrency: self.batch_concurrency,
+            scheduler: Arc::new(Scheduler::new(self.max_concurrent_requests, self.min_request_interval)),
+            priority: Priority::default(),
+            quota,
+            buffer_pool: self.buffer_pool,
         });
+        SongKick::from_opts(opts)
+    }
+}
+
+/// Base paths of endpoints or families of endpoints, see [`SongKickBuilder::route`]
+#[derive(Debug, Clone, Default)]
+struct Routes {
+    routes: Vec<(String, String)>,
+}
+
+impl Routes {
+    fn insert(&mut self, endpoints: String, base_path: String) {
+        self.routes.retain(|(routed, _)| *routed != endpoints);
+        self.routes.push((endpoints, base_path));
+    }
+
+    fn find(&self, endpoints: &str) -> Option<&str> {
+        self.routes
+            .iter()
+            .find(|(routed, _)| routed == endpoints)
+            .map(|(_, base_path)| base_path.as_str())
+    }
+
+    /// Base path of `endpoint`, its own route before the route of its family
+    fn base_path(&self, endpoint: &str) -> Option<&str> {
+        if self.routes.is_empty() {
+            return None;
+        }
+        let family = endpoint.split('.').next().unwrap_or(endpoint);
+        self.find(endpoint).or_else(|| self.find(family))
+    }
+}
+
+impl Clone for SongKick {
+    fn clone(&self) -> SongKick {
+        SongKick::from_opts(self.opts.clone())
+    }
+}
+
+impl SongKick {
+    pub fn new<T>(api_key: T) -> SongKick
+    where
+        T: Into<String>,
+    {
+        SongKickBuilder::new(api_key).build()
+    }
+
+    fn from_opts(opts: Arc<SongKickOpts>) -> SongKick {
         let artist = ArtistEndpoint::new(opts.clone());
         let event = EventEndpoint::new(opts.clone());
+        let metro_area = MetroAreaEndpoint::new(opts.clone());
+        let user = UserEndpoint::new(opts.clone());
+        let venue = VenueEndpoint::new(opts.clone());
         SongKick {
-            artist: artist,
-            event: event,
-            opts: opts,
+            artist,
+            event,
+            metro_area,
+            user,
+            venue,
+            opts,
         }
     }
+
+    /// Handle of the client sending its requests with `priority`
+    ///
+    /// The handle shares the settings and the [limits](crate::schedule) of the client: once
+    /// they are reached, the [foreground](Priority::Foreground) requests of the client and
+    /// of its handles are served before the [background](Priority::Background) ones.
+    pub fn with_priority(&self, priority: Priority) -> SongKick {
+        let mut opts = SongKickOpts::clone(&self.opts);
+        opts.priority = priority;
+        SongKick::from_opts(Arc::new(opts))
+    }
+
+    /// Check that the API key is accepted, with a single cheap request
+    ///
+    /// A rejected key is reported as [`SkError::Unauthorized`], whether the API answers
+    /// with a 401/403 status or with an error message about the key.
+    pub fn validate_key(&self) -> SkResult<()> {
+        match self.artist.get(VALIDATION_ARTIST) {
+            Ok(_) => Ok(()),
+            // The key went through, the artist is just gone
+            Err(ref err) if matches!(err.inner(), SkError::NotFound { .. }) => Ok(()),
+            Err(err) => Err(key_error(err)),
+        }
+    }
+}
+
+/// Turn the API messages about the key into [`SkError::Unauthorized`]
+fn key_error(err: SkError) -> SkError {
+    match err {
+        SkError::Request { endpoint, url, source } => SkError::Request {
+            endpoint,
+            url,
+            source: Box::new(key_error(*source)),
+        },
+        SkError::Api { message } if message.to_lowercase().contains("apikey") => {
+            SkError::Unauthorized {
+                status: 401,
+                body: message,
+            }
+        }
+        err => err,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_errors_are_unauthorized() {
+        let err = SkError::Api {
+            message: String::from("Invalid or missing apikey"),
+        }
+        .with_request("artists.get", "http://api.songkick.com/api/3.0/artists/1.json?apikey=k");
+
+        let err = key_error(err);
+        assert_eq!(Some("artists.get"), err.endpoint());
+        match err.inner() {
+            SkError::Unauthorized { ref body, .. } => assert_eq!("Invalid or missing apikey", body),
+            err => panic!("expected Unauthorized, got {}", err),
+        }
+
+        let err = key_error(SkError::Api {
+            message: String::from("Internal error"),
+        });
+        assert!(matches!(err, SkError::Api { .. }));
+    }
+
+    #[test]
+    fn routed_endpoints() {
+        use crate::options::OptionsBuilder;
+        use crate::testing::MockTransport;
+        use crate::transport::HttpResponse;
+
+        let mock = MockTransport::new().on("", HttpResponse::json(r#"{"resultsPage": {"status": "ok", "results": {}}}"#));
+        let sk = SongKickBuilder::new("KEY")
+            .transport(mock.clone())
+            .route("events", "http://events.example.com")
+            .route("events.search", "http://old.example.com")
+            .route("events.search", "http://cache.example.com")
+            .build();
+        sk.event.search(OptionsBuilder::new().build()).unwrap();
+        sk.event.get(1).unwrap();
+        sk.artist.get(2).unwrap();
+
+        assert_eq!(
+            vec![
+                "http://cache.example.com/events.json?apikey=KEY",
+                "http://events.example.com/events/1.json?apikey=KEY",
+                "http://api.songkick.com/api/3.0/artists/2.json?apikey=KEY",
+            ],
+            mock.requests()
+        );
+        assert_eq!(BASE_PATH, sk.opts.base_path_for("venues.get"));
+    }
+
+    #[test]
+    fn prioritized_handles() {
+        use crate::testing::MockTransport;
+        use crate::transport::HttpResponse;
+
+        let mock = MockTransport::new().on("", HttpResponse::json(r#"{"resultsPage": {"status": "ok", "results": {}}}"#));
+        let sk = SongKickBuilder::new("KEY").transport(mock.clone()).max_concurrent_requests(1).build();
+        let background = sk.with_priority(Priority::Background);
+        background.artist.get(1).unwrap();
+        sk.artist.get(2).unwrap();
+
+        assert_eq!(Priority::Foreground, sk.opts.priority());
+        assert_eq!(Priority::Background, background.opts.priority());
+        assert!(Arc::ptr_eq(&sk.opts.scheduler, &background.opts.scheduler));
+        assert_eq!(2, mock.requests().len());
+    }
+
+    #[test]
+    fn shared_clones() {
+        use crate::testing::MockTransport;
+        use crate::transport::HttpResponse;
+
+        fn assert_shareable<T: Clone + Send + Sync + 'static>(_: &T) {}
+
+        let mock = MockTransport::new().on("", HttpResponse::json(r#"{"resultsPage": {"status": "ok", "results": {}}}"#));
+        let sk = SongKickBuilder::new("KEY").transport(mock.clone()).base_path("http://localhost").build();
+        assert_shareable(&sk);
+        let clone = sk.clone();
+        std::thread::spawn(move || clone.artist.get(1).map(|_| ())).join().unwrap().unwrap();
+        sk.artist.get(2).unwrap();
+
+        assert_eq!(
+            vec!["http://localhost/artists/1.json?apikey=KEY", "http://localhost/artists/2.json?apikey=KEY"],
+            mock.requests()
+        );
+        assert!(Arc::ptr_eq(&sk.opts, &sk.clone().opts));
+    }
+
+    #[test]
+    fn exhausted_quota() {
+        use crate::testing::MockTransport;
+        use crate::transport::HttpResponse;
+
+        let path = std::env::temp_dir().join(format!("songkick-client-quota-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mock = MockTransport::new().on("", HttpResponse::json(r#"{"resultsPage": {"status": "ok", "results": {}}}"#));
+        let sk = SongKickBuilder::new("KEY").transport(mock.clone()).daily_quota(1, &path).build();
+        sk.artist.get(1).unwrap();
+        let err = sk.artist.get(2).err().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(err.inner(), SkError::QuotaExhausted { .. }), "{}", err);
+        assert_eq!(Some("artists.get"), err.endpoint());
+        assert!(err.inner().to_string().starts_with("Daily request budget exhausted, until "), "{}", err);
+        assert!(!err.is_retriable());
+        assert_eq!(1, mock.requests().len());
+    }
 }
@@ -0,0 +1,143 @@
+//! Changes between two snapshots of a calendar
+//!
+//! ```rust,no_run
+//! use songkick::SongKick;
+//! use songkick::diff::{self, Change};
+//!
+//! let sk = SongKick::new("API_KEY");
+//! // RadioHead ID
+//! let before = sk.artist.calendar_all(253846).unwrap();
+//! // ... some time later
+//! let after = sk.artist.calendar_all(253846).unwrap();
+//!
+//! for change in diff::diff(&before, &after) {
+//!     if let Change::Cancelled(event) = change {
+//!         println!("Cancelled: {}", event);
+//!     }
+//! }
+//! ```
+
+use crate::resources::event::{Event, When};
+use crate::resources::ids::EventId;
+use std::collections::HashMap;
+
+/// Status of the cancelled events
+const CANCELLED: &str = "cancelled";
+
+/// A difference between two snapshots of a calendar
+#[derive(Clone, Copy)]
+pub enum Change<'a> {
+    /// Event only in the new snapshot
+    Added(&'a Event),
+    /// Event only in the old snapshot, cancelled and deleted or past in an upcoming calendar
+    Removed(&'a Event),
+    /// Event cancelled since the old snapshot
+    Cancelled(&'a Event),
+    /// Event moved to another date or venue since the old snapshot
+    Rescheduled { before: &'a Event, after: &'a Event },
+}
+
+impl<'a> Change<'a> {
+    /// The event as it is now, or as it was for removed events
+    pub fn event(&self) -> &'a Event {
+        match *self {
+            Change::Added(event) | Change::Removed(event) | Change::Cancelled(event) => event,
+            Change::Rescheduled { after, .. } => after,
+        }
+    }
+}
+
+/// Changes from `before` to `after`, events are matched by ID
+///
+/// Changes come in the order of `after`, followed by the removed events in the order of `before`.
+/// Events changed in other ways (e.g. popularity, line-up) are not reported.
+pub fn diff<'a>(before: &'a [Event], after: &'a [Event]) -> Vec<Change<'a>> {
+    let old: HashMap<EventId, &Event> = before.iter().map(|event| (event.id, event)).collect();
+    let mut changes = Vec::new();
+    for event in after {
+        match old.get(&event.id) {
+            None => changes.push(Change::Added(event)),
+            Some(previous) if is_cancelled(event) && !is_cancelled(previous) => {
+                changes.push(Change::Cancelled(event))
+            }
+            Some(previous) if is_rescheduled(previous, event) => changes.push(Change::Rescheduled {
+                before: previous,
+                after: event,
+            }),
+            Some(_) => {}
+        }
+    }
+    let new: HashMap<EventId, &Event> = after.iter().map(|event| (event.id, event)).collect();
+    changes.extend(
+        before
+            .iter()
+            .filter(|event| !new.contains_key(&event.id))
+            .map(Change::Removed),
+    );
+    changes
+}
+
+fn is_cancelled(event: &Event) -> bool {
+    event.status.eq_ignore_ascii_case(CANCELLED)
+}
+
+fn is_rescheduled(before: &Event, after: &Event) -> bool {
+    let same_venue = match (before.venue.id, after.venue.id) {
+        (Some(old), Some(new)) => old == new,
+        (None, None) => before.venue.display_name == after.venue.display_name,
+        _ => false,
+    };
+    !same_venue || !same_time(&before.start, &after.start)
+}
+
+fn same_time(before: &When, after: &When) -> bool {
+    before.date == after.date && before.time == after.time && before.datetime == after.datetime
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::SkResultSet;
+    use serde_json::Value;
+
+    fn calendar() -> Value {
+        serde_json::from_str(&std::fs::read_to_string("fixtures/event/artist-324967-calendar.json").unwrap())
+            .unwrap()
+    }
+
+    fn events(data: &Value) -> Vec<Event> {
+        SkResultSet::<Event>::from_json(data).unwrap().collect()
+    }
+
+    #[test]
+    fn same_snapshot_has_no_changes() {
+        let data = calendar();
+        assert!(diff(&events(&data), &events(&data)).is_empty());
+    }
+
+    #[test]
+    fn classify_changes() {
+        let old = calendar();
+        let mut new = calendar();
+        {
+            let list = new["resultsPage"]["results"]["event"].as_array_mut().unwrap();
+            list[1]["status"] = Value::from("cancelled");
+            list[2]["start"]["date"] = Value::from("2030-01-01");
+            list[3]["venue"]["id"] = Value::from(1);
+            let removed = list.remove(0);
+            let mut added = removed.clone();
+            added["id"] = Value::from(1);
+            list.push(added);
+        }
+        let (before, after) = (events(&old), events(&new));
+
+        let changes = diff(&before, &after);
+        assert_eq!(5, changes.len());
+        assert!(matches!(changes[0], Change::Cancelled(event) if event.id == before[1].id));
+        assert!(matches!(changes[1], Change::Rescheduled { after, .. } if after.id == before[2].id));
+        assert!(matches!(changes[2], Change::Rescheduled { before: old, .. } if old.id == before[3].id));
+        assert!(matches!(changes[3], Change::Added(event) if event.id == EventId(1)));
+        assert!(matches!(changes[4], Change::Removed(event) if event.id == before[0].id));
+        assert_eq!(before[0].id, changes[4].event().id);
+    }
+}
@@ -0,0 +1,284 @@
+//! Discovery of related artists through the similar artists of SongKick
+//!
+//! A [`SimilarArtists`] expands the similar artists of a seed artist breadth-first, then
+//! theirs, up to a depth, keeping only the most similar artists of each. The result is
+//! an [`ArtistGraph`] whose edges are weighted by similarity.
+//!
+//! ```rust,no_run
+//! use songkick::SongKick;
+//! use songkick::discovery::SimilarArtists;
+//!
+//! let sk = SongKick::new("API_KEY");
+//! // RadioHead ID
+//! let graph = SimilarArtists::new(&sk, 253846).depth(2).fan_out(5).explore().unwrap();
+//! for (artist, score) in graph.ranked().into_iter().take(10) {
+//!     println!("{:.2} {}", score, artist.display_name);
+//! }
+//! ```
+
+use crate::error::SkError;
+use crate::resources::artist::Artist;
+use crate::resources::ids::ArtistId;
+use crate::watch::DEFAULT_PAUSE;
+use crate::{SkResult, SongKick};
+use std::collections::{HashMap, VecDeque};
+use std::thread;
+use std::time::Duration;
+
+/// Default depth of the expansion
+pub const DEFAULT_DEPTH: usize = 2;
+/// Default count of similar artists kept per artist
+pub const DEFAULT_FAN_OUT: usize = 10;
+/// Default largest count of artists in a graph
+pub const DEFAULT_MAX_ARTISTS: usize = 200;
+
+/// Wait after a rate limited request without a `Retry-After` delay
+const RATE_LIMIT_WAIT: Duration = Duration::from_secs(30);
+/// Attempts of a rate limited request before giving up
+const RATE_LIMIT_ATTEMPTS: usize = 3;
+
+/// Artist of an [`ArtistGraph`]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ArtistNode {
+    pub artist: Artist,
+    /// Distance from the seed, 0 for the seed itself
+    pub depth: usize,
+}
+
+/// Similarity of `to` to `from`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct SimilarityEdge {
+    pub from: ArtistId,
+    pub to: ArtistId,
+    /// `1 / (1 + rank)` of `to` in the similar artists of `from`, 1 for the most similar
+    pub weight: f64,
+}
+
+/// Related artists found by [`SimilarArtists::explore`]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ArtistGraph {
+    /// The seed artist
+    pub seed: ArtistId,
+    /// Artists of the graph, the seed included
+    pub nodes: HashMap<ArtistId, ArtistNode>,
+    /// Similarities found, in the order they were fetched
+    pub edges: Vec<SimilarityEdge>,
+}
+
+impl ArtistGraph {
+    /// Artists similar to `id`, with the weights
+    pub fn neighbours(&self, id: ArtistId) -> impl Iterator<Item = (&Artist, f64)> + '_ {
+        self.edges
+            .iter()
+            .filter(move |edge| edge.from == id)
+            .filter_map(move |edge| Some((&self.nodes.get(&edge.to)?.artist, edge.weight)))
+    }
+
+    /// Artists other than the seed, by decreasing relatedness
+    ///
+    /// The score of an artist sums the weights of the edges to it, each divided by the
+    /// depth of its origin plus one: artists recommended by several related artists, or
+    /// by close ones, come first.
+    pub fn ranked(&self) -> Vec<(&Artist, f64)> {
+        let mut scores: HashMap<ArtistId, f64> = HashMap::new();
+        for edge in &self.edges {
+            let depth = self.nodes.get(&edge.from).map_or(0, |node| node.depth);
+            *scores.entry(edge.to).or_insert(0.0) += edge.weight / (depth + 1) as f64;
+        }
+        let mut ranked: Vec<(&Artist, f64)> = scores
+            .into_iter()
+            .filter(|&(id, _)| id != self.seed)
+            .filter_map(|(id, score)| Some((&self.nodes.get(&id)?.artist, score)))
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.id.cmp(&b.0.id)));
+        ranked
+    }
+}
+
+/// Bounded breadth-first expansion of the similar artists of a seed
+pub struct SimilarArtists<'a> {
+    sk: &'a SongKick,
+    seed: ArtistId,
+    depth: usize,
+    fan_out: usize,
+    max_artists: usize,
+    pause: Duration,
+}
+
+impl<'a> SimilarArtists<'a> {
+    /// Expansion from the artist `seed`
+    pub fn new<I: Into<ArtistId>>(sk: &'a SongKick, seed: I) -> SimilarArtists<'a> {
+        SimilarArtists {
+            sk,
+            seed: seed.into(),
+            depth: DEFAULT_DEPTH,
+            fan_out: DEFAULT_FAN_OUT,
+            max_artists: DEFAULT_MAX_ARTISTS,
+            pause: DEFAULT_PAUSE,
+        }
+    }
+
+    /// Largest distance from the seed of the expanded artists, [`DEFAULT_DEPTH`] by default
+    pub fn depth(mut self, depth: usize) -> SimilarArtists<'a> {
+        self.depth = depth;
+        self
+    }
+
+    /// Similar artists kept per artist, the most similar ones, [`DEFAULT_FAN_OUT`] by default
+    pub fn fan_out(mut self, fan_out: usize) -> SimilarArtists<'a> {
+        self.fan_out = fan_out;
+        self
+    }
+
+    /// Largest count of artists in the graph, [`DEFAULT_MAX_ARTISTS`] by default
+    pub fn max_artists(mut self, max_artists: usize) -> SimilarArtists<'a> {
+        self.max_artists = max_artists.max(1);
+        self
+    }
+
+    /// Pause between two requests, [`DEFAULT_PAUSE`] by default
+    pub fn pause(mut self, pause: Duration) -> SimilarArtists<'a> {
+        self.pause = pause;
+        self
+    }
+
+    /// Fetch the graph, one request per expanded artist
+    ///
+    /// Rate limited requests wait for the `Retry-After` delay of the API and are sent
+    /// again, a few times at most. Expanded artists unknown to the API are left
+    /// without edges.
+    pub fn explore(&self) -> SkResult<ArtistGraph> {
+        let seed = self
+            .sk
+            .artist
+            .get(self.seed)?
+            .next()
+            .ok_or_else(|| SkError::decode(format!("No artist in the response for {}", self.seed)))?;
+        let mut graph = ArtistGraph {
+            seed: self.seed,
+            nodes: HashMap::new(),
+            edges: Vec::new(),
+        };
+        graph.nodes.insert(self.seed, ArtistNode { artist: seed, depth: 0 });
+
+        let mut queue = VecDeque::from(vec![(self.seed, 0)]);
+        let mut first = true;
+        while let Some((id, depth)) = queue.pop_front() {
+            if depth >= self.depth {
+                continue;
+            }
+            if !first {
+                thread::sleep(self.pause);
+            }
+            first = false;
+
+            let similar = match self.similar(id) {
+                Ok(similar) => similar,
+                Err(ref err) if matches!(err.inner(), SkError::NotFound { .. }) => continue,
+                Err(err) => return Err(err),
+            };
+            for (rank, artist) in similar.into_iter().take(self.fan_out).enumerate() {
+                let to = artist.id;
+                if to == id {
+                    continue;
+                }
+                if !graph.nodes.contains_key(&to) {
+                    if graph.nodes.len() >= self.max_artists {
+                        continue;
+                    }
+                    graph.nodes.insert(to, ArtistNode { artist, depth: depth + 1 });
+                    queue.push_back((to, depth + 1));
+                }
+                graph.edges.push(SimilarityEdge {
+                    from: id,
+                    to,
+                    weight: 1.0 / (1 + rank) as f64,
+                });
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Similar artists of `id`, waiting out rate limiting
+    fn similar(&self, id: ArtistId) -> SkResult<Vec<Artist>> {
+        let mut attempt = 1;
+        loop {
+            match self.sk.artist.similar(id, None) {
+                Ok(similar) => return Ok(similar.collect()),
+                Err(err) => match *err.inner() {
+                    SkError::RateLimited { retry_after, .. } if attempt < RATE_LIMIT_ATTEMPTS => {
+                        thread::sleep(retry_after.unwrap_or(RATE_LIMIT_WAIT));
+                        attempt += 1;
+                    }
+                    _ => return Err(err),
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{ArtistBuilder, MockTransport};
+    use crate::transport::HttpResponse;
+    use crate::SongKickBuilder;
+    use serde_json::json;
+
+    fn similar_page(ids: &[u64]) -> HttpResponse {
+        let artists: Vec<_> = ids
+            .iter()
+            .map(|&id| ArtistBuilder::new().id(id).name(format!("Artist {}", id)).json())
+            .collect();
+        HttpResponse::json(
+            json!({"resultsPage": {"status": "ok", "results": {"artist": artists},
+                "perPage": 50, "page": 1, "totalEntries": ids.len()}})
+            .to_string(),
+        )
+    }
+
+    fn client() -> (SongKick, MockTransport) {
+        let mock = MockTransport::new()
+            .on("artists/1.json", similar_page(&[1]))
+            .on("artists/1/similar_artists.json", similar_page(&[2, 3, 4]))
+            .once("artists/2/similar_artists.json", HttpResponse::new(429, "").with_header("Retry-After", "0"))
+            .on("artists/2/similar_artists.json", similar_page(&[3, 5, 1]))
+            .on("artists/3/similar_artists.json", HttpResponse::new(404, ""))
+            .on("artists/5/similar_artists.json", similar_page(&[6]));
+        (SongKickBuilder::new("KEY").transport(mock.clone()).build(), mock)
+    }
+
+    #[test]
+    fn bounded_expansion() {
+        let (sk, mock) = client();
+        let graph = SimilarArtists::new(&sk, 1).depth(2).fan_out(2).pause(Duration::ZERO).explore().unwrap();
+
+        let mut ids: Vec<u64> = graph.nodes.keys().map(|id| id.0).collect();
+        ids.sort();
+        assert_eq!(vec![1, 2, 3, 5], ids);
+        assert_eq!(2, graph.nodes[&ArtistId(5)].depth);
+        assert_eq!(4, graph.edges.len());
+        assert_eq!(
+            SimilarityEdge { from: ArtistId(2), to: ArtistId(5), weight: 0.5 },
+            graph.edges[3]
+        );
+        let neighbours: Vec<u64> = graph.neighbours(ArtistId(1)).map(|(artist, _)| artist.id.0).collect();
+        assert_eq!(vec![2, 3], neighbours);
+        // 3 is recommended by 1 and 2, as much as 2 by 1 alone
+        let ranked: Vec<(u64, f64)> = graph.ranked().iter().map(|(artist, score)| (artist.id.0, *score)).collect();
+        assert_eq!(vec![(2, 1.0), (3, 1.0), (5, 0.25)], ranked);
+        // The seed, 1 retried, 2 and 3; 5 is at the largest depth
+        assert_eq!(5, mock.requests().len());
+    }
+
+    #[test]
+    fn limited_artists() {
+        let (sk, _) = client();
+        let graph = SimilarArtists::new(&sk, 1).depth(5).max_artists(3).pause(Duration::ZERO).explore().unwrap();
+        assert_eq!(3, graph.nodes.len());
+        assert!(graph.edges.iter().all(|edge| graph.nodes.contains_key(&edge.to)));
+    }
+}
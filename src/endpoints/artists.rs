@@ -1,12 +1,65 @@
 use crate::client::SongKickOpts;
 use crate::endpoints::SkEndpoint;
 use crate::endpoints::SkEndpointInternal;
-use crate::options::Options;
+use crate::analysis::{civil_date, day_number, was_held};
+use crate::endpoints::{all_pages, fan_out, for_page, Pages, MAX_PER_PAGE};
+use crate::error::SkError;
+use crate::matching::best_match;
+#[cfg(feature = "strsim")]
+use crate::matching::{rank, Scored};
+use crate::options::{Options, OptionsBuilder};
 use crate::resources::artist::Artist;
+use crate::resources::ids::ArtistId;
 use crate::resources::event::Event;
 use crate::result::SkResultSet;
 use crate::SkResult;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
+
+/// Calls of the Artist endpoint, as a trait to substitute fakes to [`ArtistEndpoint`] in tests
+///
+/// ```rust
+/// use songkick::endpoints::ArtistApi;
+/// use songkick::resources::ArtistId;
+///
+/// fn on_tour(artists: &dyn ArtistApi, id: ArtistId) -> songkick::Result<bool> {
+///     Ok(artists.calendar(id, None)?.total_entries > 0)
+/// }
+/// ```
+pub trait ArtistApi {
+    /// Single Artist with ID
+    fn get(&self, id: ArtistId) -> SkResult<SkResultSet<Artist>>;
+
+    /// Search Artists by name
+    fn search_by_name(&self, name: &str) -> SkResult<SkResultSet<Artist>>;
+
+    /// Page of the upcoming events of the artist
+    fn calendar(&self, id: ArtistId, options: Option<Options>) -> SkResult<SkResultSet<Event>>;
+
+    /// Page of the past events of the artist
+    fn gigography(&self, id: ArtistId, options: Option<Options>) -> SkResult<SkResultSet<Event>>;
+
+    /// Lazy iterator over the pages of the calendar of the artist
+    fn calendar_pages(&self, id: ArtistId) -> Pages<'_, Event> {
+        Pages::new(move |options| self.calendar(id, Some(options)))
+    }
+
+    /// Lazy iterator over the pages of the gigography of the artist
+    fn gigography_pages(&self, id: ArtistId) -> Pages<'_, Event> {
+        Pages::new(move |options| self.gigography(id, Some(options)))
+    }
+
+    /// All the upcoming events of the artist, fetching every page of the calendar
+    fn calendar_all(&self, id: ArtistId) -> SkResult<Vec<Event>> {
+        all_pages(|options| self.calendar(id, Some(options)))
+    }
+}
+
+/// Upcoming events read by [`ArtistEndpoint::get_many`] for the artists without record
+const LINE_UP_PAGE_SIZE: u64 = 5;
 
 #[doc(hidden)]
 struct ArtistEndpointDelegate {}
@@ -28,22 +81,33 @@ pub struct ArtistEndpoint {
 
 impl SkEndpoint for ArtistEndpoint {
     type Model = Artist;
+    type Id = ArtistId;
 
     fn new(sk: Arc<SongKickOpts>) -> ArtistEndpoint {
         let delegate = ArtistEndpointDelegate::new();
         ArtistEndpoint {
-            delegate: delegate,
-            sk: sk,
+            delegate,
+            sk,
         }
     }
 
-    /// Get Single Artist with ID
-    fn get(&self, id: u64) -> SkResult<SkResultSet<Self::Model>> {
-        self.delegate.get(id, self.sk.as_ref(), "artists")
+    fn get<I>(&self, id: I) -> SkResult<SkResultSet<Self::Model>>
+    where
+        I: Into<ArtistId>,
+    {
+        ArtistEndpoint::get(self, id)
     }
 }
 
 impl ArtistEndpoint {
+    /// Get Single Artist with ID
+    pub fn get<I>(&self, id: I) -> SkResult<SkResultSet<Artist>>
+    where
+        I: Into<ArtistId>,
+    {
+        self.delegate.get(id.into().0, self.sk.as_ref(), "artists")
+    }
+
     /// Search [Artists](https://www.songkick.com/developer/artist-search) by name
     pub fn search_by_name<T>(&self, text: T) -> SkResult<SkResultSet<Artist>>
     where
@@ -53,15 +117,353 @@ impl ArtistEndpoint {
             .search_by_name(&text.into(), self.sk.as_ref(), "artists", None)
     }
 
+    /// Search Artists by name, the results by decreasing [`score`](crate::matching::score) of their
+    /// names to `text`
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "strsim")]
+    /// # {
+    /// use songkick::SongKick;
+    ///
+    /// let sk = SongKick::new("API_KEY");
+    /// for scored in sk.artist.search_ranked("the cure").unwrap().iter().filter(|scored| scored.score > 0.9) {
+    ///     println!("{:.2} {}", scored.score, scored.item.display_name);
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "strsim")]
+    pub fn search_ranked<T>(&self, text: T) -> SkResult<Vec<Scored<Artist>>>
+    where
+        T: Into<String>,
+    {
+        let text = text.into();
+        Ok(rank(&text, self.search_by_name(text.as_str())?))
+    }
+
+    /// Artist named `name`, the best match of the search for it
+    ///
+    /// The search results are compared to `name` as [normalized](crate::matching::normalize):
+    /// an exact name is preferred to a name differing in case, accents or punctuation, itself
+    /// preferred to a name differing in a leading `The`. Results of a same likeness keep the
+    /// order of the search. `None` when no result has the name, e.g. only tribute acts.
+    ///
+    /// ```rust,no_run
+    /// use songkick::SongKick;
+    ///
+    /// let sk = SongKick::new("API_KEY");
+    /// if let Some(artist) = sk.artist.resolve("the cure").unwrap() {
+    ///     println!("{} ({})", artist.display_name, artist.id);
+    /// }
+    /// ```
+    pub fn resolve<T>(&self, name: T) -> SkResult<Option<Artist>>
+    where
+        T: Into<String>,
+    {
+        let name = name.into();
+        let mut candidates: Vec<Artist> = self.search_by_name(name.as_str())?.collect();
+        Ok(best_match(&name, &candidates, |artist| &artist.display_name).map(|i| candidates.swap_remove(i)))
+    }
+
     /// Retrieve [Calendar](https://www.songkick.com/developer/upcoming-events-for-artist) for an Artist with ID
-    pub fn calendar(&self, id: u64, options: Option<Options>) -> SkResult<SkResultSet<Event>> {
+    pub fn calendar<I>(&self, id: I, options: Option<Options>) -> SkResult<SkResultSet<Event>>
+    where
+        I: Into<ArtistId>,
+    {
         self.delegate
-            .calendar(id, self.sk.as_ref(), "artists", options)
+            .calendar(id.into().0, self.sk.as_ref(), "artists", options)
+    }
+
+    /// Page of the calendar of the artist, each event handed to `f` as soon as it is decoded
+    ///
+    /// The first events are handed before the rest of the page is read. The returned set has
+    /// the paging fields of the page, and no events.
+    ///
+    /// ```rust,no_run
+    /// use songkick::SongKick;
+    ///
+    /// let sk = SongKick::new("API_KEY");
+    /// // RadioHead ID
+    /// let page = sk.artist.calendar_each(253846, None, |event| println!("{}", event)).unwrap();
+    /// println!("{} events", page.total_entries);
+    /// ```
+    pub fn calendar_each<I, F>(&self, id: I, options: Option<Options>, mut f: F) -> SkResult<SkResultSet<Event>>
+    where
+        I: Into<ArtistId>,
+        F: FnMut(Event),
+    {
+        self.delegate
+            .calendar_each(id.into().0, self.sk.as_ref(), "artists", options, &mut f)
+    }
+
+    /// Lazy iterator over the pages of the calendar of the artist
+    pub fn calendar_pages<I>(&self, id: I) -> Pages<'_, Event>
+    where
+        I: Into<ArtistId>,
+    {
+        let id = id.into();
+        Pages::new(move |options| self.calendar(id, Some(options)))
+    }
+
+    /// Lazy iterator over the pages of the gigography of the artist
+    pub fn gigography_pages<I>(&self, id: I) -> Pages<'_, Event>
+    where
+        I: Into<ArtistId>,
+    {
+        let id = id.into();
+        Pages::new(move |options| self.gigography(id, Some(options)))
+    }
+
+    /// All the upcoming events of the artist, fetching every page of the calendar
+    pub fn calendar_all<I>(&self, id: I) -> SkResult<Vec<Event>>
+    where
+        I: Into<ArtistId>,
+    {
+        let id = id.into();
+        all_pages(|options| self.calendar(id, Some(options)))
+    }
+
+    /// All the upcoming events of the artist, rendered as an iCalendar feed
+    #[cfg(feature = "ics")]
+    pub fn calendar_ics<I>(&self, id: I) -> SkResult<String>
+    where
+        I: Into<ArtistId>,
+    {
+        Ok(crate::export::ics::to_ics(&self.calendar_all(id)?))
+    }
+
+    /// Upcoming events of each of the artists, fetching every page of their calendars
+    ///
+    /// The calendars are fetched [`batch_concurrency`](crate::SongKickBuilder::batch_concurrency)
+    /// at a time, each with its own result: a failed calendar doesn't fail the others.
+    /// The filters and the order of `options` apply to every calendar, its paging is ignored.
+    ///
+    /// ```rust,no_run
+    /// use songkick::SongKick;
+    /// use songkick::resources::ArtistId;
+    ///
+    /// let sk = SongKick::new("API_KEY");
+    /// // RadioHead and Placebo IDs
+    /// let calendars = sk.artist.calendars(&[ArtistId(253846), ArtistId(324967)], None);
+    /// for (id, calendar) in &calendars {
+    ///     match calendar {
+    ///         Ok(events) => println!("{}: {} upcoming events", id, events.len()),
+    ///         Err(err) => println!("{}: {}", id, err),
+    ///     }
+    /// }
+    /// ```
+    pub fn calendars(&self, ids: &[ArtistId], options: Option<Options>) -> HashMap<ArtistId, SkResult<Vec<Event>>> {
+        fan_out(ids, self.sk.batch_concurrency(), |id| {
+            all_pages(|page| self.calendar(id, Some(for_page(&options, page))))
+        })
+    }
+
+    /// Past events of each of the artists, fetching every page of their gigographies
+    ///
+    /// Fetched as the [`calendars`](ArtistEndpoint::calendars) are.
+    pub fn gigographies(&self, ids: &[ArtistId], options: Option<Options>) -> HashMap<ArtistId, SkResult<Vec<Event>>> {
+        fan_out(ids, self.sk.batch_concurrency(), |id| {
+            all_pages(|page| self.gigography(id, Some(for_page(&options, page))))
+        })
+    }
+
+    /// Past events of the artist on the `month`/`day` calendar day of every year, the oldest
+    /// first, e.g. for an "on this day in 1997" feature
+    ///
+    /// Cancelled and postponed events are left out. Either the whole gigography is fetched
+    /// and filtered, or the day of each year since the first event, whichever takes fewer
+    /// requests. February 29 matches only leap years.
+    ///
+    /// ```rust,no_run
+    /// use songkick::SongKick;
+    ///
+    /// let sk = SongKick::new("API_KEY");
+    /// // RadioHead ID
+    /// for event in sk.artist.on_this_day(253846, 6, 6).unwrap() {
+    ///     println!("{}", event.display_name);
+    /// }
+    /// ```
+    pub fn on_this_day<I>(&self, id: I, month: u32, day: u32) -> SkResult<Vec<Event>>
+    where
+        I: Into<ArtistId>,
+    {
+        let id = id.into();
+        if day_number(&format!("2000-{:02}-{:02}", month, day)).map(civil_date) != Some((2000, month, day)) {
+            return Err(SkError::InvalidInput(format!("invalid calendar day {:02}-{:02}", month, day)));
+        }
+        let on_day = |event: &Event| {
+            let date = event.start.date.as_ref().and_then(|date| day_number(&date.to_string()));
+            was_held(event) && date.map(civil_date).is_some_and(|(_, m, d)| (m, d) == (month, day))
+        };
+
+        // The oldest event, for the count of years, and the count of pages
+        let first = self.gigography(id, Some(OptionsBuilder::new().paging(1, 1).build()))?;
+        let pages = first.total_entries.div_ceil(MAX_PER_PAGE);
+        let first_year = first
+            .into_iter()
+            .next()
+            .and_then(|event| event.start.date.and_then(|date| day_number(&date.to_string())))
+            .map(|days| civil_date(days).0);
+        let first_year = match first_year {
+            Some(year) => year,
+            None => return Ok(Vec::new()),
+        };
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+        let this_year = civil_date((secs / 86_400) as i64).0;
+        let years = (first_year..=this_year).filter(|&year| {
+            let date = format!("{:04}-{:02}-{:02}", year, month, day);
+            day_number(&date).map(civil_date) == Some((year, month, day))
+        });
+
+        let mut events: Vec<Event> = if pages <= (this_year - first_year + 1) as u64 {
+            let mut events = all_pages(|page| self.gigography(id, Some(page)))?;
+            events.retain(on_day);
+            events
+        } else {
+            let mut events = Vec::new();
+            for year in years {
+                let date = format!("{:04}-{:02}-{:02}", year, month, day);
+                let options = Options::filtered(None, |filter| {
+                    filter.min_date(date.as_str()).max_date(date.as_str());
+                });
+                let found = all_pages(|page| self.gigography(id, Some(options.for_page(page))))?;
+                events.extend(found.into_iter().filter(on_day));
+            }
+            events
+        };
+        let mut seen = HashSet::new();
+        events.retain(|event| seen.insert(event.id));
+        events.sort_by_key(|event| event.start.date.as_ref().and_then(|date| day_number(&date.to_string())));
+        Ok(events)
+    }
+
+    /// Full record of each of the artists, e.g. to refresh a list of followed artists
+    ///
+    /// The artists are fetched [`batch_concurrency`](crate::SongKickBuilder::batch_concurrency)
+    /// at a time, each with its own result: a failed artist doesn't fail the others. An
+    /// artist without record, e.g. merged into another one, is read from the line-up of its
+    /// upcoming events when it has some.
+    ///
+    /// ```rust,no_run
+    /// use songkick::SongKick;
+    /// use songkick::resources::ArtistId;
+    ///
+    /// let sk = SongKick::new("API_KEY");
+    /// // RadioHead and Placebo IDs
+    /// for (id, artist) in sk.artist.get_many(&[ArtistId(253846), ArtistId(324967)]) {
+    ///     match artist {
+    ///         Ok(artist) => println!("{}: {}", id, artist.display_name),
+    ///         Err(err) => println!("{}: {}", id, err),
+    ///     }
+    /// }
+    /// ```
+    pub fn get_many(&self, ids: &[ArtistId]) -> HashMap<ArtistId, SkResult<Artist>> {
+        fan_out(ids, self.sk.batch_concurrency(), |id| self.get_one(id))
+    }
+
+    /// Artist with ID of [`get_many`](ArtistEndpoint::get_many)
+    fn get_one(&self, id: ArtistId) -> SkResult<Artist> {
+        let missing = match self.get(id) {
+            Ok(mut artists) => match artists.next() {
+                Some(artist) => return Ok(artist),
+                None => SkError::NotFound {
+                    body: format!("no artist in the response for {}", id),
+                }
+                .with_request("artists.get", &self.delegate.get_url(id.0, self.sk.as_ref(), "artists")),
+            },
+            Err(err) if matches!(err.inner(), SkError::NotFound { .. }) => err,
+            Err(err) => return Err(err),
+        };
+        let page = OptionsBuilder::new().paging(1, LINE_UP_PAGE_SIZE).build();
+        self.calendar(id, Some(page))
+            .ok()
+            .and_then(|events| {
+                events
+                    .flat_map(|event| event.performances)
+                    .map(|performance| performance.artist)
+                    .find(|artist| artist.id == id)
+            })
+            .ok_or(missing)
+    }
+
+    /// Retrieve [Similar Artists](https://www.songkick.com/developer/similar-artists) of an Artist with ID,
+    /// the most similar first
+    pub fn similar<I>(&self, id: I, options: Option<Options>) -> SkResult<SkResultSet<Artist>>
+    where
+        I: Into<ArtistId>,
+    {
+        let url = format!(
+            "{}/artists/{}/similar_artists.json?apikey={}",
+            self.sk.base_path_for("artists.similar"),
+            id.into(),
+            self.sk.api_key()
+        );
+        self.delegate.fetch(&url, self.sk.as_ref(), options, "artists.similar")
     }
 
     /// Retrieve [Gigography](https://www.songkick.com/developer/past-events-for-artist) for an Artist with ID
-    pub fn gigography(&self, id: u64, options: Option<Options>) -> SkResult<SkResultSet<Event>> {
+    pub fn gigography<I>(&self, id: I, options: Option<Options>) -> SkResult<SkResultSet<Event>>
+    where
+        I: Into<ArtistId>,
+    {
+        self.delegate
+            .gigography(id.into().0, self.sk.as_ref(), "artists", options)
+    }
+
+    /// Page of the gigography of the artist, each event handed to `f` as soon as it is decoded
+    ///
+    /// The first events are handed before the rest of the page is read, see
+    /// [`calendar_each`](ArtistEndpoint::calendar_each).
+    pub fn gigography_each<I, F>(&self, id: I, options: Option<Options>, mut f: F) -> SkResult<SkResultSet<Event>>
+    where
+        I: Into<ArtistId>,
+        F: FnMut(Event),
+    {
         self.delegate
-            .gigography(id, self.sk.as_ref(), "artists", options)
+            .gigography_each(id.into().0, self.sk.as_ref(), "artists", options, &mut f)
+    }
+
+    /// Retrieve [Gigography](https://www.songkick.com/developer/past-events-for-artist) for an Artist
+    /// with [MusicBrainz](https://musicbrainz.org) ID, e.g. an [`Identifier`](crate::resources::identifier::Identifier)
+    ///
+    /// ```rust,no_run
+    /// use songkick::SongKick;
+    ///
+    /// let sk = SongKick::new("API_KEY");
+    /// // RadioHead MusicBrainz ID
+    /// let events = sk.artist.gigography_by_mbid("a74b1b7f-71a5-4011-9441-d0b5e4122711", None).unwrap();
+    /// ```
+    pub fn gigography_by_mbid<M>(&self, mbid: M, options: Option<Options>) -> SkResult<SkResultSet<Event>>
+    where
+        M: fmt::Display,
+    {
+        let mbid = mbid.to_string();
+        if mbid.trim().is_empty() {
+            return Err(SkError::InvalidInput(String::from("MusicBrainz ID must not be empty")));
+        }
+        let url = format!(
+            "{}/artists/mbid:{}/gigography.json?apikey={}",
+            self.sk.base_path_for("artists.gigography"),
+            utf8_percent_encode(mbid.trim(), PATH_SEGMENT_ENCODE_SET),
+            self.sk.api_key()
+        );
+        self.delegate.fetch(&url, self.sk.as_ref(), options, "artists.gigography")
+    }
+}
+
+impl ArtistApi for ArtistEndpoint {
+    fn get(&self, id: ArtistId) -> SkResult<SkResultSet<Artist>> {
+        ArtistEndpoint::get(self, id)
+    }
+
+    fn search_by_name(&self, name: &str) -> SkResult<SkResultSet<Artist>> {
+        ArtistEndpoint::search_by_name(self, name)
+    }
+
+    fn calendar(&self, id: ArtistId, options: Option<Options>) -> SkResult<SkResultSet<Event>> {
+        ArtistEndpoint::calendar(self, id, options)
+    }
+
+    fn gigography(&self, id: ArtistId, options: Option<Options>) -> SkResult<SkResultSet<Event>> {
+        ArtistEndpoint::gigography(self, id, options)
     }
 }
@@ -0,0 +1,131 @@
+use crate::options::Options;
+use crate::SkResult;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+
+/// Result of `fetch` for each of `ids`, calling it from `concurrency` threads at most
+///
+/// Duplicated IDs are fetched once.
+pub(crate) fn fan_out<K, V, F>(ids: &[K], concurrency: usize, fetch: F) -> HashMap<K, SkResult<V>>
+where
+    K: Copy + Eq + Hash + Send + Sync,
+    V: Send,
+    F: Fn(K) -> SkResult<V> + Sync,
+{
+    let mut seen = HashSet::with_capacity(ids.len());
+    let ids: Vec<K> = ids.iter().copied().filter(|&id| seen.insert(id)).collect();
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(HashMap::with_capacity(ids.len()));
+
+    thread::scope(|scope| {
+        for _ in 0..concurrency.clamp(1, ids.len().max(1)) {
+            scope.spawn(|| {
+                // Each worker takes the next ID until there is none left
+                while let Some(&id) = ids.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let result = fetch(id);
+                    results.lock().unwrap_or_else(PoisonError::into_inner).insert(id, result);
+                }
+            });
+        }
+    });
+    results.into_inner().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Options of `page`, with the filters and the order of `options` if any
+pub(crate) fn for_page(options: &Option<Options>, page: Options) -> Options {
+    match options {
+        Some(options) => options.for_page(page),
+        None => page,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::SkError;
+    use crate::resources::ids::ArtistId;
+    use crate::testing::MockTransport;
+    use crate::transport::HttpResponse;
+    use crate::SongKickBuilder;
+    use std::time::Duration;
+
+    #[test]
+    fn bounded_concurrency() {
+        let running = AtomicUsize::new(0);
+        let most = AtomicUsize::new(0);
+        let ids: Vec<u64> = (0..20).chain(0..5).collect();
+        let results = fan_out(&ids, 3, |id| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            most.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(5));
+            running.fetch_sub(1, Ordering::SeqCst);
+            if id == 7 {
+                Err(SkError::InvalidInput(String::from("seven")))
+            } else {
+                Ok(id * 2)
+            }
+        });
+
+        assert_eq!(20, results.len());
+        assert_eq!(6, *results[&3].as_ref().unwrap());
+        assert!(results[&7].is_err());
+        assert!(most.load(Ordering::SeqCst) <= 3);
+        assert!(fan_out(&[] as &[u64], 3, Ok).is_empty());
+    }
+
+    #[test]
+    fn artist_calendars() {
+        let calendar = crate::testing::fixtures::ARTIST_CALENDAR.json();
+        let mock = MockTransport::new()
+            .on("artists/324967/calendar.json", HttpResponse::json(calendar))
+            .on("artists/324967/gigography.json", HttpResponse::json(calendar))
+            .on("artists/1/calendar.json", HttpResponse::new(500, "oops"));
+        let sk = SongKickBuilder::new("KEY").transport(mock.clone()).batch_concurrency(2).build();
+
+        let options = crate::options::OptionsBuilder::new()
+            .filter(|f| {
+                f.min_date("2017-06-06");
+            })
+            .build();
+        let calendars = sk.artist.calendars(&[ArtistId(324967), ArtistId(1), ArtistId(324967)], Some(options));
+        assert_eq!(2, calendars.len());
+        assert_eq!(33, calendars[&ArtistId(324967)].as_ref().unwrap().len());
+        assert!(matches!(
+            calendars[&ArtistId(1)].as_ref().err().unwrap().inner(),
+            SkError::Http { status: 500, .. }
+        ));
+        let requests = mock.requests();
+        assert_eq!(2, requests.len());
+        assert!(requests.iter().all(|url| url.contains("min_date=2017%2D06%2D06&page=1&per_page=50")));
+
+        let gigographies = sk.artist.gigographies(&[ArtistId(324967)], None);
+        assert_eq!(33, gigographies[&ArtistId(324967)].as_ref().unwrap().len());
+    }
+
+    #[test]
+    fn many_artists() {
+        let calendar = crate::testing::fixtures::ARTIST_CALENDAR.json();
+        let mock = MockTransport::new()
+            .on("artists/324967.json", crate::testing::fixtures::ARTIST.response())
+            .on("artists/2.json", crate::testing::fixtures::NOT_FOUND.response())
+            .on("artists/2/calendar.json", HttpResponse::json(calendar))
+            .on("artists/3100651.json", crate::testing::fixtures::NOT_FOUND.response())
+            .on("artists/3100651/calendar.json", HttpResponse::json(calendar))
+            .on("artists/4.json", HttpResponse::new(500, "oops"));
+        let sk = SongKickBuilder::new("KEY").transport(mock.clone()).batch_concurrency(2).build();
+        let ids = [ArtistId(324967), ArtistId(2), ArtistId(3100651), ArtistId(4)];
+        let artists = sk.artist.get_many(&ids);
+
+        assert_eq!(4, artists.len());
+        assert_eq!("Placebo", artists[&ArtistId(324967)].as_ref().unwrap().display_name);
+        assert!(matches!(artists[&ArtistId(4)].as_ref().err().unwrap().inner(), SkError::Http { status: 500, .. }));
+        // Not in the line-up of the calendar either
+        assert!(matches!(artists[&ArtistId(2)].as_ref().err().unwrap().inner(), SkError::NotFound { .. }));
+        // Supporting Placebo
+        assert_eq!("The Mirror Trap", artists[&ArtistId(3100651)].as_ref().unwrap().display_name);
+        assert_eq!(6, mock.requests().len());
+    }
+}
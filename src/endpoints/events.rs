@@ -1,4 +1,5 @@
 use crate::resources::event::Event;
+use crate::resources::ids::EventId;
 use crate::result::{SkResultSet};
 use crate::SkResult;
 use crate::client::SongKickOpts;
@@ -6,6 +7,20 @@ use std::sync::Arc;
 use crate::endpoints::SkEndpoint;
 use crate::endpoints::SkEndpointInternal;
 use crate::options::Options;
+use crate::error::SkError;
+use crate::analysis::{civil_date, day_number};
+use crate::endpoints::all_pages;
+use std::net::IpAddr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Calls of the Event endpoint, as a trait to substitute fakes to [`EventEndpoint`] in tests
+pub trait EventApi {
+    /// Single Event with ID
+    fn get(&self, id: EventId) -> SkResult<SkResultSet<Event>>;
+
+    /// Search for Events
+    fn search(&self, options: Options) -> SkResult<SkResultSet<Event>>;
+}
 
 
 #[doc(hidden)]
@@ -29,33 +44,113 @@ pub struct EventEndpoint {
 
 impl SkEndpoint for EventEndpoint {
     type Model = Event;
+    type Id = EventId;
 
     fn new(sk: Arc<SongKickOpts>) -> EventEndpoint {
         let delegate = EventEndpointDelegate::new();
         EventEndpoint {
-            sk: sk,
-            delegate: delegate
+            sk,
+            delegate
         }
     }
-    /// Get a Single [Event](https://www.songkick.com/developer/events-details) with ID
-    fn get(&self, id: u64) -> SkResult<SkResultSet<Self::Model>> {
-        self.delegate.get(id, self.sk.as_ref(), "events")
+    fn get<I>(&self, id: I) -> SkResult<SkResultSet<Self::Model>>
+    where
+        I: Into<EventId>,
+    {
+        EventEndpoint::get(self, id)
     }
 }
 
 impl EventEndpoint {
+    /// Get a Single [Event](https://www.songkick.com/developer/events-details) with ID
+    pub fn get<I>(&self, id: I) -> SkResult<SkResultSet<Event>>
+    where
+        I: Into<EventId>,
+    {
+        self.delegate.get(id.into().0, self.sk.as_ref(), "events")
+    }
 
     /// Search for [Events](https://www.songkick.com/developer/event-search)
     pub fn search(&self, options: Options) -> SkResult<SkResultSet<Event>> {
-        let url = format!("{}/events.json?apikey={}", self.sk.base_path(), self.sk.api_key());
+        let url = format!("{}/events.json?apikey={}", self.sk.base_path_for("events.search"), self.sk.api_key());
 
-        self.delegate.fetch::<Event>(&url, self.sk.as_ref(), Some(options))
+        self.delegate
+            .fetch::<Event>(&url, self.sk.as_ref(), Some(options), "events.search")
     }
-}
-
-
 
+    /// Search for the upcoming [Events](https://www.songkick.com/developer/event-search) of the artists
+    /// named `name`, with the other filters, the paging and the order of `options`
+    ///
+    /// ```rust,no_run
+    /// use songkick::SongKick;
+    ///
+    /// let sk = SongKick::new("API_KEY");
+    /// for event in sk.event.for_artist_name("Radiohead", None).unwrap() {
+    ///     println!("{}", event.display_name);
+    /// }
+    /// ```
+    pub fn for_artist_name<T>(&self, name: T, options: Option<Options>) -> SkResult<SkResultSet<Event>>
+    where
+        T: Into<String>,
+    {
+        let name = name.into();
+        if name.trim().is_empty() {
+            return Err(SkError::InvalidInput(String::from("artist name must not be empty")));
+        }
+        self.search(Options::filtered(options, |filter| {
+            filter.artist_name(name);
+        }))
+    }
 
+    /// Upcoming [Events](https://www.songkick.com/developer/event-search) near the IP address `ip` in
+    /// the next `days` days, today (UTC) included, sorted by date
+    ///
+    /// Every page of the search is fetched.
+    ///
+    /// ```rust,no_run
+    /// use songkick::SongKick;
+    /// use std::net::Ipv4Addr;
+    ///
+    /// let sk = SongKick::new("API_KEY");
+    /// for event in sk.event.near_ip(Ipv4Addr::new(94, 228, 36, 39), 7).unwrap() {
+    ///     println!("{}", event.display_name);
+    /// }
+    /// ```
+    pub fn near_ip<I>(&self, ip: I, days: u32) -> SkResult<Vec<Event>>
+    where
+        I: Into<IpAddr>,
+    {
+        let ip = ip.into();
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+        let today = (secs / 86_400) as i64;
+        let options = Options::filtered(None, |filter| {
+            filter
+                .location(format!("ip:{}", ip))
+                .min_date(iso_date(today))
+                .max_date(iso_date(today + i64::from(days.max(1)) - 1));
+        });
+
+        let mut events = all_pages(|page| self.search(options.for_page(page)))?;
+        let day = |event: &Event| {
+            event.start.date.as_ref().and_then(|date| day_number(&date.to_string())).unwrap_or(i64::MAX)
+        };
+        events.sort_by(|a, b| day(a).cmp(&day(b)).then_with(|| a.start.time.cmp(&b.start.time)));
+        Ok(events)
+    }
+}
 
+/// `YYYY-MM-DD` date `days` from 1970-01-01
+fn iso_date(days: i64) -> String {
+    let (year, month, day) = civil_date(days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
 
+impl EventApi for EventEndpoint {
+    fn get(&self, id: EventId) -> SkResult<SkResultSet<Event>> {
+        EventEndpoint::get(self, id)
+    }
 
+    fn search(&self, options: Options) -> SkResult<SkResultSet<Event>> {
+        EventEndpoint::search(self, options)
+    }
+}
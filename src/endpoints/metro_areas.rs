@@ -0,0 +1,182 @@
+use crate::client::SongKickOpts;
+use crate::endpoints::{all_pages, Pages, SkEndpointInternal};
+use crate::error::SkError;
+use crate::options::{format_with_options, Options, OptionsBuilder};
+use crate::resources::event::Event;
+use crate::resources::ids::MetroAreaId;
+use crate::resources::location::LocationMatch;
+use crate::resources::metro_area::MetroArea;
+use crate::result::SkResultSet;
+use crate::util::encode;
+use crate::SkResult;
+use std::sync::Arc;
+
+/// Calls of the Metro Area endpoint, as a trait to substitute fakes to [`MetroAreaEndpoint`] in tests
+pub trait MetroAreaApi {
+    /// Search the cities and metro areas of a `location`
+    fn locate(&self, location: &str) -> SkResult<SkResultSet<LocationMatch>>;
+
+    /// Page of the upcoming events in the metro area
+    fn calendar(&self, id: MetroAreaId, options: Option<Options>) -> SkResult<SkResultSet<Event>>;
+
+    /// Lazy iterator over the pages of the calendar of the metro area
+    fn calendar_pages(&self, id: MetroAreaId) -> Pages<'_, Event> {
+        Pages::new(move |options| self.calendar(id, Some(options)))
+    }
+
+    /// All the upcoming events in the metro area, fetching every page of the calendar
+    fn calendar_all(&self, id: MetroAreaId) -> SkResult<Vec<Event>> {
+        all_pages(|options| self.calendar(id, Some(options)))
+    }
+
+    /// Metro Area with ID, read from its upcoming events
+    fn get(&self, id: MetroAreaId) -> SkResult<MetroArea> {
+        self.calendar(id, Some(lookup_page()))
+            .and_then(|events| metro_area_in(events, id))
+    }
+}
+
+/// First page of the calendar read by [`MetroAreaApi::get`]
+fn lookup_page() -> Options {
+    OptionsBuilder::new().paging(1, LOOKUP_PAGE_SIZE).build()
+}
+
+/// Metro area `id` of the venue of one of `events`
+fn metro_area_in(events: SkResultSet<Event>, id: MetroAreaId) -> SkResult<MetroArea> {
+    events
+        .filter_map(|event| event.venue.metro_area)
+        .find(|metro_area| metro_area.id == id)
+        .ok_or_else(|| SkError::NotFound {
+            body: format!("no upcoming event in metro area {} to read it from", id),
+        })
+}
+
+/// Events of the calendar read by [`MetroAreaApi::get`], a few in case the first ones are at
+/// venues of neighbouring areas
+const LOOKUP_PAGE_SIZE: u64 = 5;
+
+#[doc(hidden)]
+struct MetroAreaEndpointDelegate {}
+
+impl SkEndpointInternal for MetroAreaEndpointDelegate {
+    type Model = MetroArea;
+    fn new() -> MetroAreaEndpointDelegate {
+        MetroAreaEndpointDelegate {}
+    }
+}
+
+pub struct MetroAreaEndpoint {
+    delegate: MetroAreaEndpointDelegate,
+    sk: Arc<SongKickOpts>,
+}
+
+impl MetroAreaEndpoint {
+    pub fn new(sk: Arc<SongKickOpts>) -> MetroAreaEndpoint {
+        MetroAreaEndpoint {
+            delegate: MetroAreaEndpointDelegate::new(),
+            sk,
+        }
+    }
+
+    /// [Search](https://www.songkick.com/developer/location-search) the cities and metro areas of a `location`
+    ///
+    /// The location is `clientip` to locate the IP address of the caller, `ip:94.228.36.39`
+    /// for another IP address, or `geo:51.5078,-0.128` for coordinates.
+    ///
+    /// ```rust,no_run
+    /// use songkick::SongKick;
+    ///
+    /// let sk = SongKick::new("API_KEY");
+    /// let nearest = sk.metro_area.locate("clientip").unwrap().next();
+    /// if let Some(location) = nearest {
+    ///     println!("{} ({})", location.metro_area.display_name, location.metro_area.id);
+    /// }
+    /// ```
+    pub fn locate(&self, location: &str) -> SkResult<SkResultSet<LocationMatch>> {
+        let url = format!(
+            "{}/search/locations.json?location={}&apikey={}",
+            self.sk.base_path_for("locations.search"),
+            encode(location),
+            self.sk.api_key()
+        );
+        self.delegate
+            .fetch(&url, self.sk.as_ref(), None, "locations.search")
+    }
+
+    /// Upcoming events in the metro area
+    pub fn calendar<I>(&self, id: I, options: Option<Options>) -> SkResult<SkResultSet<Event>>
+    where
+        I: Into<MetroAreaId>,
+    {
+        self.delegate
+            .calendar(id.into().0, self.sk.as_ref(), "metro_areas", options)
+    }
+
+    /// Page of the upcoming events of the metro area, each event handed to `f` as soon as it is
+    /// decoded, see [`ArtistEndpoint::calendar_each`](crate::endpoints::ArtistEndpoint::calendar_each)
+    pub fn calendar_each<I, F>(&self, id: I, options: Option<Options>, mut f: F) -> SkResult<SkResultSet<Event>>
+    where
+        I: Into<MetroAreaId>,
+        F: FnMut(Event),
+    {
+        self.delegate
+            .calendar_each(id.into().0, self.sk.as_ref(), "metro_areas", options, &mut f)
+    }
+
+    /// Lazy iterator over the pages of the calendar of the metro area
+    pub fn calendar_pages<I>(&self, id: I) -> Pages<'_, Event>
+    where
+        I: Into<MetroAreaId>,
+    {
+        let id = id.into();
+        Pages::new(move |options| self.calendar(id, Some(options)))
+    }
+
+    /// All the upcoming events in the metro area, fetching every page of the calendar
+    pub fn calendar_all<I>(&self, id: I) -> SkResult<Vec<Event>>
+    where
+        I: Into<MetroAreaId>,
+    {
+        let id = id.into();
+        all_pages(|options| self.calendar(id, Some(options)))
+    }
+
+    /// Metro Area with ID, e.g. to show the name and the country of a stored ID
+    ///
+    /// SongKick has no details of metro areas: they are read from the venues of the first
+    /// events of the calendar, a [`SkError::NotFound`] for the areas without upcoming events.
+    ///
+    /// ```rust,no_run
+    /// use songkick::SongKick;
+    ///
+    /// let sk = SongKick::new("API_KEY");
+    /// // London ID
+    /// let london = sk.metro_area.get(24426).unwrap();
+    /// println!("{}, {}", london.display_name, london.country.display_name);
+    /// ```
+    pub fn get<I>(&self, id: I) -> SkResult<MetroArea>
+    where
+        I: Into<MetroAreaId>,
+    {
+        let id = id.into();
+        let events = self.calendar(id, Some(lookup_page()))?;
+        metro_area_in(events, id).map_err(|err| {
+            let url = self.delegate.calendar_url(id.0, self.sk.as_ref(), "metro_areas");
+            err.with_request("metro_areas.calendar", &format_with_options(&url, Some(lookup_page())))
+        })
+    }
+}
+
+impl MetroAreaApi for MetroAreaEndpoint {
+    fn get(&self, id: MetroAreaId) -> SkResult<MetroArea> {
+        MetroAreaEndpoint::get(self, id)
+    }
+
+    fn locate(&self, location: &str) -> SkResult<SkResultSet<LocationMatch>> {
+        MetroAreaEndpoint::locate(self, location)
+    }
+
+    fn calendar(&self, id: MetroAreaId, options: Option<Options>) -> SkResult<SkResultSet<Event>> {
+        MetroAreaEndpoint::calendar(self, id, options)
+    }
+}
@@ -1,24 +1,38 @@
 use crate::client::SongKickOpts;
+use crate::error::SkError;
+use crate::instrument::RequestTrace;
 use crate::options::{format_with_options, Options};
 use crate::resources::event::Event;
-use crate::resources::Resource;
-use crate::result::SkResultSet;
+use crate::resources::TopLevelResource;
+use crate::result::{api_error, SkResultSet};
+use crate::transport::{HttpResponse, ResponseHeaders, StreamedResponse};
 use crate::util::encode;
 use crate::SkResult;
 
+use serde_json::Value;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 mod artists;
+mod batch;
 mod events;
+mod metro_areas;
+mod pages;
+mod users;
+mod venues;
 
-pub use crate::endpoints::artists::ArtistEndpoint;
-pub use crate::endpoints::events::EventEndpoint;
-
-use reqwest;
+pub use crate::endpoints::artists::{ArtistApi, ArtistEndpoint};
+pub use crate::endpoints::events::{EventApi, EventEndpoint};
+pub use crate::endpoints::metro_areas::{MetroAreaApi, MetroAreaEndpoint};
+pub use crate::endpoints::pages::{PageState, Pages};
+pub use crate::endpoints::users::{CalendarReason, UserApi, UserEndpoint};
+pub use crate::endpoints::venues::{VenueApi, VenueEndpoint};
+pub(crate) use crate::endpoints::batch::{fan_out, for_page};
+pub(crate) use crate::endpoints::pages::{all_pages, MAX_PER_PAGE};
 
 #[doc(hidden)]
 trait SkEndpointInternal {
-    type Model: Resource;
+    type Model: TopLevelResource;
     fn new() -> Self;
     fn get(
         &self,
@@ -26,15 +40,19 @@ trait SkEndpointInternal {
         sk: &SongKickOpts,
         ctx_path: &str,
     ) -> SkResult<SkResultSet<Self::Model>> {
-        let url = format!(
+        let url = self.get_url(id, sk, ctx_path);
+        self.fetch(&url, sk, None, &format!("{}.get", ctx_path))
+    }
+
+    /// URL of [`get`](SkEndpointInternal::get), to attach to the errors read from its response
+    fn get_url(&self, id: u64, sk: &SongKickOpts, ctx_path: &str) -> String {
+        format!(
             "{}/{}/{}.json?apikey={}",
-            sk.base_path(),
+            sk.base_path_for(&format!("{}.get", ctx_path)),
             ctx_path,
             id,
             sk.api_key()
-        );
-
-        self.fetch(&url, sk, None)
+        )
     }
 
     fn calendar(
@@ -44,14 +62,32 @@ trait SkEndpointInternal {
         ctx_path: &str,
         options: Option<Options>,
     ) -> SkResult<SkResultSet<Event>> {
-        let url = format!(
+        let url = self.calendar_url(id, sk, ctx_path);
+        self.fetch(&url, sk, options, &format!("{}.calendar", ctx_path))
+    }
+
+    /// [`calendar`](SkEndpointInternal::calendar) handing each event to `f` as it is decoded
+    fn calendar_each(
+        &self,
+        id: u64,
+        sk: &SongKickOpts,
+        ctx_path: &str,
+        options: Option<Options>,
+        f: &mut dyn FnMut(Event),
+    ) -> SkResult<SkResultSet<Event>> {
+        let url = self.calendar_url(id, sk, ctx_path);
+        self.fetch_with(&url, sk, options, &format!("{}.calendar", ctx_path), Some(f))
+    }
+
+    /// URL of [`calendar`](SkEndpointInternal::calendar), without its options
+    fn calendar_url(&self, id: u64, sk: &SongKickOpts, ctx_path: &str) -> String {
+        format!(
             "{}/{}/{}/calendar.json?apikey={}",
-            sk.base_path(),
+            sk.base_path_for(&format!("{}.calendar", ctx_path)),
             ctx_path,
             id,
             sk.api_key()
-        );
-        self.fetch(&url, sk, options)
+        )
     }
     fn search_by_name(
         &self,
@@ -60,14 +96,20 @@ trait SkEndpointInternal {
         ctx_path: &str,
         options: Option<Options>,
     ) -> SkResult<SkResultSet<Self::Model>> {
+        if text.trim().is_empty() {
+            return Err(SkError::InvalidInput(String::from(
+                "search query must not be empty",
+            )));
+        }
+        let endpoint = format!("{}.search", ctx_path);
         let url = format!(
             "{}/search/{}.json?query={}&apikey={}",
-            sk.base_path(),
+            sk.base_path_for(&endpoint),
             ctx_path,
             encode(text),
             sk.api_key()
         );
-        self.fetch(&url, sk, options)
+        self.fetch(&url, sk, options, &endpoint)
     }
 
     fn gigography(
@@ -77,15 +119,31 @@ trait SkEndpointInternal {
         ctx_path: &str,
         options: Option<Options>,
     ) -> SkResult<SkResultSet<Event>> {
-        let url = format!(
+        let url = self.gigography_url(id, sk, ctx_path);
+        self.fetch(&url, sk, options, &format!("{}.gigography", ctx_path))
+    }
+
+    /// [`gigography`](SkEndpointInternal::gigography) handing each event to `f` as it is decoded
+    fn gigography_each(
+        &self,
+        id: u64,
+        sk: &SongKickOpts,
+        ctx_path: &str,
+        options: Option<Options>,
+        f: &mut dyn FnMut(Event),
+    ) -> SkResult<SkResultSet<Event>> {
+        let url = self.gigography_url(id, sk, ctx_path);
+        self.fetch_with(&url, sk, options, &format!("{}.gigography", ctx_path), Some(f))
+    }
+
+    fn gigography_url(&self, id: u64, sk: &SongKickOpts, ctx_path: &str) -> String {
+        format!(
             "{}/{}/{}/gigography.json?apikey={}",
-            sk.base_path(),
+            sk.base_path_for(&format!("{}.gigography", ctx_path)),
             ctx_path,
             id,
             sk.api_key()
-        );
-
-        self.fetch(&url, sk, options)
+        )
     }
 
     fn fetch<M>(
@@ -93,21 +151,530 @@ trait SkEndpointInternal {
         base_path: &str,
         sk: &SongKickOpts,
         options: Option<Options>,
+        endpoint: &str,
     ) -> SkResult<SkResultSet<M>>
     where
-        M: Resource,
+        M: TopLevelResource,
     {
-        let url = format_with_options(&base_path, options);
-        let full_resp = reqwest::blocking::get(&url)?.text()?;
+        self.fetch_with(base_path, sk, options, endpoint, None)
+    }
+
+    /// [`fetch`](SkEndpointInternal::fetch), the results handed to `each` as they are decoded
+    /// instead of being kept in the set when given
+    fn fetch_with<M>(
+        &self,
+        base_path: &str,
+        sk: &SongKickOpts,
+        options: Option<Options>,
+        endpoint: &str,
+        each: Option<&mut dyn FnMut(M)>,
+    ) -> SkResult<SkResultSet<M>>
+    where
+        M: TopLevelResource,
+    {
+        let _turn = sk.scheduler().acquire(sk.priority());
+        let page = options.as_ref().and_then(Options::page);
+        let url = format_with_options(base_path, options);
+        if let Some(quota) = sk.quota() {
+            quota.spend().map_err(|err| err.with_request(endpoint, &url))?;
+        }
+        let trace = RequestTrace::start(endpoint, page);
+        trace.url(&url);
+        let result = trace.in_scope(|| fetch_url(&url, sk, &trace, each));
+        trace.finish(&result);
+        result.map_err(|err| err.with_request(endpoint, &url))
+    }
+}
 
-        let data = serde_json::from_str(&full_resp)?;
+fn fetch_url<M>(
+    url: &str,
+    sk: &SongKickOpts,
+    trace: &RequestTrace,
+    each: Option<&mut dyn FnMut(M)>,
+) -> SkResult<SkResultSet<M>>
+where
+    M: TopLevelResource,
+{
+    let resp = sk.transport().get_streamed(url)?;
+    trace.response(resp.status);
+    let headers = ResponseHeaders::select(&resp.headers);
+    decode_limited(resp, sk, each).map(|results| results.with_headers(headers))
+}
 
-        SkResultSet::from_json(&data)
+fn decode_limited<M>(
+    resp: StreamedResponse,
+    sk: &SongKickOpts,
+    each: Option<&mut dyn FnMut(M)>,
+) -> SkResult<SkResultSet<M>>
+where
+    M: TopLevelResource,
+{
+    let limit = match sk.max_response_size() {
+        Some(limit) => limit,
+        None => return decode_response(resp, sk, each),
+    };
+    let length = resp.header("content-length").and_then(|length| length.trim().parse::<u64>().ok());
+    if length.is_some_and(|length| length > limit) {
+        return Err(SkError::ResponseTooLarge { limit });
     }
+    let (resp, exceeded) = resp.limited(limit);
+    let result = decode_response(resp, sk, each);
+    if exceeded.load(Ordering::SeqCst) {
+        return Err(SkError::ResponseTooLarge { limit });
+    }
+    result
+}
+
+fn decode_response<M>(
+    resp: StreamedResponse,
+    sk: &SongKickOpts,
+    each: Option<&mut dyn FnMut(M)>,
+) -> SkResult<SkResultSet<M>>
+where
+    M: TopLevelResource,
+{
+    // Successful JSON pages are decoded as they are read, the others are read whole to
+    // tell their errors apart.
+    let is_json = resp.header("content-type").is_some_and(|content_type| content_type.contains("json"));
+    if resp.is_success() && is_json {
+        let body = sk.buffer_pool().reader(resp.body);
+        return match each {
+            Some(f) => SkResultSet::from_reader_each(body, sk.parse_mode(), f),
+            None => SkResultSet::from_reader(body, sk.parse_mode()),
+        };
+    }
+    let resp = resp.into_response_in(sk.buffer_pool())?;
+    let result = decode_buffered(&resp, sk);
+    sk.buffer_pool().put(resp.body.into_bytes());
+    match each {
+        Some(f) => result.map(|results| results.drain_into(f)),
+        None => result,
+    }
+}
+
+fn decode_buffered<M>(resp: &HttpResponse, sk: &SongKickOpts) -> SkResult<SkResultSet<M>>
+where
+    M: TopLevelResource,
+{
+    let data = decode_body(resp.header("content-type"), &resp.body);
+
+    if !resp.is_success() {
+        // Keep the dedicated variants of well-known statuses, but prefer the
+        // message of the API over a bare status for the others
+        if !matches!(resp.status, 401 | 403 | 404 | 429) {
+            if let Some(err) = data.as_ref().ok().and_then(api_error) {
+                return Err(err);
+            }
+        }
+        return Err(SkError::from_status(
+            resp.status,
+            resp.header("retry-after"),
+            &resp.body,
+        ));
+    }
+
+    let data = data?;
+
+    SkResultSet::from_json_with(&data, sk.parse_mode())
+}
+
+/// Parse the JSON `body` of a response, telling apart the pages that are not JSON at all
+fn decode_body(content_type: Option<&str>, body: &str) -> SkResult<Value> {
+    serde_json::from_str(body).map_err(|err| {
+        let is_json = content_type.is_some_and(|content_type| content_type.contains("json"));
+        if is_json {
+            SkError::from(err)
+        } else {
+            SkError::unexpected_content(content_type, body)
+        }
+    })
 }
 
 pub trait SkEndpoint {
-    type Model: Resource;
+    type Model: TopLevelResource;
+    /// Typed ID of `Model`
+    type Id;
     fn new(sk: Arc<SongKickOpts>) -> Self;
-    fn get(&self, id: u64) -> SkResult<SkResultSet<Self::Model>>;
+    fn get<I>(&self, id: I) -> SkResult<SkResultSet<Self::Model>>
+    where
+        I: Into<Self::Id>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::artist::Artist;
+    use crate::resources::ids::{ArtistId, EventId, MetroAreaId, VenueId};
+    use crate::testing::MockTransport;
+    use crate::SongKickBuilder;
+
+    #[test]
+    fn decode_html_page() {
+        let page = "<html><body><h1>Down for maintenance</h1></body></html>";
+        match decode_body(Some("text/html; charset=utf-8"), page) {
+            Err(SkError::UnexpectedContentType { ref content_type, ref snippet }) => {
+                assert_eq!("text/html; charset=utf-8", content_type);
+                assert_eq!(page, snippet);
+            }
+            _ => panic!("expected an UnexpectedContentType error"),
+        }
+
+        assert!(matches!(
+            decode_body(Some("application/json"), "{\"resultsPage\""),
+            Err(SkError::Decode { .. })
+        ));
+        assert!(decode_body(None, "{\"resultsPage\": {}}").is_ok());
+    }
+
+    #[test]
+    fn fetch_through_transport() {
+        let calendar = std::fs::read_to_string("fixtures/event/artist-324967-calendar.json").unwrap();
+        let mock = MockTransport::new()
+            .once("artists/324967/calendar.json", HttpResponse::new(429, "").with_header("Retry-After", "30"))
+            .on("artists/324967/calendar.json", HttpResponse::json(calendar))
+            .on("artists/1/calendar.json", HttpResponse::new(500, "oops"));
+        let sk = SongKickBuilder::new("KEY").transport(mock.clone()).build();
+
+        let err = sk.artist.calendar(324967, None).err().unwrap();
+        assert_eq!(Some("artists.calendar"), err.endpoint());
+        match err.inner() {
+            SkError::RateLimited { retry_after, .. } => {
+                assert_eq!(Some(std::time::Duration::from_secs(30)), *retry_after)
+            }
+            err => panic!("expected RateLimited, got {}", err),
+        }
+        assert_eq!(33, sk.artist.calendar(324967, None).unwrap().count());
+        assert!(matches!(
+            sk.artist.calendar(1, None).err().unwrap().inner(),
+            SkError::Http { status: 500, .. }
+        ));
+
+        let requests = mock.requests();
+        assert_eq!(3, requests.len());
+        assert!(requests[0].starts_with("http://api.songkick.com/api/3.0/artists/324967/calendar.json?apikey=KEY"));
+    }
+
+    #[test]
+    fn calendar_each_event() {
+        let mock = MockTransport::new()
+            .on("artists/324967/calendar.json", crate::testing::fixtures::ARTIST_CALENDAR.response())
+            .on("venues/17522/calendar.json", HttpResponse::new(200, crate::testing::fixtures::ARTIST_CALENDAR.json()))
+            .on("gigography.json", crate::testing::fixtures::ARTIST_GIGOGRAPHY.response());
+        let sk = SongKickBuilder::new("KEY").transport(mock.clone()).build();
+
+        let mut events = Vec::new();
+        let page = sk.artist.calendar_each(324967, None, |event| events.push(event)).unwrap();
+        assert_eq!((33, 0), (page.total_entries, page.count()));
+        assert_eq!(33, events.len());
+        // Read whole, without a JSON content type
+        let mut count = 0;
+        sk.venue.calendar_each(17522, None, |_| count += 1).unwrap();
+        assert_eq!(33, count);
+        count = 0;
+        sk.artist.gigography_each(324967, None, |_| count += 1).unwrap();
+        assert_eq!(6, count);
+    }
+
+    #[test]
+    fn pooled_page_buffers() {
+        use crate::transport::{BufferPool, StreamedResponse, Transport};
+        use std::io::Read;
+        use std::sync::Mutex;
+
+        let pool = Arc::new(BufferPool::new(1, 4096));
+
+        /// Calendar pages, keeping the addresses of the buffers their bodies are read into
+        #[derive(Clone, Default)]
+        struct Recording(Arc<Mutex<Vec<usize>>>);
+
+        struct Body(std::io::Cursor<&'static str>, Recording);
+
+        impl Read for Body {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                (self.1).0.lock().unwrap().push(buf.as_ptr() as usize);
+                self.0.read(buf)
+            }
+        }
+
+        impl Transport for Recording {
+            fn get(&self, _: &str) -> SkResult<HttpResponse> {
+                Ok(crate::testing::fixtures::ARTIST_CALENDAR.response())
+            }
+
+            fn get_streamed(&self, url: &str) -> SkResult<StreamedResponse> {
+                let mut resp = StreamedResponse::from(self.get(url)?);
+                resp.body = Box::new(Body(std::io::Cursor::new(crate::testing::fixtures::ARTIST_CALENDAR.json()), self.clone()));
+                Ok(resp)
+            }
+        }
+
+        let recording = Recording::default();
+        let sk = SongKickBuilder::new("KEY").transport(recording.clone()).buffer_pool(pool.clone()).build();
+        assert_eq!(33, sk.artist.calendar(324967, None).unwrap().count());
+        assert_eq!(1, pool.len());
+        let first = std::mem::take(&mut *recording.0.lock().unwrap());
+        assert_eq!(33, sk.artist.calendar(324967, None).unwrap().count());
+        let second = recording.0.lock().unwrap().clone();
+
+        // Every read of both pages into the same pooled buffer
+        assert!(first.len() > 1);
+        assert!(first.iter().chain(&second).all(|address| *address == first[0]));
+        assert_eq!(1, pool.len());
+    }
+
+    #[test]
+    fn gigography_by_mbid() {
+        let mock = MockTransport::new().on("gigography.json", crate::testing::fixtures::ARTIST_GIGOGRAPHY.response());
+        let sk = SongKickBuilder::new("KEY").transport(mock.clone()).build();
+
+        let mbid = "a74b1b7f-71a5-4011-9441-d0b5e4122711";
+        let events = sk.artist.gigography_by_mbid(mbid, Some(crate::options::OptionsBuilder::new().paging(2, 10).build())).unwrap();
+        assert_eq!(6, events.count());
+        assert!(matches!(sk.artist.gigography_by_mbid(" ", None), Err(SkError::InvalidInput(_))));
+
+        let requests = mock.requests();
+        assert_eq!(1, requests.len());
+        assert!(requests[0].starts_with(&format!("http://api.songkick.com/api/3.0/artists/mbid:{}/gigography.json?apikey=KEY", mbid)));
+        assert!(requests[0].contains("page=2"));
+    }
+
+    #[test]
+    fn response_headers_kept() {
+        let calendar = crate::testing::fixtures::ARTIST_CALENDAR
+            .response()
+            .with_header("X-RateLimit-Remaining", "7")
+            .with_header("X-Request-Id", "req-1")
+            .with_header("Server", "nginx");
+        let mock = MockTransport::new().on("calendar.json", calendar);
+        let both = [
+            SongKickBuilder::new("KEY").transport(mock.clone()).build(),
+            SongKickBuilder::new("KEY").transport(mock.clone()).max_response_size(1 << 20).build(),
+        ];
+        for sk in &both {
+            let events = sk.artist.calendar(324967, None).unwrap();
+            assert_eq!(Some(7), events.headers().rate_limit().and_then(|rate_limit| rate_limit.remaining));
+            assert_eq!(Some("req-1"), events.headers().request_id());
+            assert_eq!(None, events.headers().get("server"));
+        }
+    }
+
+    #[test]
+    fn events_for_artist_name() {
+        let mock = MockTransport::new().on("events.json", crate::testing::fixtures::EVENT_SEARCH.response());
+        let sk = SongKickBuilder::new("KEY").transport(mock.clone()).build();
+
+        assert!(sk.event.for_artist_name("Sigur Rós", None).unwrap().count() > 0);
+        let options = crate::options::OptionsBuilder::new().filter(|f| {
+            f.location("clientip");
+        });
+        sk.event.for_artist_name("Radiohead", Some(options.build())).unwrap();
+        sk.event.for_artist_name("Simon & Garfunkel", None).unwrap();
+        assert!(matches!(sk.event.for_artist_name("", None), Err(SkError::InvalidInput(_))));
+
+        let requests = mock.requests();
+        assert_eq!(3, requests.len());
+        assert!(requests[0].ends_with("/events.json?apikey=KEY&artist_name=Sigur%20R%C3%B3s"));
+        assert!(requests[1].ends_with("&artist_name=Radiohead&location=clientip"));
+        assert!(requests[2].ends_with("&artist_name=Simon%20%26%20Garfunkel"));
+    }
+
+    #[test]
+    fn events_near_ip() {
+        let mock = MockTransport::new().on("events.json", crate::testing::fixtures::EVENT_SEARCH.response());
+        let sk = SongKickBuilder::new("KEY").transport(mock.clone()).build();
+
+        let events = sk.event.near_ip(std::net::Ipv4Addr::new(94, 228, 36, 39), 7).unwrap();
+        assert!(!events.is_empty());
+        let days: Vec<i64> = events
+            .iter()
+            .filter_map(|event| crate::analysis::day_number(&event.start.date.as_ref()?.to_string()))
+            .collect();
+        assert!(days.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        let request = &mock.requests()[0];
+        assert!(request.contains("&location=ip:94.228.36.39"));
+        assert!(request.contains("&min_date=") && request.contains("&max_date="));
+        assert!(request.contains("&page=1"));
+    }
+
+    #[test]
+    fn metro_area_from_calendar() {
+        let empty = r#"{"resultsPage": {"status": "ok", "results": {}, "perPage": 5, "page": 1, "totalEntries": 0}}"#;
+        let mock = MockTransport::new()
+            .on("metro_areas/24426/calendar.json", crate::testing::fixtures::METRO_AREA_CALENDAR.response())
+            .on("metro_areas/1/calendar.json", HttpResponse::json(empty));
+        let sk = SongKickBuilder::new("KEY").transport(mock.clone()).build();
+
+        let london = sk.metro_area.get(24426).unwrap();
+        assert_eq!(MetroAreaId(24426), london.id);
+        assert_eq!(("London", "UK"), (london.display_name.as_str(), london.country.display_name.as_str()));
+        let err = sk.metro_area.get(1).err().unwrap();
+        assert!(matches!(err.inner(), SkError::NotFound { .. }));
+        assert_eq!(Some("metro_areas.calendar"), err.endpoint());
+        assert!(err.url().unwrap().ends_with("/metro_areas/1/calendar.json?apikey=REDACTED&page=1&per_page=5"));
+        assert!(mock.requests()[0].ends_with("&page=1&per_page=5"));
+    }
+
+    #[test]
+    fn resolve_artist_name() {
+        let names = ["The Cure Tribute", "Cure", "The Cure", "The Cult"];
+        let artists: Vec<_> = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| crate::testing::ArtistBuilder::new().id(i as u64 + 1).name(*name).json())
+            .collect();
+        let page = serde_json::json!({"resultsPage": {"status": "ok", "results": {"artist": artists},
+            "perPage": 50, "page": 1, "totalEntries": names.len()}});
+        let mock = MockTransport::new()
+            .on("query=zzz", HttpResponse::json(std::fs::read_to_string("fixtures/empty_search.json").unwrap()))
+            .on("search/artists.json", HttpResponse::json(page.to_string()));
+        let sk = SongKickBuilder::new("KEY").transport(mock.clone()).build();
+
+        assert_eq!(Some(ArtistId(3)), sk.artist.resolve("the cure").unwrap().map(|artist| artist.id));
+        assert_eq!(Some(ArtistId(2)), sk.artist.resolve("Cure").unwrap().map(|artist| artist.id));
+        assert!(sk.artist.resolve("The Smiths").unwrap().is_none());
+        assert!(sk.artist.resolve("zzz").unwrap().is_none());
+        assert!(sk.artist.resolve("Florence + the Machine").unwrap().is_none());
+        let requests = mock.requests();
+        assert!(requests[0].contains("query=the%20cure"));
+        assert!(requests[4].contains("query=Florence%20%2B%20the%20Machine&apikey=KEY"));
+    }
+
+    #[cfg(feature = "strsim")]
+    #[test]
+    fn ranked_searches() {
+        let server = crate::testing::MockSongKick::start();
+        let sk = server.client();
+        let artists = sk.artist.search_ranked("placebo").unwrap();
+        assert_eq!(10, artists.len());
+        assert_eq!("Placebo", artists[0].item.display_name);
+        assert!(artists.windows(2).all(|pair| pair[0].score >= pair[1].score));
+    }
+
+    #[test]
+    fn venue_search() {
+        let venue = crate::testing::VenueBuilder::new().name("O2 Academy Brixton").json();
+        let page = serde_json::json!({"resultsPage": {"status": "ok", "results": {"venue": [venue]},
+            "perPage": 50, "page": 1, "totalEntries": 1}});
+        let mock = MockTransport::new().on("search/venues.json", HttpResponse::json(page.to_string()));
+        let sk = SongKickBuilder::new("KEY").transport(mock.clone()).build();
+
+        let venues: Vec<_> = sk.venue.search_by_name("brixton").unwrap().collect();
+        assert_eq!(Some("O2 Academy Brixton"), venues[0].display_name.as_deref());
+        assert!(mock.requests()[0].contains("/search/venues.json?query=brixton&apikey=KEY"));
+        #[cfg(feature = "strsim")]
+        assert!(sk.venue.search_ranked("o2 academy brixton").unwrap()[0].score > 0.99);
+    }
+
+    #[test]
+    fn on_this_day_from_gigography() {
+        let mock = MockTransport::new().on("gigography.json", crate::testing::fixtures::ARTIST_GIGOGRAPHY.response());
+        let sk = SongKickBuilder::new("KEY").transport(mock.clone()).build();
+
+        let events = sk.artist.on_this_day(324967, 3, 11).unwrap();
+        assert_eq!(vec![EventId(1292031)], events.iter().map(|event| event.id).collect::<Vec<_>>());
+        // Postponed
+        assert!(sk.artist.on_this_day(324967, 11, 21).unwrap().is_empty());
+        assert!(matches!(sk.artist.on_this_day(324967, 2, 30), Err(SkError::InvalidInput(_))));
+        assert!(matches!(sk.artist.on_this_day(324967, 13, 1), Err(SkError::InvalidInput(_))));
+        // The first event, then the single page of the gigography, twice
+        assert_eq!(4, mock.requests().len());
+    }
+
+    #[test]
+    fn on_this_day_by_year() {
+        let event = |date: &str| crate::testing::EventBuilder::new().id(7).date(date);
+        let page = |date, total| {
+            let mut page = crate::testing::results_page(&[event(date)]);
+            page["resultsPage"]["totalEntries"] = serde_json::json!(total);
+            HttpResponse::json(page.to_string())
+        };
+        let mock = MockTransport::new()
+            .on("gigography.json?apikey=KEY&page=1&per_page=1", page("2010-03-11", 100_000))
+            .on("gigography.json", page("2015-03-11", 1));
+        let sk = SongKickBuilder::new("KEY").transport(mock.clone()).build();
+
+        let events = sk.artist.on_this_day(324967, 3, 11).unwrap();
+        assert_eq!(1, events.len());
+        let requests = mock.requests();
+        assert!(requests.len() > 10);
+        assert!(requests[1].contains("&min_date=2010%2D03%2D11&max_date=2010%2D03%2D11&page=1"));
+        assert!(requests[2].contains("&min_date=2011%2D03%2D11&max_date=2011%2D03%2D11&page=1"));
+    }
+
+    #[test]
+    fn response_size_limit() {
+        let calendar = crate::testing::fixtures::ARTIST_CALENDAR.json();
+        let size = calendar.len() as u64;
+        let mock = MockTransport::new()
+            .on("artists/1/calendar.json", HttpResponse::json(calendar))
+            .on("artists/2/calendar.json", HttpResponse::new(502, "x".repeat(2 * size as usize)))
+            .on(
+                "artists/3/calendar.json",
+                HttpResponse::json("{}").with_header("Content-Length", (size + 1).to_string()),
+            );
+        let limited = |limit| SongKickBuilder::new("KEY").transport(mock.clone()).max_response_size(limit).build();
+        let too_large = |sk: &crate::SongKick, id: u64| {
+            matches!(sk.artist.calendar(id, None).err().unwrap().inner(), SkError::ResponseTooLarge { .. })
+        };
+
+        assert_eq!(33, limited(size).artist.calendar(1, None).unwrap().count());
+        let sk = limited(size - 1);
+        assert!(too_large(&sk, 1));
+        assert!(too_large(&sk, 2));
+        assert!(too_large(&limited(size), 3));
+        assert!(matches!(
+            limited(10 * size).artist.calendar(2, None).err().unwrap().inner(),
+            SkError::Http { status: 502, .. }
+        ));
+    }
+
+    /// Artists of the fixtures, whatever the IDs
+    struct FakeArtists;
+
+    impl ArtistApi for FakeArtists {
+        fn get(&self, _: ArtistId) -> SkResult<SkResultSet<Artist>> {
+            crate::testing::fixtures::ARTIST.load()
+        }
+
+        fn search_by_name(&self, _: &str) -> SkResult<SkResultSet<Artist>> {
+            crate::testing::fixtures::ARTIST_SEARCH.load()
+        }
+
+        fn calendar(&self, _: ArtistId, _: Option<Options>) -> SkResult<SkResultSet<Event>> {
+            crate::testing::fixtures::ARTIST_CALENDAR.load()
+        }
+
+        fn gigography(&self, _: ArtistId, _: Option<Options>) -> SkResult<SkResultSet<Event>> {
+            crate::testing::fixtures::ARTIST_GIGOGRAPHY.load()
+        }
+    }
+
+    #[test]
+    fn endpoints_as_trait_objects() {
+        let fake: Box<dyn ArtistApi> = Box::new(FakeArtists);
+        assert_eq!("Placebo", fake.get(ArtistId(1)).unwrap().next().unwrap().display_name);
+        assert_eq!(33, fake.calendar_all(ArtistId(1)).unwrap().len());
+        assert_eq!(6, fake.gigography_pages(ArtistId(1)).items().count());
+
+        let server = crate::testing::MockSongKick::start();
+        let sk = server.client();
+        let artists: &dyn ArtistApi = &sk.artist;
+        assert_eq!(10, artists.search_by_name("placebo").unwrap().count());
+        assert_eq!(33, artists.calendar_pages(ArtistId(324967)).per_page(20).items().count());
+        let venues: &dyn VenueApi = &sk.venue;
+        assert_eq!(33, venues.calendar_all(VenueId(17522)).unwrap().len());
+        assert_eq!(Some(4921), venues.get(VenueId(17522)).unwrap().next().unwrap().capacity);
+        let users: &dyn UserApi = &sk.user;
+        assert_eq!(3, users.calendar_all("fan", CalendarReason::Attendance).unwrap().len());
+        let metro_areas: &dyn MetroAreaApi = &sk.metro_area;
+        assert_eq!(2, metro_areas.locate("clientip").unwrap().count());
+        let events: &dyn EventApi = &sk.event;
+        assert_eq!(1, events.get(EventId(27081999)).unwrap().count());
+
+        // Inherent, not ambiguous with SkEndpoint and the Api traits in scope
+        assert_eq!(1, sk.artist.get(324967).unwrap().count());
+        assert_eq!(1, sk.event.get(27081999).unwrap().count());
+        assert_eq!(1, sk.venue.get(17522).unwrap().count());
+    }
 }
@@ -0,0 +1,267 @@
+use crate::options::{FilterBuilder, Options, OptionsBuilder, Sort};
+use crate::resources::TopLevelResource;
+use crate::result::SkResultSet;
+use crate::SkResult;
+use serde::{Deserialize, Serialize};
+
+/// Largest page size accepted by the API
+pub(crate) const MAX_PER_PAGE: u64 = 50;
+
+/// Filters set with [`Pages::filter`]
+type PageFilter<'a> = Box<dyn Fn(&mut FilterBuilder) + 'a>;
+
+/// Progress of a [`Pages`] iteration, to resume it later with [`Pages::resume`]
+///
+/// Errors do not advance the progress, resuming after a failed page fetches it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct PageState {
+    /// Next page to fetch
+    pub page: u64,
+    /// Size of the pages
+    pub per_page: u64,
+    /// Results fetched so far
+    pub fetched: u64,
+    /// Whether the last page was fetched
+    pub complete: bool,
+}
+
+/// Lazy iterator over the pages of a paginated call
+///
+/// Each page is fetched when the iterator reaches it, so exports of many pages only
+/// hold one page in memory. The iteration ends after the last page or the first error.
+pub struct Pages<'a, M: TopLevelResource> {
+    fetch: Box<dyn FnMut(Options) -> SkResult<SkResultSet<M>> + 'a>,
+    page: u64,
+    per_page: u64,
+    sort: Option<Sort>,
+    filter: Option<PageFilter<'a>>,
+    fetched: u64,
+    complete: bool,
+    done: bool,
+}
+
+impl<'a, M: TopLevelResource> Pages<'a, M> {
+    /// Pages of `fetch`, which is called with the options of each page
+    pub(crate) fn new<F>(fetch: F) -> Pages<'a, M>
+    where
+        F: FnMut(Options) -> SkResult<SkResultSet<M>> + 'a,
+    {
+        Pages {
+            fetch: Box::new(fetch),
+            page: 1,
+            per_page: MAX_PER_PAGE,
+            sort: None,
+            filter: None,
+            fetched: 0,
+            complete: false,
+            done: false,
+        }
+    }
+
+    /// Progress of the iteration
+    pub fn state(&self) -> PageState {
+        PageState {
+            page: self.page,
+            per_page: self.per_page,
+            fetched: self.fetched,
+            complete: self.complete,
+        }
+    }
+
+    /// Continue from `state`, with the page size it was taken with
+    pub fn resume(mut self, state: PageState) -> Pages<'a, M> {
+        self.page = state.page.max(1);
+        self.per_page = state.per_page.clamp(1, MAX_PER_PAGE);
+        self.fetched = state.fetched;
+        self.complete = state.complete;
+        self.done = state.complete;
+        self
+    }
+
+    /// Size of the requested pages, at most 50
+    pub fn per_page(mut self, per_page: u64) -> Pages<'a, M> {
+        self.per_page = per_page.clamp(1, MAX_PER_PAGE);
+        self
+    }
+
+    /// Order of the results, by date
+    pub fn sort(mut self, sort: Sort) -> Pages<'a, M> {
+        self.sort = Some(sort);
+        self
+    }
+
+    /// Filters applied to every page (e.g. a `min_date`)
+    pub fn filter<F>(mut self, filter: F) -> Pages<'a, M>
+    where
+        F: Fn(&mut FilterBuilder) + 'a,
+    {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Iterator over the results of every page
+    pub fn items(self) -> impl Iterator<Item = SkResult<M>> + 'a
+    where
+        M: 'a,
+    {
+        self.flat_map(|page| {
+            let (items, err) = match page {
+                Ok(page) => (Some(page), None),
+                Err(err) => (None, Some(Err(err))),
+            };
+            items.into_iter().flatten().map(Ok).chain(err)
+        })
+    }
+}
+
+impl<'a, M: TopLevelResource> Iterator for Pages<'a, M> {
+    type Item = SkResult<SkResultSet<M>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut options = OptionsBuilder::new().paging(self.page, self.per_page);
+        if let Some(sort) = self.sort {
+            options = options.sort(sort);
+        }
+        if let Some(ref filter) = self.filter {
+            options = options.filter(filter);
+        }
+        let options = options.build();
+        match (self.fetch)(options) {
+            Ok(page) => {
+                let count = page.len() as u64;
+                self.fetched += count;
+                self.page += 1;
+                self.complete = count == 0 || self.fetched >= page.total_entries;
+                self.done = self.complete;
+                Some(Ok(page))
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// Collect the results of every page, `fetch` is called with the options of each page
+pub(crate) fn all_pages<M, F>(fetch: F) -> SkResult<Vec<M>>
+where
+    M: TopLevelResource,
+    F: FnMut(Options) -> SkResult<SkResultSet<M>>,
+{
+    Pages::new(fetch).items().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::SkError;
+    use crate::options::format_with_options;
+    use crate::resources::event::Event;
+    use serde_json::Value;
+
+    fn calendar(total_entries: u64) -> Value {
+        let mut data: Value = serde_json::from_str(
+            &std::fs::read_to_string("fixtures/event/artist-324967-calendar.json").unwrap(),
+        )
+        .unwrap();
+        data["resultsPage"]["totalEntries"] = Value::from(total_entries);
+        data
+    }
+
+    #[test]
+    fn collect_all_pages() {
+        let data = calendar(66);
+
+        let mut calls = 0;
+        let events = all_pages(|options| {
+            calls += 1;
+            assert!(format_with_options("", Some(options)).contains(&format!("page={}", calls)));
+            SkResultSet::<Event>::from_json(&data)
+        })
+        .unwrap();
+
+        assert_eq!(2, calls);
+        assert_eq!(66, events.len());
+    }
+
+    #[test]
+    fn pages_stop_at_first_error() {
+        let data = calendar(99);
+        let mut calls = 0;
+        let pages: Vec<SkResult<SkResultSet<Event>>> = Pages::new(|_| {
+            calls += 1;
+            match calls {
+                1 => SkResultSet::from_json(&data),
+                _ => Err(SkError::from_status(503, None, "")),
+            }
+        })
+        .collect();
+
+        assert_eq!(2, pages.len());
+        assert_eq!(33, pages[0].as_ref().unwrap().len());
+        assert!(pages[1].is_err());
+    }
+
+    #[test]
+    fn resume_pages() {
+        let data = calendar(99);
+        let mut calls = 0;
+        let mut pages = Pages::<Event>::new(|_| {
+            calls += 1;
+            match calls {
+                1 => SkResultSet::from_json(&data),
+                _ => Err(SkError::from_status(503, None, "")),
+            }
+        })
+        .per_page(33);
+        assert!(pages.next().unwrap().is_ok());
+        assert!(pages.next().unwrap().is_err());
+        let state = pages.state();
+        assert_eq!(
+            PageState {
+                page: 2,
+                per_page: 33,
+                fetched: 33,
+                complete: false,
+            },
+            state
+        );
+
+        let mut requested = Vec::new();
+        let resumed: Vec<SkResult<SkResultSet<Event>>> = Pages::new(|options| {
+            requested.push(format_with_options("", Some(options)));
+            SkResultSet::from_json(&data)
+        })
+        .resume(state)
+        .collect();
+        assert_eq!(2, resumed.len());
+        assert!(requested[0].contains("page=2&per_page=33"));
+        assert!(requested[1].contains("page=3&per_page=33"));
+
+        let done = PageState { complete: true, ..state };
+        assert_eq!(0, Pages::<Event>::new(|_| unreachable!()).resume(done).count());
+    }
+
+    #[test]
+    fn pages_with_sort_and_filter() {
+        let data = calendar(33);
+        let pages: Vec<SkResult<SkResultSet<Event>>> = Pages::new(|options| {
+            let url = format_with_options("", Some(options));
+            assert!(url.contains("min_date=2016%2D01%2D01"));
+            assert!(url.contains("order=desc"));
+            SkResultSet::from_json(&data)
+        })
+        .sort(Sort::DESC)
+        .filter(|filter| {
+            filter.min_date("2016-01-01");
+        })
+        .collect();
+
+        assert_eq!(1, pages.len());
+    }
+}
@@ -0,0 +1,111 @@
+use crate::client::SongKickOpts;
+use crate::endpoints::{all_pages, Pages, SkEndpointInternal};
+use crate::options::Options;
+use crate::resources::calendar_entry::CalendarEntry;
+use crate::result::SkResultSet;
+use crate::util::encode;
+use crate::SkResult;
+use std::sync::Arc;
+
+/// Which events of a user calendar are returned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CalendarReason {
+    /// Upcoming events of the artists tracked by the user
+    TrackedArtist,
+    /// Events the user marked as going or might go
+    Attendance,
+}
+
+impl CalendarReason {
+    fn as_param(self) -> &'static str {
+        match self {
+            CalendarReason::TrackedArtist => "tracked_artist",
+            CalendarReason::Attendance => "attendance",
+        }
+    }
+}
+
+/// Calls of the User endpoint, as a trait to substitute fakes to [`UserEndpoint`] in tests
+pub trait UserApi {
+    /// Page of the calendar of the user
+    fn calendar(
+        &self,
+        username: &str,
+        reason: CalendarReason,
+        options: Option<Options>,
+    ) -> SkResult<SkResultSet<CalendarEntry>>;
+
+    /// Lazy iterator over the pages of the calendar of the user
+    fn calendar_pages<'a>(&'a self, username: &'a str, reason: CalendarReason) -> Pages<'a, CalendarEntry> {
+        Pages::new(move |options| self.calendar(username, reason, Some(options)))
+    }
+
+    /// Every entry of the calendar of the user, fetching all its pages
+    fn calendar_all(&self, username: &str, reason: CalendarReason) -> SkResult<Vec<CalendarEntry>> {
+        all_pages(|options| self.calendar(username, reason, Some(options)))
+    }
+}
+
+#[doc(hidden)]
+struct UserEndpointDelegate {}
+
+impl SkEndpointInternal for UserEndpointDelegate {
+    type Model = CalendarEntry;
+    fn new() -> UserEndpointDelegate {
+        UserEndpointDelegate {}
+    }
+}
+
+/// Public Struct for User Endpoint
+pub struct UserEndpoint {
+    delegate: UserEndpointDelegate,
+    sk: Arc<SongKickOpts>,
+}
+
+impl UserEndpoint {
+    pub fn new(sk: Arc<SongKickOpts>) -> UserEndpoint {
+        UserEndpoint {
+            delegate: UserEndpointDelegate::new(),
+            sk,
+        }
+    }
+
+    /// [Calendar](https://www.songkick.com/developer/upcoming-events-for-user) of the user
+    pub fn calendar(
+        &self,
+        username: &str,
+        reason: CalendarReason,
+        options: Option<Options>,
+    ) -> SkResult<SkResultSet<CalendarEntry>> {
+        let url = format!(
+            "{}/users/{}/calendar.json?reason={}&apikey={}",
+            self.sk.base_path_for("users.calendar"),
+            encode(username),
+            reason.as_param(),
+            self.sk.api_key()
+        );
+        self.delegate
+            .fetch(&url, self.sk.as_ref(), options, "users.calendar")
+    }
+
+    /// Lazy iterator over the pages of the calendar of the user
+    pub fn calendar_pages<'a>(&'a self, username: &'a str, reason: CalendarReason) -> Pages<'a, CalendarEntry> {
+        Pages::new(move |options| self.calendar(username, reason, Some(options)))
+    }
+
+    /// Every entry of the calendar of the user, fetching all its pages
+    pub fn calendar_all(&self, username: &str, reason: CalendarReason) -> SkResult<Vec<CalendarEntry>> {
+        all_pages(|options| self.calendar(username, reason, Some(options)))
+    }
+}
+
+impl UserApi for UserEndpoint {
+    fn calendar(
+        &self,
+        username: &str,
+        reason: CalendarReason,
+        options: Option<Options>,
+    ) -> SkResult<SkResultSet<CalendarEntry>> {
+        UserEndpoint::calendar(self, username, reason, options)
+    }
+}
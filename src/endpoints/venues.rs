@@ -0,0 +1,142 @@
+use crate::client::SongKickOpts;
+use crate::endpoints::{all_pages, Pages, SkEndpoint, SkEndpointInternal};
+use crate::options::Options;
+use crate::resources::event::Event;
+use crate::resources::ids::VenueId;
+use crate::resources::venue::Venue;
+use crate::result::SkResultSet;
+use crate::SkResult;
+#[cfg(feature = "strsim")]
+use crate::matching::{rank, Scored};
+use std::sync::Arc;
+
+/// Calls of the Venue endpoint, as a trait to substitute fakes to [`VenueEndpoint`] in tests
+pub trait VenueApi {
+    /// Full representation of a single Venue with ID, with its address and capacity
+    fn get(&self, id: VenueId) -> SkResult<SkResultSet<Venue>>;
+
+    /// Page of the upcoming events at the venue
+    fn calendar(&self, id: VenueId, options: Option<Options>) -> SkResult<SkResultSet<Event>>;
+
+    /// Lazy iterator over the pages of the calendar of the venue
+    fn calendar_pages(&self, id: VenueId) -> Pages<'_, Event> {
+        Pages::new(move |options| self.calendar(id, Some(options)))
+    }
+
+    /// All the upcoming events at the venue, fetching every page of the calendar
+    fn calendar_all(&self, id: VenueId) -> SkResult<Vec<Event>> {
+        all_pages(|options| self.calendar(id, Some(options)))
+    }
+}
+
+#[doc(hidden)]
+struct VenueEndpointDelegate {}
+
+impl SkEndpointInternal for VenueEndpointDelegate {
+    type Model = Venue;
+    fn new() -> VenueEndpointDelegate {
+        VenueEndpointDelegate {}
+    }
+}
+
+pub struct VenueEndpoint {
+    delegate: VenueEndpointDelegate,
+    sk: Arc<SongKickOpts>,
+}
+
+impl SkEndpoint for VenueEndpoint {
+    type Model = Venue;
+    type Id = VenueId;
+
+    fn new(sk: Arc<SongKickOpts>) -> VenueEndpoint {
+        VenueEndpoint {
+            delegate: VenueEndpointDelegate::new(),
+            sk,
+        }
+    }
+
+    fn get<I>(&self, id: I) -> SkResult<SkResultSet<Self::Model>>
+    where
+        I: Into<VenueId>,
+    {
+        VenueEndpoint::get(self, id)
+    }
+}
+
+impl VenueEndpoint {
+    /// Get the full representation of a single [Venue](https://www.songkick.com/developer/venue-details) with ID
+    pub fn get<I>(&self, id: I) -> SkResult<SkResultSet<Venue>>
+    where
+        I: Into<VenueId>,
+    {
+        self.delegate.get(id.into().0, self.sk.as_ref(), "venues")
+    }
+
+    /// Search [Venues](https://www.songkick.com/developer/venue-search) by name
+    pub fn search_by_name<T>(&self, text: T) -> SkResult<SkResultSet<Venue>>
+    where
+        T: Into<String>,
+    {
+        self.delegate
+            .search_by_name(&text.into(), self.sk.as_ref(), "venues", None)
+    }
+
+    /// Search Venues by name, the results by decreasing [`score`](crate::matching::score) of their
+    /// names to `text`
+    #[cfg(feature = "strsim")]
+    pub fn search_ranked<T>(&self, text: T) -> SkResult<Vec<Scored<Venue>>>
+    where
+        T: Into<String>,
+    {
+        let text = text.into();
+        Ok(rank(&text, self.search_by_name(text.as_str())?))
+    }
+
+    /// Upcoming events at the venue
+    pub fn calendar<I>(&self, id: I, options: Option<Options>) -> SkResult<SkResultSet<Event>>
+    where
+        I: Into<VenueId>,
+    {
+        self.delegate
+            .calendar(id.into().0, self.sk.as_ref(), "venues", options)
+    }
+
+    /// Page of the upcoming events of the venue, each event handed to `f` as soon as it is
+    /// decoded, see [`ArtistEndpoint::calendar_each`](crate::endpoints::ArtistEndpoint::calendar_each)
+    pub fn calendar_each<I, F>(&self, id: I, options: Option<Options>, mut f: F) -> SkResult<SkResultSet<Event>>
+    where
+        I: Into<VenueId>,
+        F: FnMut(Event),
+    {
+        self.delegate
+            .calendar_each(id.into().0, self.sk.as_ref(), "venues", options, &mut f)
+    }
+
+    /// Lazy iterator over the pages of the calendar of the venue
+    pub fn calendar_pages<I>(&self, id: I) -> Pages<'_, Event>
+    where
+        I: Into<VenueId>,
+    {
+        let id = id.into();
+        Pages::new(move |options| self.calendar(id, Some(options)))
+    }
+
+    /// All the upcoming events at the venue, fetching every page of the calendar
+    pub fn calendar_all<I>(&self, id: I) -> SkResult<Vec<Event>>
+    where
+        I: Into<VenueId>,
+    {
+        let id = id.into();
+        all_pages(|options| self.calendar(id, Some(options)))
+    }
+}
+
+impl VenueApi for VenueEndpoint {
+    fn get(&self, id: VenueId) -> SkResult<SkResultSet<Venue>> {
+        VenueEndpoint::get(self, id)
+    }
+
+    fn calendar(&self, id: VenueId, options: Option<Options>) -> SkResult<SkResultSet<Event>> {
+        VenueEndpoint::calendar(self, id, options)
+    }
+}
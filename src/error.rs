@@ -1,67 +1,400 @@
-use serde_json;
-use std::error;
-use std::fmt;
-use std::io;
+use std::error::Error as StdError;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
 
-#[derive(Debug)]
+/// Maximum number of bytes of the response body kept in HTTP errors
+pub const BODY_SNIPPET_LEN: usize = 512;
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum SkError {
-    Default(String),
-    Json(serde_json::Error),
-    JsonError(String),
-    Io(io::Error),
-    Http(reqwest::Error),
-    BadRequest(String),
+    /// The request could not be sent, or its response could not be read
+    #[error("Transport error: {}", redact_url(&.0.to_string()))]
+    Transport(#[source] reqwest::Error),
+    /// The API answered with a non-success HTTP status
+    ///
+    /// `body` holds the first [`BODY_SNIPPET_LEN`] bytes of the response in every HTTP error
+    #[error("Http error: status {status}{}", display_body(body))]
+    Http { status: u16, body: String },
+    /// The requested resource does not exist (HTTP 404)
+    #[error("Not found{}", display_body(body))]
+    NotFound { body: String },
+    /// The API key was rejected (HTTP 401 or 403)
+    #[error("Unauthorized: status {status}{}", display_body(body))]
+    Unauthorized { status: u16, body: String },
+    /// Too many requests (HTTP 429), `retry_after` comes from the `Retry-After` header
+    #[error("Rate limited{}{}", display_retry_after(retry_after), display_body(body))]
+    RateLimited {
+        retry_after: Option<Duration>,
+        body: String,
+    },
+    /// The response is not JSON (e.g. an HTML maintenance page), `snippet` holds its beginning
+    #[error("Unexpected content type {content_type}{}", display_body(snippet))]
+    UnexpectedContentType {
+        content_type: String,
+        snippet: String,
+    },
+    /// The response is larger than the limit set with
+    /// [`SongKickBuilder::max_response_size`](crate::SongKickBuilder::max_response_size)
+    #[error("Response larger than the limit of {limit} bytes")]
+    ResponseTooLarge { limit: u64 },
+    /// Unexpected JSON shape at `path` (e.g. `resultsPage.results.event[3].venue.id`)
+    #[error("Decode error: {}{message}", display_path(path))]
+    Decode {
+        path: String,
+        message: String,
+        /// Underlying parser error, if any
+        #[source]
+        source: Option<Box<dyn StdError + Send + Sync>>,
+    },
+    /// The API reported an error in its response
+    #[error("API error: {message}")]
+    Api { message: String },
+    /// The arguments of a call were rejected before sending the request
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    /// Writing an export failed
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The [daily budget](crate::SongKickBuilder::daily_quota) of requests is spent, until `resets_at`
+    #[error("Daily request budget exhausted, until {}", display_time(*resets_at))]
+    QuotaExhausted { resets_at: SystemTime },
+    /// Reading or writing the store of the sync subsystem failed
+    #[error("Storage error: {0}")]
+    Store(#[source] Box<dyn StdError + Send + Sync>),
+    /// Failure of a request to `endpoint` (e.g. `artists.calendar`)
+    ///
+    /// `url` is the requested URL with the API key masked. Every endpoint wraps the errors of
+    /// the requests it sends, only arguments rejected before sending are
+    /// [`InvalidInput`](SkError::InvalidInput).
+    #[error("{endpoint} request failed ({url})")]
+    Request {
+        endpoint: String,
+        url: String,
+        source: Box<SkError>,
+    },
+}
+
+fn display_path(path: &str) -> String {
+    if path.is_empty() {
+        String::new()
+    } else {
+        format!("{}: ", path)
+    }
+}
+
+/// `time` in UTC, e.g. `2026-10-15T00:00:00Z`
+fn display_time(time: SystemTime) -> String {
+    let seconds = time.duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+    let (year, month, day) = crate::analysis::civil_date((seconds / 86_400) as i64);
+    let seconds = seconds % 86_400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+fn display_body(body: &str) -> String {
+    if body.is_empty() {
+        String::new()
+    } else {
+        format!(": {}", body)
+    }
+}
+
+/// First [`BODY_SNIPPET_LEN`] bytes of `body`, cut on a char boundary
+fn snippet(body: &str) -> String {
+    let mut end = body.len().min(BODY_SNIPPET_LEN);
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    String::from(body[..end].trim())
+}
+
+/// Mask the value of the `apikey` query parameter in `url`, or in a message quoting it
+pub(crate) fn redact_url(url: &str) -> String {
+    match url.find("apikey=") {
+        Some(start) => {
+            let value = start + "apikey=".len();
+            let end = url[value..]
+                .find(['&', '#', ')', ' '])
+                .map_or(url.len(), |end| value + end);
+            format!("{}REDACTED{}", &url[..value], &url[end..])
+        }
+        None => String::from(url),
+    }
 }
 
-impl fmt::Display for SkError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+fn display_retry_after(retry_after: &Option<Duration>) -> String {
+    match *retry_after {
+        Some(delay) => format!(", retry after {}s", delay.as_secs()),
+        None => String::new(),
+    }
+}
+
+impl SkError {
+    /// Error for a response with a non-success `status`
+    pub(crate) fn from_status(status: u16, retry_after: Option<&str>, body: &str) -> SkError {
+        let body = snippet(body);
+        match status {
+            404 => SkError::NotFound { body },
+            401 | 403 => SkError::Unauthorized { status, body },
+            429 => SkError::RateLimited {
+                retry_after: retry_after
+                    .and_then(|secs| secs.trim().parse().ok())
+                    .map(Duration::from_secs),
+                body,
+            },
+            status => SkError::Http { status, body },
+        }
+    }
+
+    /// Attach the `endpoint` name and the redacted `url` of the failing request
+    pub(crate) fn with_request(self, endpoint: &str, url: &str) -> SkError {
+        SkError::Request {
+            endpoint: String::from(endpoint),
+            url: redact_url(url),
+            source: Box::new(self),
+        }
+    }
+
+    /// The error without its request context
+    pub fn inner(&self) -> &SkError {
+        match *self {
+            SkError::Request { ref source, .. } => source.inner(),
+            ref err => err,
+        }
+    }
+
+    /// Name of the endpoint of the failing request (e.g. `artists.calendar`)
+    pub fn endpoint(&self) -> Option<&str> {
+        match *self {
+            SkError::Request { ref endpoint, .. } => Some(endpoint),
+            _ => None,
+        }
+    }
+
+    /// URL of the failing request, with the API key masked
+    pub fn url(&self) -> Option<&str> {
         match *self {
-            SkError::Default(ref err) => write!(f, "Generic error: {}", err),
-            SkError::Json(ref err) => write!(f, "JSON error: {}", err),
-            SkError::Io(ref err) => write!(f, "IO error: {}", err),
-            SkError::Http(ref err) => write!(f, "Http error: {}", err),
-            SkError::JsonError(ref err) => write!(f, "Http error: {}", err),
-            SkError::BadRequest(ref err) => write!(f, "Http error: {}", err),
+            SkError::Request { ref url, .. } => Some(url),
+            _ => None,
+        }
+    }
+
+    /// HTTP status of the response, for errors caused by a non-success status
+    pub fn status(&self) -> Option<u16> {
+        match *self.inner() {
+            SkError::Http { status, .. } | SkError::Unauthorized { status, .. } => Some(status),
+            SkError::NotFound { .. } => Some(404),
+            SkError::RateLimited { .. } => Some(429),
+            _ => None,
+        }
+    }
+
+    /// Whether sending the same request again may succeed
+    ///
+    /// True for timeouts and connection failures, 5xx statuses and rate limiting;
+    /// false for the other 4xx statuses, API errors, decoding errors and invalid input.
+    pub fn is_retriable(&self) -> bool {
+        match *self.inner() {
+            SkError::Transport(ref err) => err.is_timeout() || err.is_connect() || err.is_request(),
+            SkError::Http { status, .. } => status >= 500,
+            SkError::RateLimited { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the request timed out, connecting, waiting for data or past its deadline
+    pub fn is_timeout(&self) -> bool {
+        matches!(*self.inner(), SkError::Transport(ref err) if err.is_timeout())
+    }
+
+    /// Beginning of the response body, for errors caused by a non-success status
+    pub fn body_snippet(&self) -> Option<&str> {
+        match *self.inner() {
+            SkError::Http { ref body, .. }
+            | SkError::NotFound { ref body }
+            | SkError::Unauthorized { ref body, .. }
+            | SkError::RateLimited { ref body, .. } => Some(body),
+            _ => None,
+        }
+    }
+
+    /// Error for a successful response whose `body` is not JSON
+    pub(crate) fn unexpected_content(content_type: Option<&str>, body: &str) -> SkError {
+        SkError::UnexpectedContentType {
+            content_type: String::from(content_type.unwrap_or("unknown")),
+            snippet: snippet(body),
+        }
+    }
+
+    /// Decoding error of the value itself, to be located with [`SkError::at`]
+    pub(crate) fn decode<S: Into<String>>(message: S) -> SkError {
+        SkError::Decode {
+            path: String::new(),
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Decoding error of the value itself caused by `source`
+    pub(crate) fn decode_with<S, E>(message: S, source: E) -> SkError
+    where
+        S: Into<String>,
+        E: StdError + Send + Sync + 'static,
+    {
+        SkError::Decode {
+            path: String::new(),
+            message: message.into(),
+            source: Some(Box::new(source)),
+        }
+    }
+
+    /// Prefix the path of a decoding error with the `segment` of its parent value
+    pub(crate) fn at(self, segment: &str) -> SkError {
+        match self {
+            SkError::Decode { path, message, source } => {
+                let path = if path.is_empty() {
+                    String::from(segment)
+                } else if path.starts_with('[') {
+                    format!("{}{}", segment, path)
+                } else {
+                    format!("{}.{}", segment, path)
+                };
+                SkError::Decode { path, message, source }
+            }
+            err => err,
         }
     }
 }
 
 impl From<reqwest::Error> for SkError {
     fn from(err: reqwest::Error) -> SkError {
-        SkError::Http(err)
+        SkError::Transport(err)
     }
 }
 
 impl From<serde_json::Error> for SkError {
     fn from(err: serde_json::Error) -> SkError {
-        SkError::Json(err)
+        SkError::decode_with("invalid JSON", err)
     }
 }
 
-impl From<io::Error> for SkError {
-    fn from(err: io::Error) -> SkError {
-        SkError::Io(err)
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for SkError {
+    fn from(err: rusqlite::Error) -> SkError {
+        SkError::Store(Box::new(err))
     }
 }
 
-impl error::Error for SkError {
-    fn description(&self) -> &str {
-        match *self {
-            SkError::Default(ref err) => err,
-            SkError::Json(ref err) => err.description(),
-            SkError::Io(ref err) => err.description(),
-            SkError::Http(ref err) => err.description(),
-            SkError::JsonError(ref err) => err,
-            SkError::BadRequest(ref err) => err,
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn decode_error_display() {
+        let err = SkError::decode("missing field").at("id").at("[0]").at("event");
+        assert_eq!("Decode error: event[0].id: missing field", err.to_string());
+        assert_eq!("Decode error: missing field", SkError::decode("missing field").to_string());
+    }
+
+    #[test]
+    fn error_from_status() {
+        assert!(matches!(SkError::from_status(404, None, ""), SkError::NotFound { .. }));
+        assert!(matches!(SkError::from_status(401, None, ""), SkError::Unauthorized { status: 401, .. }));
+        assert!(matches!(SkError::from_status(403, None, ""), SkError::Unauthorized { status: 403, .. }));
+        assert!(matches!(SkError::from_status(500, None, ""), SkError::Http { status: 500, .. }));
+
+        match SkError::from_status(429, Some("120"), "") {
+            SkError::RateLimited { retry_after, .. } => {
+                assert_eq!(Some(Duration::from_secs(120)), retry_after)
+            }
+            err => panic!("expected RateLimited, got {}", err),
+        }
+        match SkError::from_status(429, Some("Wed, 21 Oct 2015 07:28:00 GMT"), "") {
+            SkError::RateLimited { retry_after, .. } => assert_eq!(None, retry_after),
+            err => panic!("expected RateLimited, got {}", err),
         }
     }
 
-    fn cause(&self) -> Option<&error::Error> {
-        match *self {
-            SkError::Json(ref err) => Some(err),
-            SkError::Io(ref err) => Some(err),
-            SkError::Http(ref err) => Some(err),
-            _ => None,
+    #[test]
+    fn http_error_keeps_body_snippet() {
+        let err = SkError::from_status(502, None, "<html>Bad Gateway</html>\n");
+        assert_eq!(Some(502), err.status());
+        assert_eq!(Some("<html>Bad Gateway</html>"), err.body_snippet());
+        assert_eq!("Http error: status 502: <html>Bad Gateway</html>", err.to_string());
+
+        let body = "é".repeat(BODY_SNIPPET_LEN);
+        let err = SkError::from_status(404, None, &body);
+        assert_eq!(Some(404), err.status());
+        assert_eq!(BODY_SNIPPET_LEN, err.body_snippet().unwrap().len());
+        assert_eq!(None, SkError::decode("missing field").status());
+    }
+
+    #[test]
+    fn keeps_source_chain() {
+        let json_err = serde_json::from_str::<serde_json::Value>("<html>").unwrap_err();
+        let expected = json_err.to_string();
+        let err = SkError::from(json_err).at("resultsPage");
+
+        assert_eq!("Decode error: resultsPage: invalid JSON", err.to_string());
+        assert_eq!(expected, err.source().unwrap().to_string());
+        assert!(SkError::decode("missing field").source().is_none());
+    }
+
+    #[test]
+    fn request_context_redacts_api_key() {
+        let err = SkError::from_status(404, None, "").with_request(
+            "artists.calendar",
+            "https://api.songkick.com/api/3.0/artists/1/calendar.json?apikey=s3cr3t&page=2",
+        );
+        assert_eq!(Some("artists.calendar"), err.endpoint());
+        assert_eq!(
+            Some("https://api.songkick.com/api/3.0/artists/1/calendar.json?apikey=REDACTED&page=2"),
+            err.url()
+        );
+        assert!(matches!(err.inner(), SkError::NotFound { .. }));
+        assert_eq!(Some(404), err.status());
+        assert!(!err.to_string().contains("s3cr3t"));
+        assert_eq!(
+            "artists.calendar request failed (https://api.songkick.com/api/3.0/artists/1/calendar.json?apikey=REDACTED&page=2)",
+            err.to_string()
+        );
+        assert_eq!(err.inner().to_string(), err.source().unwrap().to_string());
+
+        assert_eq!("https://host/a?apikey=REDACTED", redact_url("https://host/a?apikey=abc"));
+        assert_eq!("https://host/a", redact_url("https://host/a"));
+        assert_eq!(
+            "error sending request for url (https://host/a?apikey=REDACTED): timed out",
+            redact_url("error sending request for url (https://host/a?apikey=abc): timed out")
+        );
+    }
+
+    #[test]
+    fn retriable_errors() {
+        assert!(SkError::from_status(503, None, "").is_retriable());
+        assert!(SkError::from_status(500, None, "").with_request("events.get", "").is_retriable());
+        assert!(SkError::from_status(429, Some("5"), "").is_retriable());
+
+        assert!(!SkError::from_status(400, None, "").is_retriable());
+        assert!(!SkError::from_status(401, None, "").is_retriable());
+        assert!(!SkError::from_status(404, None, "").is_retriable());
+        assert!(!SkError::decode("missing field").is_retriable());
+        assert!(!SkError::InvalidInput(String::from("empty query")).is_retriable());
+    }
+
+    #[test]
+    fn boxes_into_dyn_error() {
+        fn fails() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            Err(SkError::InvalidInput(String::from("empty query")))?
         }
+        assert_eq!("Invalid input: empty query", fails().unwrap_err().to_string());
     }
 }
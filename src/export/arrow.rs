@@ -0,0 +1,222 @@
+//! Arrow record batches of events, and Parquet files with the `parquet` feature
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "parquet")]
+//! # {
+//! use songkick::SongKick;
+//! use songkick::export::arrow;
+//! use std::fs::File;
+//!
+//! let sk = SongKick::new("API_KEY");
+//! // One batch per page of the gigography of RadioHead
+//! let batches = sk
+//!     .artist
+//!     .gigography_pages(253846)
+//!     .map(|page| page.map(|events| arrow::to_record_batch(&events.collect::<Vec<_>>())));
+//!
+//! arrow::write_parquet(File::create("gigography.parquet").unwrap(), batches).unwrap();
+//! # }
+//! ```
+
+use crate::resources::event::Event;
+use arrow_array::builder::{ListBuilder, StringBuilder};
+use arrow_array::{
+    ArrayRef, Date32Array, Float64Array, RecordBatch, StringArray, TimestampMillisecondArray,
+    UInt64Array,
+};
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use chrono::NaiveDate;
+use std::sync::Arc;
+
+/// Schema of the batches built by [`to_record_batch`], one row per Event
+///
+/// | column | type |
+/// |---|---|
+/// | `id` | `UInt64` |
+/// | `name`, `type`, `status` | `Utf8` |
+/// | `date` | `Date32`, start date |
+/// | `start` | `Timestamp(ms, UTC)`, exact start |
+/// | `artists` | `List<Utf8>`, in billing order |
+/// | `venue_id` | `UInt64` |
+/// | `venue`, `city`, `country` | `Utf8` |
+/// | `lat`, `lng`, `popularity` | `Float64` |
+pub fn schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("id", DataType::UInt64, false),
+        Field::new("name", DataType::Utf8, false),
+        Field::new("type", DataType::Utf8, false),
+        Field::new("status", DataType::Utf8, false),
+        Field::new("date", DataType::Date32, true),
+        Field::new(
+            "start",
+            DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+            true,
+        ),
+        Field::new(
+            "artists",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            false,
+        ),
+        Field::new("venue_id", DataType::UInt64, true),
+        Field::new("venue", DataType::Utf8, true),
+        Field::new("city", DataType::Utf8, true),
+        Field::new("country", DataType::Utf8, true),
+        Field::new("lat", DataType::Float64, true),
+        Field::new("lng", DataType::Float64, true),
+        Field::new("popularity", DataType::Float64, false),
+    ]))
+}
+
+/// Batch of `events` with the columns of [`schema`]
+pub fn to_record_batch(events: &[Event]) -> RecordBatch {
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    let strings = |f: &dyn Fn(&Event) -> Option<String>| -> ArrayRef {
+        Arc::new(events.iter().map(f).collect::<StringArray>())
+    };
+    let floats = |f: &dyn Fn(&Event) -> Option<f64>| -> ArrayRef {
+        Arc::new(events.iter().map(f).collect::<Float64Array>())
+    };
+
+    let mut artists = ListBuilder::new(StringBuilder::new());
+    for event in events {
+        for performance in &event.performances {
+            artists.values().append_value(&performance.display_name);
+        }
+        artists.append(true);
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(events.iter().map(|event| event.id.0).collect::<UInt64Array>()),
+        strings(&|event| Some(event.display_name.clone())),
+        strings(&|event| Some(event.event_type.clone())),
+        strings(&|event| Some(event.status.clone())),
+        Arc::new(
+            events
+                .iter()
+                .map(|event| event.start.date_parsed().map(|date| (date - epoch).num_days() as i32))
+                .collect::<Date32Array>(),
+        ),
+        Arc::new(
+            events
+                .iter()
+                .map(|event| event.start.datetime_parsed().map(|datetime| datetime.timestamp_millis()))
+                .collect::<TimestampMillisecondArray>()
+                .with_timezone("UTC"),
+        ),
+        Arc::new(artists.finish()),
+        Arc::new(
+            events
+                .iter()
+                .map(|event| event.venue.id.map(|id| id.0))
+                .collect::<UInt64Array>(),
+        ),
+        strings(&|event| event.venue.display_name.clone()),
+        strings(&|event| {
+            event
+                .venue
+                .metro_area
+                .as_ref()
+                .map(|metro_area| metro_area.display_name.clone())
+                .or_else(|| event.location.as_ref().map(|location| location.city.clone()))
+        }),
+        strings(&|event| {
+            event
+                .venue
+                .metro_area
+                .as_ref()
+                .map(|metro_area| metro_area.country.display_name.clone())
+        }),
+        floats(&|event| {
+            event
+                .venue
+                .lat
+                .or_else(|| event.location.as_ref().and_then(|location| location.lat))
+        }),
+        floats(&|event| {
+            event
+                .venue
+                .lng
+                .or_else(|| event.location.as_ref().and_then(|location| location.lng))
+        }),
+        floats(&|event| Some(event.popularity)),
+    ];
+
+    RecordBatch::try_new(schema(), columns).expect("columns match the schema")
+}
+
+/// Write the fetched `batches` as a Parquet file, stopping at the first error
+///
+/// Batches are written as they arrive, so results of many pages are never held in memory.
+#[cfg(feature = "parquet")]
+pub fn write_parquet<W, I>(writer: W, batches: I) -> crate::SkResult<()>
+where
+    W: std::io::Write + Send,
+    I: IntoIterator<Item = crate::SkResult<RecordBatch>>,
+{
+    use parquet::arrow::ArrowWriter;
+    use std::io;
+
+    let mut writer = ArrowWriter::try_new(writer, schema(), None).map_err(io::Error::other)?;
+    for batch in batches {
+        writer.write(&batch?).map_err(io::Error::other)?;
+    }
+    writer.close().map_err(io::Error::other)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::SkResultSet;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::UInt64Type;
+    use arrow_array::Array;
+    use serde_json::Value;
+
+    fn load_events(path: &str) -> Vec<Event> {
+        let data: Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        SkResultSet::<Event>::from_json(&data).unwrap().collect()
+    }
+
+    #[test]
+    fn events_to_record_batch() {
+        let events = load_events("fixtures/event/artist-324967-calendar.json");
+        let batch = to_record_batch(&events);
+
+        assert_eq!(events.len(), batch.num_rows());
+        assert_eq!(schema(), batch.schema());
+
+        let ids = batch.column_by_name("id").unwrap().as_primitive::<UInt64Type>();
+        assert_eq!(26486294, ids.value(0));
+
+        let artists = batch.column_by_name("artists").unwrap().as_list::<i32>().value(0);
+        let artists = artists.as_string::<i32>();
+        assert_eq!(vec![Some("Placebo"), Some("The Mirror Trap")], artists.iter().collect::<Vec<_>>());
+
+        let dates = batch.column_by_name("date").unwrap();
+        let dates = dates.as_any().downcast_ref::<Date32Array>().unwrap();
+        assert_eq!(NaiveDate::from_ymd_opt(2016, 10, 18), dates.value_as_date(0));
+
+        let starts = batch.column_by_name("start").unwrap();
+        let starts = starts.as_any().downcast_ref::<TimestampMillisecondArray>().unwrap();
+        assert_eq!(1476811800000, starts.value(0));
+
+        let lat = batch.column_by_name("lat").unwrap();
+        assert!(lat.null_count() < events.len());
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn batches_to_parquet() {
+        let events = load_events("fixtures/event/artist-324967-calendar.json");
+        let batches = events.chunks(10).map(|events| Ok(to_record_batch(events)));
+
+        let mut out = Vec::new();
+        write_parquet(&mut out, batches).unwrap();
+        assert_eq!(b"PAR1", &out[..4]);
+        assert_eq!(b"PAR1", &out[out.len() - 4..]);
+
+        let failing = vec![Err(crate::error::SkError::from_status(500, None, ""))];
+        assert!(write_parquet(Vec::new(), failing).is_err());
+    }
+}
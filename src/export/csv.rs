@@ -0,0 +1,322 @@
+//! CSV export of events, one row per Event
+//!
+//! ```rust,no_run
+//! use songkick::SongKick;
+//! use songkick::export::csv::{Column, CsvWriter};
+//!
+//! let sk = SongKick::new("API_KEY");
+//! let events = sk.artist.calendar_all(253846).unwrap();
+//!
+//! let mut writer = CsvWriter::with_columns(std::io::stdout(), &[Column::Date, Column::Name, Column::City]);
+//! writer.write_all(&events).unwrap();
+//! ```
+//!
+//! [`write_google_calendar`] writes the format expected by the Google Calendar CSV import.
+
+use crate::resources::event::Event;
+use crate::SkResult;
+use std::io;
+
+/// Column of the exported rows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Column {
+    Id,
+    Name,
+    /// Start date (`YYYY-MM-DD`)
+    Date,
+    /// Performing artists, separated by `; `
+    Artists,
+    Venue,
+    City,
+    Country,
+    Status,
+    Lat,
+    Lng,
+}
+
+impl Column {
+    /// Every column, in the default order
+    pub const ALL: [Column; 10] = [
+        Column::Id,
+        Column::Name,
+        Column::Date,
+        Column::Artists,
+        Column::Venue,
+        Column::City,
+        Column::Country,
+        Column::Status,
+        Column::Lat,
+        Column::Lng,
+    ];
+
+    /// Name of the column in the header row
+    pub fn header(self) -> &'static str {
+        match self {
+            Column::Id => "id",
+            Column::Name => "name",
+            Column::Date => "date",
+            Column::Artists => "artists",
+            Column::Venue => "venue",
+            Column::City => "city",
+            Column::Country => "country",
+            Column::Status => "status",
+            Column::Lat => "lat",
+            Column::Lng => "lng",
+        }
+    }
+
+    /// Value of the column for `event`, empty when unknown
+    pub fn value(self, event: &Event) -> String {
+        let metro_area = event.venue.metro_area.as_ref();
+        let location = event.location.as_ref();
+        let to_string = |val: Option<f64>| val.map(|val| val.to_string()).unwrap_or_default();
+
+        match self {
+            Column::Id => event.id.to_string(),
+            Column::Name => event.display_name.clone(),
+            Column::Date => event.start.date.as_ref().map(|date| date.to_string()).unwrap_or_default(),
+            Column::Artists => event
+                .performances
+                .iter()
+                .map(|performance| performance.display_name.as_str())
+                .collect::<Vec<_>>()
+                .join("; "),
+            Column::Venue => event.venue.display_name.clone().unwrap_or_default(),
+            Column::City => metro_area
+                .map(|metro_area| metro_area.display_name.clone())
+                .or_else(|| location.map(|location| location.city.clone()))
+                .unwrap_or_default(),
+            Column::Country => metro_area
+                .map(|metro_area| metro_area.country.display_name.clone())
+                .unwrap_or_default(),
+            Column::Status => event.status.clone(),
+            Column::Lat => to_string(event.venue.lat.or_else(|| location.and_then(|location| location.lat))),
+            Column::Lng => to_string(event.venue.lng.or_else(|| location.and_then(|location| location.lng))),
+        }
+    }
+}
+
+/// Writer of events as CSV rows, the header row is written before the first Event
+pub struct CsvWriter<W: io::Write> {
+    writer: csv::Writer<W>,
+    columns: Vec<Column>,
+    header: bool,
+}
+
+impl<W: io::Write> CsvWriter<W> {
+    /// Writer of every [`Column`]
+    pub fn new(writer: W) -> CsvWriter<W> {
+        CsvWriter::with_columns(writer, &Column::ALL)
+    }
+
+    /// Writer of the given `columns`, in the given order
+    pub fn with_columns(writer: W, columns: &[Column]) -> CsvWriter<W> {
+        CsvWriter {
+            writer: csv::Writer::from_writer(writer),
+            columns: columns.to_vec(),
+            header: false,
+        }
+    }
+
+    /// Write the row of `event`
+    pub fn write_event(&mut self, event: &Event) -> SkResult<()> {
+        self.write_header()?;
+        let row = self.columns.iter().map(|column| column.value(event));
+        self.writer.write_record(row).map_err(io::Error::from)?;
+        Ok(())
+    }
+
+    /// Write the rows of `events`
+    pub fn write_all<'a, I>(&mut self, events: I) -> SkResult<()>
+    where
+        I: IntoIterator<Item = &'a Event>,
+    {
+        for event in events {
+            self.write_event(event)?;
+        }
+        self.write_header()?;
+        self.flush()
+    }
+
+    /// Flush the underlying writer
+    pub fn flush(&mut self) -> SkResult<()> {
+        Ok(self.writer.flush()?)
+    }
+
+    /// Flush and return the underlying writer
+    pub fn into_inner(mut self) -> SkResult<W> {
+        self.write_header()?;
+        self.writer
+            .into_inner()
+            .map_err(|err| err.into_error().into())
+    }
+
+    fn write_header(&mut self) -> SkResult<()> {
+        if !self.header {
+            let header = self.columns.iter().map(|column| column.header());
+            self.writer.write_record(header).map_err(io::Error::from)?;
+            self.header = true;
+        }
+        Ok(())
+    }
+}
+
+/// Render `events` as a CSV document with every [`Column`]
+pub fn to_csv(events: &[Event]) -> SkResult<String> {
+    let mut writer = CsvWriter::new(Vec::new());
+    writer.write_all(events)?;
+    let bytes = writer.into_inner()?;
+    Ok(String::from_utf8(bytes).expect("CSV of UTF-8 fields"))
+}
+
+/// Header of the Google Calendar import format
+const GOOGLE_CALENDAR_HEADER: [&str; 6] = [
+    "Subject",
+    "Start Date",
+    "Start Time",
+    "All Day Event",
+    "Location",
+    "Description",
+];
+
+/// Write `events` in the Google Calendar CSV import format
+///
+/// Start times are the venue local times, events without a time are all-day events.
+pub fn write_google_calendar<'a, W, I>(writer: W, events: I) -> SkResult<()>
+where
+    W: io::Write,
+    I: IntoIterator<Item = &'a Event>,
+{
+    let mut writer = csv::Writer::from_writer(writer);
+    writer
+        .write_record(GOOGLE_CALENDAR_HEADER)
+        .map_err(io::Error::from)?;
+
+    for event in events {
+        let start_time = event
+            .start
+            .time
+            .as_ref()
+            .and_then(|time| twelve_hour(&time.to_string()));
+        let description = event
+            .performances
+            .iter()
+            .map(|performance| performance.display_name.clone())
+            .chain(event.page_url())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        writer
+            .write_record(&[
+                event.display_name.clone(),
+                Column::Date.value(event).get(..10).map(us_date).unwrap_or_default(),
+                start_time.clone().unwrap_or_default(),
+                String::from(if start_time.is_some() { "False" } else { "True" }),
+                event.venue.to_string(),
+                description,
+            ])
+            .map_err(io::Error::from)?;
+    }
+    Ok(writer.flush()?)
+}
+
+/// Render `events` in the Google Calendar CSV import format
+pub fn to_google_calendar_csv(events: &[Event]) -> SkResult<String> {
+    let mut out = Vec::new();
+    write_google_calendar(&mut out, events)?;
+    Ok(String::from_utf8(out).expect("CSV of UTF-8 fields"))
+}
+
+/// `2016-10-18` as `10/18/2016`
+fn us_date(date: &str) -> String {
+    format!("{}/{}/{}", &date[5..7], &date[8..10], &date[..4])
+}
+
+/// `19:30:00` as `07:30 PM`
+fn twelve_hour(time: &str) -> Option<String> {
+    let hour: u32 = time.get(..2)?.parse().ok()?;
+    let minutes = time.get(3..5)?;
+    let (hour, suffix) = match hour {
+        0 => (12, "AM"),
+        1..=11 => (hour, "AM"),
+        12 => (12, "PM"),
+        _ => (hour - 12, "PM"),
+    };
+    Some(format!("{:02}:{} {}", hour, minutes, suffix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::SkResultSet;
+    use serde_json::Value;
+    use std::fs::File;
+    use std::io::Read;
+
+    fn load_events(path: &str) -> Vec<Event> {
+        let mut file = File::open(path).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+        let data: Value = serde_json::from_str(&content).unwrap();
+        SkResultSet::<Event>::from_json(&data).unwrap().collect()
+    }
+
+    #[test]
+    fn calendar_to_csv() {
+        let events = load_events("fixtures/event/artist-324967-calendar.json");
+        let csv = to_csv(&events).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(events.len() + 1, lines.len());
+        assert_eq!("id,name,date,artists,venue,city,country,status,lat,lng", lines[0]);
+        assert_eq!(
+            "26486294,Placebo with The Mirror Trap at Cirkus (October 18, 2016),2016-10-18,\
+             Placebo; The Mirror Trap,Cirkus,Stockholm,Sweden,ok,59.3245767,18.0996982",
+            lines[1].replace('"', "")
+        );
+    }
+
+    #[test]
+    fn selected_columns() {
+        let events = load_events("fixtures/event/artist-324967-calendar.json");
+        let mut writer = CsvWriter::with_columns(Vec::new(), &[Column::Date, Column::City]);
+        writer.write_all(&events[..1]).unwrap();
+
+        let csv = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert_eq!("date,city\n2016-10-18,Stockholm\n", csv);
+
+        let empty = CsvWriter::with_columns(Vec::new(), &[Column::Id]).into_inner().unwrap();
+        assert_eq!(b"id\n".to_vec(), empty);
+    }
+
+    #[test]
+    fn google_calendar_import() {
+        let events = load_events("fixtures/event/artist-324967-calendar.json");
+        let csv = to_google_calendar_csv(&events[..1]).unwrap();
+        let mut rows = csv::Reader::from_reader(csv.as_bytes());
+
+        assert_eq!(
+            vec!["Subject", "Start Date", "Start Time", "All Day Event", "Location", "Description"],
+            rows.headers().unwrap().iter().collect::<Vec<_>>()
+        );
+        let row = rows.records().next().unwrap().unwrap();
+        assert_eq!("Placebo with The Mirror Trap at Cirkus (October 18, 2016)", &row[0]);
+        assert_eq!("10/18/2016", &row[1]);
+        assert_eq!("07:30 PM", &row[2]);
+        assert_eq!("False", &row[3]);
+        assert_eq!("Cirkus, Stockholm", &row[4]);
+        assert_eq!(
+            "Placebo\nThe Mirror Trap\nhttps://www.songkick.com/concerts/26486294-placebo-at-cirkus",
+            &row[5]
+        );
+    }
+
+    #[test]
+    fn twelve_hour_times() {
+        assert_eq!(Some(String::from("12:05 AM")), twelve_hour("00:05:00"));
+        assert_eq!(Some(String::from("12:00 PM")), twelve_hour("12:00:00"));
+        assert_eq!(Some(String::from("11:45 PM")), twelve_hour("23:45:00"));
+        assert_eq!(None, twelve_hour(""));
+    }
+}
@@ -0,0 +1,153 @@
+//! polars DataFrames of events
+//!
+//! ```rust,no_run
+//! use songkick::SongKick;
+//! use songkick::export::dataframe::ToDataFrame;
+//!
+//! let sk = SongKick::new("API_KEY");
+//! // RadioHead ID
+//! let df = sk.artist.calendar_all(253846).unwrap().to_dataframe();
+//! println!("{}", df);
+//! ```
+
+use crate::resources::event::Event;
+use chrono::NaiveDate;
+use polars::prelude::{Column, DataFrame, DataType, IntoColumn, NamedFrom, Series, TimeUnit};
+
+/// Conversion of a list of events to a DataFrame with one row per Event
+///
+/// Nested fields are flattened: `venue` is the venue name, `venue_id`, `city`, `country`,
+/// `lat` and `lng` its details; `artists` lists the performers and `headliner` is the first.
+/// `date` is a `Date` and `start` a UTC `Datetime` in milliseconds.
+pub trait ToDataFrame {
+    fn to_dataframe(&self) -> DataFrame;
+}
+
+impl ToDataFrame for [Event] {
+    fn to_dataframe(&self) -> DataFrame {
+        let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+        let strings = |name: &str, f: &dyn Fn(&Event) -> Option<String>| -> Column {
+            Column::new(name.into(), self.iter().map(f).collect::<Vec<_>>())
+        };
+        let floats = |name: &str, f: &dyn Fn(&Event) -> Option<f64>| -> Column {
+            Column::new(name.into(), self.iter().map(f).collect::<Vec<_>>())
+        };
+        let artists: Vec<Series> = self
+            .iter()
+            .map(|event| {
+                let names: Vec<&str> = event
+                    .performances
+                    .iter()
+                    .map(|performance| performance.display_name.as_str())
+                    .collect();
+                Series::new("".into(), names)
+            })
+            .collect();
+        let dates: Vec<Option<i32>> = self
+            .iter()
+            .map(|event| event.start.date_parsed().map(|date| (date - epoch).num_days() as i32))
+            .collect();
+        let starts: Vec<Option<i64>> = self
+            .iter()
+            .map(|event| event.start.datetime_parsed().map(|datetime| datetime.timestamp_millis()))
+            .collect();
+
+        let columns = vec![
+            Column::new("id".into(), self.iter().map(|event| event.id.0).collect::<Vec<_>>()),
+            strings("name", &|event| Some(event.display_name.clone())),
+            strings("type", &|event| Some(event.event_type.clone())),
+            strings("status", &|event| Some(event.status.clone())),
+            Series::new("date".into(), dates)
+                .cast(&DataType::Date)
+                .expect("days since epoch are dates")
+                .into_column(),
+            Series::new("start".into(), starts)
+                .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+                .expect("milliseconds since epoch are datetimes")
+                .into_column(),
+            strings("headliner", &|event| {
+                event.performances.first().map(|headliner| headliner.display_name.clone())
+            }),
+            Column::new("artists".into(), artists),
+            Column::new(
+                "venue_id".into(),
+                self.iter().map(|event| event.venue.id.map(|id| id.0)).collect::<Vec<_>>(),
+            ),
+            strings("venue", &|event| event.venue.display_name.clone()),
+            strings("city", &|event| {
+                event
+                    .venue
+                    .metro_area
+                    .as_ref()
+                    .map(|metro_area| metro_area.display_name.clone())
+                    .or_else(|| event.location.as_ref().map(|location| location.city.clone()))
+            }),
+            strings("country", &|event| {
+                event
+                    .venue
+                    .metro_area
+                    .as_ref()
+                    .map(|metro_area| metro_area.country.display_name.clone())
+            }),
+            floats("lat", &|event| {
+                event
+                    .venue
+                    .lat
+                    .or_else(|| event.location.as_ref().and_then(|location| location.lat))
+            }),
+            floats("lng", &|event| {
+                event
+                    .venue
+                    .lng
+                    .or_else(|| event.location.as_ref().and_then(|location| location.lng))
+            }),
+            floats("popularity", &|event| Some(event.popularity)),
+        ];
+
+        DataFrame::new(self.len(), columns).expect("columns of the same length")
+    }
+}
+
+impl ToDataFrame for Vec<Event> {
+    fn to_dataframe(&self) -> DataFrame {
+        self.as_slice().to_dataframe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::SkResultSet;
+    use polars::prelude::AnyValue;
+    use serde_json::Value;
+
+    fn load_events(path: &str) -> Vec<Event> {
+        let data: Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        SkResultSet::<Event>::from_json(&data).unwrap().collect()
+    }
+
+    #[test]
+    fn events_to_dataframe() {
+        let events = load_events("fixtures/event/artist-324967-calendar.json");
+        let df = events.to_dataframe();
+
+        assert_eq!((events.len(), 15), df.shape());
+        assert_eq!(&DataType::Date, df.column("date").unwrap().dtype());
+        assert_eq!(
+            AnyValue::UInt64(26486294),
+            df.column("id").unwrap().get(0).unwrap()
+        );
+        assert_eq!(
+            AnyValue::String("Stockholm"),
+            df.column("city").unwrap().get(0).unwrap()
+        );
+        assert_eq!(
+            AnyValue::String("Placebo"),
+            df.column("headliner").unwrap().get(0).unwrap()
+        );
+        match df.column("artists").unwrap().get(0).unwrap() {
+            AnyValue::List(artists) => assert_eq!(2, artists.len()),
+            other => panic!("expected a list, got {:?}", other),
+        }
+    }
+}
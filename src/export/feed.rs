@@ -0,0 +1,193 @@
+//! RSS 2.0 and Atom feeds of calendars
+//!
+//! ```rust,no_run
+//! use songkick::SongKick;
+//! use songkick::export::feed::Feed;
+//!
+//! let sk = SongKick::new("API_KEY");
+//! // RadioHead ID
+//! let events = sk.artist.calendar_all(253846).unwrap();
+//!
+//! let feed = Feed::new("Radiohead concerts", "https://www.songkick.com/artists/253846");
+//! println!("{}", feed.rss(&events));
+//! ```
+
+use crate::resources::event::Event;
+use chrono::{DateTime, TimeZone, Utc};
+
+/// Metadata of a feed, rendered around the items of the events
+pub struct Feed {
+    title: String,
+    link: String,
+    description: Option<String>,
+    updated: Option<DateTime<Utc>>,
+}
+
+impl Feed {
+    pub fn new<T, L>(title: T, link: L) -> Feed
+    where
+        T: Into<String>,
+        L: Into<String>,
+    {
+        Feed {
+            title: title.into(),
+            link: link.into(),
+            description: None,
+            updated: None,
+        }
+    }
+
+    /// Description of the feed, defaults to its title
+    pub fn description<D: Into<String>>(mut self, description: D) -> Feed {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Last update of the feed, defaults to the latest publication date of the events
+    pub fn updated(mut self, updated: DateTime<Utc>) -> Feed {
+        self.updated = Some(updated);
+        self
+    }
+
+    /// RSS 2.0 document with one item per Event
+    pub fn rss(&self, events: &[Event]) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\">\n<channel>\n",
+        );
+        xml += &element("title", &self.title);
+        xml += &element("link", &self.link);
+        xml += &element("description", self.description.as_ref().unwrap_or(&self.title));
+        xml += &element("lastBuildDate", &self.last_updated(events).to_rfc2822());
+
+        for event in events {
+            xml += "<item>\n";
+            xml += &element("title", &event.display_name);
+            if let Some(url) = event.page_url() {
+                xml += &element("link", &url);
+            }
+            xml += &format!(
+                "<guid isPermaLink=\"false\">{}</guid>\n",
+                escape(&guid(event))
+            );
+            if let Some(published) = published(event) {
+                xml += &element("pubDate", &published.to_rfc2822());
+            }
+            xml += "</item>\n";
+        }
+        xml + "</channel>\n</rss>\n"
+    }
+
+    /// Atom document with one entry per Event
+    pub fn atom(&self, events: &[Event]) -> String {
+        let updated = self.last_updated(events);
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n",
+        );
+        xml += &element("id", &self.link);
+        xml += &element("title", &self.title);
+        if let Some(ref description) = self.description {
+            xml += &element("subtitle", description);
+        }
+        xml += &link(&self.link);
+        xml += &element("updated", &updated.to_rfc3339());
+
+        for event in events {
+            xml += "<entry>\n";
+            xml += &element("id", &guid(event));
+            xml += &element("title", &event.display_name);
+            if let Some(url) = event.page_url() {
+                xml += &link(&url);
+            }
+            let published = published(event).unwrap_or(updated);
+            xml += &element("published", &published.to_rfc3339());
+            xml += &element("updated", &published.to_rfc3339());
+            xml += "</entry>\n";
+        }
+        xml + "</feed>\n"
+    }
+
+    fn last_updated(&self, events: &[Event]) -> DateTime<Utc> {
+        self.updated
+            .or_else(|| events.iter().filter_map(published).max())
+            .unwrap_or_else(Utc::now)
+    }
+}
+
+/// Publication date of an Event: its start, at midnight UTC when SongKick knows only the date
+fn published(event: &Event) -> Option<DateTime<Utc>> {
+    match event.start.datetime_parsed() {
+        Some(datetime) => Some(datetime.with_timezone(&Utc)),
+        None => event
+            .start
+            .date_parsed()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|datetime| Utc.from_utc_datetime(&datetime)),
+    }
+}
+
+fn guid(event: &Event) -> String {
+    format!("urn:songkick:event:{}", event.id)
+}
+
+fn element(name: &str, text: &str) -> String {
+    format!("<{}>{}</{}>\n", name, escape(text), name)
+}
+
+fn link(href: &str) -> String {
+    format!("<link rel=\"alternate\" href=\"{}\"/>\n", escape(href))
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::SkResultSet;
+    use serde_json::Value;
+
+    fn load_events(path: &str) -> Vec<Event> {
+        let data: Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        SkResultSet::<Event>::from_json(&data).unwrap().collect()
+    }
+
+    #[test]
+    fn calendar_to_rss() {
+        let events = load_events("fixtures/event/artist-324967-calendar.json");
+        let rss = Feed::new("Placebo & friends", "https://www.songkick.com/artists/324967").rss(&events);
+
+        assert!(rss.contains("<title>Placebo &amp; friends</title>"));
+        assert_eq!(events.len(), rss.matches("<item>").count());
+        assert!(rss.contains(
+            "<link>https://www.songkick.com/concerts/26486294-placebo-at-cirkus</link>"
+        ));
+        assert!(rss.contains("<pubDate>Tue, 18 Oct 2016 17:30:00 +0000</pubDate>"));
+        assert!(rss.contains("<guid isPermaLink=\"false\">urn:songkick:event:26486294</guid>"));
+    }
+
+    #[test]
+    fn calendar_to_atom() {
+        let events = load_events("fixtures/event/artist-324967-calendar.json");
+        let updated = Utc.with_ymd_and_hms(2016, 9, 1, 12, 0, 0).unwrap();
+        let atom = Feed::new("Placebo", "https://www.songkick.com/artists/324967")
+            .updated(updated)
+            .atom(&events);
+
+        assert!(atom.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+        assert!(atom.contains("<updated>2016-09-01T12:00:00+00:00</updated>"));
+        assert_eq!(events.len(), atom.matches("<entry>").count());
+        assert!(atom.contains("<published>2016-10-18T17:30:00+00:00</published>"));
+    }
+}
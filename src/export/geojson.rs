@@ -0,0 +1,115 @@
+//! [GeoJSON](https://tools.ietf.org/html/rfc7946) export of events and venues
+//!
+//! Items without coordinates are left out, the collections serialize with `to_string()`.
+
+use crate::resources::event::Event;
+use crate::resources::venue::Venue;
+use geojson::{feature, Feature, FeatureCollection, Geometry, JsonObject, Value};
+use serde_json::json;
+
+/// Collection of the Events located at their venue (or city, when the venue has no coordinates)
+pub fn events(events: &[Event]) -> FeatureCollection {
+    collection(events.iter().filter_map(event))
+}
+
+/// Collection of the Venues with coordinates
+pub fn venues(venues: &[Venue]) -> FeatureCollection {
+    collection(venues.iter().filter_map(venue))
+}
+
+/// Point feature of an Event, with its id, name, date, status, venue and page URL as properties
+pub fn event(event: &Event) -> Option<Feature> {
+    let location = event.location.as_ref();
+    let lat = event.venue.lat.or_else(|| location.and_then(|location| location.lat))?;
+    let lng = event.venue.lng.or_else(|| location.and_then(|location| location.lng))?;
+
+    let mut properties = JsonObject::new();
+    properties.insert(String::from("id"), json!(event.id));
+    properties.insert(String::from("name"), json!(event.display_name));
+    properties.insert(String::from("date"), json!(event.start.date));
+    properties.insert(String::from("status"), json!(event.status));
+    properties.insert(String::from("venue"), json!(event.venue.display_name));
+    properties.insert(
+        String::from("city"),
+        json!(location.map(|location| &location.city)),
+    );
+    properties.insert(String::from("url"), json!(event.page_url()));
+
+    Some(point(lat, lng, Some(event.id.0), properties))
+}
+
+/// Point feature of a Venue, with its id, name and city as properties
+pub fn venue(venue: &Venue) -> Option<Feature> {
+    let (lat, lng) = (venue.lat?, venue.lng?);
+
+    let mut properties = JsonObject::new();
+    properties.insert(String::from("id"), json!(venue.id));
+    properties.insert(String::from("name"), json!(venue.display_name));
+    properties.insert(
+        String::from("city"),
+        json!(venue.metro_area.as_ref().map(|metro_area| &metro_area.display_name)),
+    );
+
+    Some(point(lat, lng, venue.id.map(|id| id.0), properties))
+}
+
+fn point(lat: f64, lng: f64, id: Option<u64>, properties: JsonObject) -> Feature {
+    Feature {
+        bbox: None,
+        // GeoJSON positions are longitude first
+        geometry: Some(Geometry::new(Value::Point(vec![lng, lat]))),
+        id: id.map(|id| feature::Id::Number(id.into())),
+        properties: Some(properties),
+        foreign_members: None,
+    }
+}
+
+fn collection<I: Iterator<Item = Feature>>(features: I) -> FeatureCollection {
+    FeatureCollection {
+        bbox: None,
+        features: features.collect(),
+        foreign_members: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::SkResultSet;
+    use serde_json::Value as JsonValue;
+
+    fn load_events(path: &str) -> Vec<Event> {
+        let data: JsonValue = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        SkResultSet::<Event>::from_json(&data).unwrap().collect()
+    }
+
+    #[test]
+    fn events_to_feature_collection() {
+        let events = load_events("fixtures/event/artist-324967-calendar.json");
+        let collection = super::events(&events);
+        assert_eq!(events.len(), collection.features.len());
+
+        let json: JsonValue = serde_json::from_str(&collection.to_string()).unwrap();
+        assert_eq!(JsonValue::from("FeatureCollection"), json["type"]);
+
+        let first = &json["features"][0];
+        assert_eq!(JsonValue::from("Point"), first["geometry"]["type"]);
+        assert_eq!(json!([18.0996982, 59.3245767]), first["geometry"]["coordinates"]);
+        assert_eq!(JsonValue::from(26486294), first["id"]);
+        assert_eq!(JsonValue::from("Cirkus"), first["properties"]["venue"]);
+        assert_eq!(JsonValue::from("2016-10-18"), first["properties"]["date"]);
+    }
+
+    #[test]
+    fn venues_without_coordinates_are_skipped() {
+        let events = load_events("fixtures/event/artist-324967-calendar.json");
+        let mut venues: Vec<Venue> = events.into_iter().map(|event| event.venue).collect();
+        venues[0].lat = None;
+        let located = venues.iter().filter(|venue| venue.lat.is_some() && venue.lng.is_some()).count();
+
+        let collection = super::venues(&venues);
+        assert!(located < venues.len());
+        assert_eq!(located, collection.features.len());
+        assert_eq!(Some(&JsonValue::from("Helsinki")), collection.features[0].property("city"));
+    }
+}
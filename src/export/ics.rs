@@ -0,0 +1,119 @@
+//! iCalendar ([RFC 5545](https://tools.ietf.org/html/rfc5545)) export of events
+
+use crate::resources::event::{Event, EventTime};
+use icalendar::{Calendar, Component, EventLike, EventStatus};
+
+/// Render `events` as an `.ics` document
+pub fn to_ics(events: &[Event]) -> String {
+    calendar(events).to_string()
+}
+
+/// Calendar with one VEVENT per Event, to be customised before rendering
+pub fn calendar(events: &[Event]) -> Calendar {
+    events.iter().map(vevent).collect::<Calendar>().done()
+}
+
+/// VEVENT of a single Event
+///
+/// Events without a start date only get their summary, location, URL and status.
+pub fn vevent(event: &Event) -> icalendar::Event {
+    let mut vevent = icalendar::Event::new();
+    vevent
+        .uid(&format!("{}@songkick.com", event.id))
+        .summary(&event.display_name)
+        .location(&event.venue.to_string());
+
+    if let (Some(lat), Some(lng)) = (event.venue.lat, event.venue.lng) {
+        vevent.add_property("GEO", format!("{};{}", lat, lng));
+    }
+    if let Some(url) = event.page_url() {
+        vevent.url(&url);
+    }
+    if let Some(status) = status(&event.status) {
+        vevent.status(status);
+    }
+
+    let interval = event.interval();
+    let start = event.start_utc();
+    let all_day = matches!(start, Some(EventTime::Date(_)))
+        || interval.is_some_and(|interval| interval.is_multi_day() && interval.end.is_none());
+
+    match (start, interval) {
+        // Events without a time, and festivals without an exact end, last whole days
+        (Some(_), Some(interval)) if all_day => {
+            // All-day events end on the day after their last day
+            vevent
+                .starts(interval.start_date)
+                .ends(interval.end_date.succ_opt().unwrap_or(interval.end_date));
+        }
+        (Some(EventTime::DateTime(start)), _) => {
+            vevent.starts(start);
+            if let Some(end) = interval.and_then(|interval| interval.end) {
+                vevent.ends(end.naive_utc().and_utc());
+            }
+        }
+        (Some(EventTime::Floating(start)), _) => {
+            vevent.starts(start);
+        }
+        _ => {}
+    }
+
+    vevent.done()
+}
+
+fn status(status: &str) -> Option<EventStatus> {
+    match status {
+        "ok" => Some(EventStatus::Confirmed),
+        "cancelled" => Some(EventStatus::Cancelled),
+        "postponed" => Some(EventStatus::Tentative),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::SkResultSet;
+    use serde_json::Value;
+    use std::fs::File;
+    use std::io::Read;
+
+    fn load_events(path: &str) -> Vec<Event> {
+        let mut file = File::open(path).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+        let data: Value = serde_json::from_str(&content).unwrap();
+        SkResultSet::<Event>::from_json(&data).unwrap().collect()
+    }
+
+    #[test]
+    fn calendar_to_ics() {
+        let events = load_events("fixtures/event/artist-324967-calendar.json");
+        let ics = to_ics(&events);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.trim_end().ends_with("END:VCALENDAR"));
+        assert_eq!(events.len(), ics.matches("BEGIN:VEVENT").count());
+        assert!(ics.contains("UID:26486294@songkick.com\r\n"));
+        assert!(ics.contains("DTSTART:20161018T173000Z\r\n"));
+        assert!(ics.contains("LOCATION:Cirkus\\, Stockholm\r\n"));
+        assert!(ics.contains("GEO:59.3245767;18.0996982\r\n"));
+        assert!(ics.contains("STATUS:CONFIRMED\r\n"));
+    }
+
+    #[test]
+    fn festival_is_all_day() {
+        let events = load_events("fixtures/event/single-event-festival-27081999.json");
+        let vevent = vevent(&events[0]);
+        let interval = events[0].interval().unwrap();
+
+        assert_eq!(
+            Some(interval.start_date.format("%Y%m%d").to_string().as_str()),
+            vevent.property_value("DTSTART")
+        );
+        assert_eq!(
+            Some(interval.end_date.succ_opt().unwrap().format("%Y%m%d").to_string().as_str()),
+            vevent.property_value("DTEND")
+        );
+    }
+}
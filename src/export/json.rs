@@ -0,0 +1,121 @@
+//! JSON files of fetched resources, and their reloading
+//!
+//! ```rust,no_run
+//! use songkick::SongKick;
+//! use songkick::export::json::{self, Format, WriteJson};
+//! use songkick::resources::Event;
+//!
+//! let sk = SongKick::new("API_KEY");
+//! // RadioHead ID
+//! let events = sk.artist.calendar_all(253846).unwrap();
+//! events.write_json("calendar.json", Format::Pretty).unwrap();
+//!
+//! let reloaded: Vec<Event> = json::read_json("calendar.json").unwrap();
+//! assert_eq!(events.len(), reloaded.len());
+//! ```
+
+use crate::error::SkError;
+use crate::resources::TopLevelResource;
+use crate::util::json::AtPath;
+use crate::SkResult;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// Layout of the written JSON
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Indented, one field per line
+    Pretty,
+    /// On a single line
+    Compact,
+}
+
+/// Writing of resources as a JSON array
+pub trait WriteJson {
+    /// Write to the file at `path`, replacing it
+    fn write_json<P: AsRef<Path>>(&self, path: P, format: Format) -> SkResult<()>;
+}
+
+impl<T: Serialize> WriteJson for [T] {
+    fn write_json<P: AsRef<Path>>(&self, path: P, format: Format) -> SkResult<()> {
+        write_json(BufWriter::new(File::create(path)?), self, format)
+    }
+}
+
+/// Write `items` to `writer` as a JSON array
+pub fn write_json<W, T>(mut writer: W, items: &[T], format: Format) -> SkResult<()>
+where
+    W: Write,
+    T: Serialize,
+{
+    match format {
+        Format::Pretty => serde_json::to_writer_pretty(&mut writer, items),
+        Format::Compact => serde_json::to_writer(&mut writer, items),
+    }
+    .map_err(io::Error::from)?;
+    writer.write_all(b"\n")?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Resources of the JSON array in the file at `path`, as written by [`WriteJson`]
+pub fn read_json<M, P>(path: P) -> SkResult<Vec<M>>
+where
+    M: TopLevelResource,
+    P: AsRef<Path>,
+{
+    let data: Value = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+    let items = data
+        .as_array()
+        .ok_or_else(|| SkError::decode(format!("Expected a JSON array, found {}", data)))?;
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| M::from_json(item).at(&format!("[{}]", i)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::event::Event;
+    use crate::result::SkResultSet;
+    use std::env;
+    use std::fs;
+
+    fn load_events(path: &str) -> Vec<Event> {
+        let json: Value = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        SkResultSet::<Event>::from_json(&json).unwrap().collect()
+    }
+
+    #[test]
+    fn write_and_read_json() {
+        let events = load_events("fixtures/event/artist-324967-calendar.json");
+        for (format, name) in [(Format::Pretty, "pretty"), (Format::Compact, "compact")] {
+            let path = env::temp_dir().join(format!("songkick-{}-{}.json", name, std::process::id()));
+            events.write_json(&path, format).unwrap();
+
+            let content = fs::read_to_string(&path).unwrap();
+            assert_eq!(format == Format::Compact, content.trim_end().lines().count() == 1);
+            let reloaded: Vec<Event> = read_json(&path).unwrap();
+            fs::remove_file(&path).unwrap();
+
+            assert_eq!(events.len(), reloaded.len());
+            assert_eq!(events[0].id, reloaded[0].id);
+            assert_eq!(events[0].start, reloaded[0].start);
+            assert_eq!(events[0].performances.len(), reloaded[0].performances.len());
+        }
+    }
+
+    #[test]
+    fn read_json_not_an_array() {
+        let path = env::temp_dir().join(format!("songkick-object-{}.json", std::process::id()));
+        fs::write(&path, "{}").unwrap();
+        let read = read_json::<Event, _>(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(read, Err(SkError::Decode { .. })));
+    }
+}
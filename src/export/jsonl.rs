@@ -0,0 +1,93 @@
+//! [JSON Lines](https://jsonlines.org) export, one JSON object per line
+//!
+//! Combined with a page stream, items are written as their page arrives:
+//!
+//! ```rust,no_run
+//! use songkick::SongKick;
+//! use songkick::export::jsonl;
+//! use std::fs::File;
+//! use std::io::BufWriter;
+//!
+//! let sk = SongKick::new("API_KEY");
+//! let file = BufWriter::new(File::create("gigography.jsonl").unwrap());
+//!
+//! // RadioHead ID
+//! let written = jsonl::write_results(file, sk.artist.gigography_pages(253846).items()).unwrap();
+//! println!("{} events", written);
+//! ```
+
+use crate::SkResult;
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// Write each of `items` on its own line, returns the number of lines written
+pub fn write_jsonl<W, T, I>(writer: W, items: I) -> SkResult<u64>
+where
+    W: Write,
+    T: Serialize,
+    I: IntoIterator<Item = T>,
+{
+    write_results(writer, items.into_iter().map(Ok))
+}
+
+/// Write each of the fetched `results` on its own line, stopping at the first error
+///
+/// Returns the number of lines written.
+pub fn write_results<W, T, I>(mut writer: W, results: I) -> SkResult<u64>
+where
+    W: Write,
+    T: Serialize,
+    I: IntoIterator<Item = SkResult<T>>,
+{
+    let mut written = 0;
+    for item in results {
+        serde_json::to_writer(&mut writer, &item?).map_err(io::Error::from)?;
+        writer.write_all(b"\n")?;
+        written += 1;
+    }
+    writer.flush()?;
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::SkError;
+    use crate::resources::event::Event;
+    use crate::result::SkResultSet;
+    use serde_json::Value;
+
+    fn load_events(path: &str) -> Vec<Event> {
+        let data: Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        SkResultSet::<Event>::from_json(&data).unwrap().collect()
+    }
+
+    #[test]
+    fn events_to_jsonl() {
+        let events = load_events("fixtures/event/artist-324967-calendar.json");
+        let mut out = Vec::new();
+        assert_eq!(33, write_jsonl(&mut out, &events).unwrap());
+
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(33, lines.len());
+
+        let first: Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(Value::from(26486294), first["id"]);
+        assert_eq!(Value::from("Cirkus"), first["venue"]["displayName"]);
+    }
+
+    #[test]
+    fn stop_at_first_error() {
+        let events = load_events("fixtures/event/artist-324967-calendar.json");
+        let results = events
+            .iter()
+            .take(2)
+            .map(Ok)
+            .chain(vec![Err(SkError::from_status(500, None, ""))]);
+
+        let mut out = Vec::new();
+        assert!(write_results(&mut out, results).is_err());
+        assert_eq!(2, String::from_utf8(out).unwrap().lines().count());
+    }
+}
@@ -0,0 +1,171 @@
+//! Markdown reports of events grouped by month, for newsletters and chat digests
+
+use crate::resources::event::Event;
+use std::collections::BTreeMap;
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Layout of the events of each month
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// Table with date, event, venue and city columns
+    Table,
+    /// Bullet list with one line per Event
+    List,
+}
+
+/// Render `events` under one `## Month Year` heading per month, in date order
+///
+/// Events without a date are listed last, under `## Date to be announced`.
+pub fn report(events: &[Event], style: Style) -> String {
+    // Keyed by (undated, month) so that undated events sort last
+    let mut months: BTreeMap<(bool, String), Vec<&Event>> = BTreeMap::new();
+    for event in events {
+        let month = date(event).get(..7).map(String::from);
+        months
+            .entry((month.is_none(), month.unwrap_or_default()))
+            .or_default()
+            .push(event);
+    }
+
+    let mut out = String::new();
+    for ((undated, month), mut events) in months {
+        events.sort_by_key(|event| date(event));
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        if undated {
+            out += "## Date to be announced\n\n";
+        } else {
+            out += &format!("## {}\n\n", heading(&month));
+        }
+        match style {
+            Style::Table => {
+                out += "| Date | Event | Venue | City |\n|---|---|---|---|\n";
+                for event in events {
+                    out += &format!(
+                        "| {} | {} | {} | {} |\n",
+                        date(event),
+                        title(event),
+                        cell(event.venue.display_name.as_deref().unwrap_or_default()),
+                        cell(&city(event))
+                    );
+                }
+            }
+            Style::List => {
+                for event in events {
+                    out += "- ";
+                    if !undated {
+                        out += &format!("**{}** ", date(event));
+                    }
+                    out += &title(event);
+                    let place: Vec<String> = event
+                        .venue
+                        .display_name
+                        .iter()
+                        .cloned()
+                        .chain(Some(city(event)).filter(|city| !city.is_empty()))
+                        .collect();
+                    if !place.is_empty() {
+                        out += &format!(" — {}", place.join(", "));
+                    }
+                    out.push('\n');
+                }
+            }
+        }
+    }
+    out
+}
+
+fn date(event: &Event) -> String {
+    event.start.date.as_ref().map(|date| date.to_string()).unwrap_or_default()
+}
+
+fn city(event: &Event) -> String {
+    match (&event.venue.metro_area, &event.location) {
+        (Some(metro_area), _) => metro_area.display_name.clone(),
+        (None, Some(location)) => location.city.clone(),
+        (None, None) => String::new(),
+    }
+}
+
+/// Name of the Event, linked to its page when known
+fn title(event: &Event) -> String {
+    let name = cell(&event.display_name).replace('[', "\\[").replace(']', "\\]");
+    match event.page_url() {
+        Some(url) => format!("[{}]({})", name, url),
+        None => name,
+    }
+}
+
+/// `2016-10` as `October 2016`
+fn heading(month: &str) -> String {
+    let name = month
+        .get(5..7)
+        .and_then(|number| number.parse::<usize>().ok())
+        .and_then(|number| MONTHS.get(number.wrapping_sub(1)));
+    match name {
+        Some(name) => format!("{} {}", name, &month[..4]),
+        None => String::from(month),
+    }
+}
+
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::SkResultSet;
+    use serde_json::Value;
+
+    fn load_events(path: &str) -> Vec<Event> {
+        let data: Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        SkResultSet::<Event>::from_json(&data).unwrap().collect()
+    }
+
+    #[test]
+    fn table_by_month() {
+        let events = load_events("fixtures/event/artist-324967-calendar.json");
+        let report = report(&events, Style::Table);
+
+        assert!(report.starts_with(
+            "## October 2016\n\n| Date | Event | Venue | City |\n|---|---|---|---|\n"
+        ));
+        assert!(report.contains(
+            "| 2016-10-18 | [Placebo with The Mirror Trap at Cirkus (October 18, 2016)]\
+             (https://www.songkick.com/concerts/26486294-placebo-at-cirkus) | Cirkus | Stockholm |\n"
+        ));
+        let headings: Vec<&str> = report.lines().filter(|line| line.starts_with("## ")).collect();
+        let mut sorted = headings.clone();
+        sorted.dedup();
+        assert_eq!(sorted, headings);
+        assert_eq!(events.len(), report.lines().filter(|line| line.starts_with("| 20")).count());
+    }
+
+    #[test]
+    fn list_by_month() {
+        let mut events = load_events("fixtures/event/artist-324967-calendar.json");
+        events.truncate(2);
+        events[1].start.date = None;
+
+        let report = report(&events, Style::List);
+        assert!(report.starts_with("## October 2016\n\n- **2016-10-18** [Placebo"));
+        assert!(report.contains("— Cirkus, Stockholm\n"));
+        assert!(report.contains("\n## Date to be announced\n\n- [Placebo"));
+    }
+}
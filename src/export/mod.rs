@@ -0,0 +1,17 @@
+//! Conversions of fetched resources to other formats
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "csv")]
+pub mod csv;
+#[cfg(feature = "polars")]
+pub mod dataframe;
+#[cfg(feature = "chrono")]
+pub mod feed;
+#[cfg(feature = "geojson")]
+pub mod geojson;
+#[cfg(feature = "ics")]
+pub mod ics;
+pub mod json;
+pub mod jsonl;
+pub mod markdown;
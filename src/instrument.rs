@@ -0,0 +1,120 @@
+//! Instrumentation of the requests to the API, enabled by the `metrics`, `tracing` and `log` features
+//!
+//! With `tracing` or `log`, each request logs the URL built at debug level, then its outcome:
+//! completed requests at info level with the number of results, failures at warn
+//! level when the request may be retried and at error level otherwise. The `log`
+//! records target the `songkick` module they come from, as usual for the facade.
+
+use crate::resources::TopLevelResource;
+use crate::result::SkResultSet;
+use crate::SkResult;
+use std::time::Instant;
+
+/// Observation of one request, from its start to its outcome
+pub(crate) struct RequestTrace<'a> {
+    #[cfg_attr(not(any(feature = "metrics", feature = "log")), allow(dead_code))]
+    endpoint: &'a str,
+    start: Instant,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+impl<'a> RequestTrace<'a> {
+    /// Start observing a request to `endpoint`, for `page` of the results
+    ///
+    /// With the `tracing` feature the request gets a `songkick.request` span, with the
+    /// OpenTelemetry attributes of an HTTP client call.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn start(endpoint: &'a str, page: Option<u64>) -> RequestTrace<'a> {
+        RequestTrace {
+            endpoint,
+            start: Instant::now(),
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "songkick.request",
+                otel.name = endpoint,
+                otel.kind = "client",
+                songkick.endpoint = endpoint,
+                songkick.page = page,
+                http.request.method = "GET",
+                http.response.status_code = tracing::field::Empty,
+                duration_ms = tracing::field::Empty,
+                error = tracing::field::Empty,
+            ),
+        }
+    }
+
+    /// Run `f` within the span of the request
+    pub(crate) fn in_scope<T, F: FnOnce() -> T>(&self, f: F) -> T {
+        #[cfg(feature = "tracing")]
+        let _entered = self.span.enter();
+        f()
+    }
+
+    /// The request is sent to `url`
+    #[cfg_attr(not(any(feature = "tracing", feature = "log")), allow(unused_variables))]
+    pub(crate) fn url(&self, url: &str) {
+        #[cfg(feature = "tracing")]
+        self.span.in_scope(|| {
+            tracing::debug!(url = %crate::error::redact_url(url), "request URL built");
+        });
+        #[cfg(feature = "log")]
+        log::debug!("{}: request URL built: {}", self.endpoint, crate::error::redact_url(url));
+    }
+
+    /// The response to the request had `status`
+    #[cfg_attr(not(any(feature = "tracing", feature = "log")), allow(unused_variables))]
+    pub(crate) fn response(&self, status: u16) {
+        #[cfg(feature = "tracing")]
+        self.span.record("http.response.status_code", status);
+        #[cfg(feature = "log")]
+        log::debug!("{}: response status {}", self.endpoint, status);
+    }
+
+    /// The request ended with `result`
+    #[cfg_attr(not(any(feature = "tracing", feature = "metrics", feature = "log")), allow(unused_variables))]
+    pub(crate) fn finish<M: TopLevelResource>(self, result: &SkResult<SkResultSet<M>>) {
+        let elapsed = self.start.elapsed();
+        #[cfg(feature = "metrics")]
+        crate::metrics::record(self.endpoint, elapsed, result.as_ref().err());
+        #[cfg(feature = "tracing")]
+        {
+            let duration_ms = elapsed.as_millis() as u64;
+            self.span.record("duration_ms", duration_ms);
+            let _entered = self.span.enter();
+            match *result {
+                Ok(ref results) => tracing::info!(
+                    duration_ms,
+                    results = results.len(),
+                    total_entries = results.total_entries,
+                    "request completed"
+                ),
+                Err(ref err) => {
+                    self.span.record("error", tracing::field::display(err));
+                    if err.is_retriable() {
+                        tracing::warn!(duration_ms, error = %err, "request failed, may be retried");
+                    } else {
+                        tracing::error!(duration_ms, error = %err, "request failed");
+                    }
+                }
+            }
+        }
+        #[cfg(feature = "log")]
+        {
+            let duration_ms = elapsed.as_millis();
+            match *result {
+                Ok(ref results) => log::info!(
+                    "{}: request completed in {}ms, {} results of {}",
+                    self.endpoint,
+                    duration_ms,
+                    results.len(),
+                    results.total_entries
+                ),
+                Err(ref err) if err.is_retriable() => {
+                    log::warn!("{}: request failed in {}ms, may be retried: {}", self.endpoint, duration_ms, err)
+                }
+                Err(ref err) => log::error!("{}: request failed in {}ms: {}", self.endpoint, duration_ms, err),
+            }
+        }
+    }
+}
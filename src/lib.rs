@@ -19,23 +19,64 @@
 //! assert_eq!(1,artists.len());
 //!
 //! ```
+//!
+//! Errors are [`Error`] values, `Result` is exported for signatures
+//!
+//! ```rust,no_run
+//! use songkick::{Error, SongKick};
+//! use songkick::endpoints::SkEndpoint;
+//! use songkick::resources::Event;
+//!
+//! fn calendar(sk: &SongKick, artist: u64) -> songkick::Result<Vec<Event>> {
+//!     match sk.artist.calendar(artist, None) {
+//!         Ok(events) => Ok(events.collect()),
+//!         Err(err) => match err.inner() {
+//!             Error::NotFound { .. } => Ok(Vec::new()),
+//!             _ => Err(err),
+//!         },
+//!     }
+//! }
+//! ```
 
 
 
 
 mod client;
+mod instrument;
+mod quota;
 mod result;
 mod util;
+pub mod analysis;
+pub mod archive;
 pub mod options;
 pub mod error;
 pub mod resources;
+pub mod schedule;
 pub mod endpoints;
+pub mod diff;
+pub mod discovery;
+pub mod export;
+pub mod matching;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "musicbrainz")]
+pub mod musicbrainz;
+pub mod sync;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod transport;
+pub mod watch;
 
 
 pub use client::SongKick as SongKick;
+pub use client::{ParseMode, SongKickBuilder, BASE_PATH, DEFAULT_BATCH_CONCURRENCY};
 pub use result::SkResultSet as SkResultSet;
 
-use error::SkError;
+pub use error::SkError;
+pub use error::SkError as Error;
 
 /// Result type alias
-pub type SkResult<T> = Result<T, SkError>;
\ No newline at end of file
+pub type SkResult<T> = std::result::Result<T, SkError>;
+
+/// Result of the calls of this crate, same as [`SkResult`]
+pub type Result<T> = SkResult<T>;
\ No newline at end of file
@@ -0,0 +1,254 @@
+//! Matching of names typed by users against the names of SongKick
+//!
+//! Searches of SongKick rank their results by popularity, not by likeness: the search
+//! for an artist regularly lists tribute acts and side projects before the artist. The
+//! helpers here compare names once [normalized](normalize), as done by
+//! [`ArtistEndpoint::resolve`](crate::endpoints::ArtistEndpoint::resolve).
+//!
+//! ```rust
+//! use songkick::matching::normalize;
+//!
+//! assert_eq!("sigur ros", normalize("Sigur Rós"));
+//! assert_eq!("guns n roses", normalize("Guns N' Roses"));
+//! ```
+//!
+//! With the `strsim` feature, [`rank`] orders search results by the similarity of their
+//! names to the query, with a [`score`] to show the confident matches first.
+
+/// Likeness of a name to a query, the lower the closer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Likeness {
+    /// Same name, leading and trailing spaces aside
+    Exact,
+    /// Same normalized name
+    Normalized,
+    /// Same normalized name without a leading `the`
+    WithoutArticle,
+}
+
+/// `name` in lower case, without accents, with its punctuation dropped and its words
+/// separated by single spaces
+///
+/// `&` is read `and`, and the apostrophes are dropped within words: `Guns N' Roses`
+/// normalizes to `guns n roses`.
+pub fn normalize(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    let mut space = false;
+    for c in name.chars().flat_map(char::to_lowercase) {
+        if c == '\'' || c == '\u{2019}' || c == '.' {
+            // Within a word: `o'brien`, `r.e.m.`
+            continue;
+        }
+        if c == '&' {
+            space = true;
+            push_word(&mut normalized, &mut space, "and");
+            space = true;
+        } else if c.is_alphanumeric() {
+            let mut buf = [0; 4];
+            push_word(&mut normalized, &mut space, fold(c).unwrap_or_else(|| c.encode_utf8(&mut buf)));
+        } else {
+            space = true;
+        }
+    }
+    normalized
+}
+
+/// Append `text` to `normalized`, after a space when one is pending
+fn push_word(normalized: &mut String, space: &mut bool, text: &str) {
+    if *space && !normalized.is_empty() {
+        normalized.push(' ');
+    }
+    *space = false;
+    normalized.push_str(text);
+}
+
+/// Latin letter without its accent, for the most common accented letters
+fn fold(c: char) -> Option<&'static str> {
+    Some(match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'č' => "c",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ę' | 'ě' => "e",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' => "i",
+        'ð' => "d",
+        'ñ' | 'ń' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' => "o",
+        'œ' => "oe",
+        'ł' => "l",
+        'ř' => "r",
+        'ś' | 'š' => "s",
+        'ß' => "ss",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        'þ' => "th",
+        _ => return None,
+    })
+}
+
+/// `normalized` without a leading `the`
+fn without_article(normalized: &str) -> &str {
+    normalized.strip_prefix("the ").unwrap_or(normalized)
+}
+
+/// Likeness of `name` to `query`, `None` for different names
+pub(crate) fn likeness(query: &str, name: &str) -> Option<Likeness> {
+    if query.trim() == name.trim() {
+        return Some(Likeness::Exact);
+    }
+    let (query, name) = (normalize(query), normalize(name));
+    if query.is_empty() {
+        None
+    } else if query == name {
+        Some(Likeness::Normalized)
+    } else if without_article(&query) == without_article(&name) {
+        Some(Likeness::WithoutArticle)
+    } else {
+        None
+    }
+}
+
+/// Index of the candidate whose name is the most like `query`, the first one of a same
+/// likeness, `None` when no name is like `query`
+pub(crate) fn best_match<T, F>(query: &str, candidates: &[T], name: F) -> Option<usize>
+where
+    F: Fn(&T) -> &str,
+{
+    candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, candidate)| Some((likeness(query, name(candidate))?, i)))
+        .min()
+        .map(|(_, i)| i)
+}
+
+/// Resource found by name, an artist or a venue
+#[cfg(feature = "strsim")]
+pub trait Named {
+    /// Name to compare to the query
+    fn name(&self) -> Option<&str>;
+}
+
+#[cfg(feature = "strsim")]
+impl Named for crate::resources::artist::Artist {
+    fn name(&self) -> Option<&str> {
+        Some(&self.display_name)
+    }
+}
+
+#[cfg(feature = "strsim")]
+impl Named for crate::resources::venue::Venue {
+    fn name(&self) -> Option<&str> {
+        self.display_name.as_deref()
+    }
+}
+
+/// Search result with the similarity of its name to the query, computed by [`rank`]
+#[cfg(feature = "strsim")]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Scored<T> {
+    pub item: T,
+    /// [`score`] of the name, from 0 for unrelated names to 1 for the same name
+    pub score: f64,
+}
+
+/// Similarity of `name` to `query`, from 0 for unrelated names to 1 for the same name
+///
+/// The Jaro-Winkler similarity of the [normalized](normalize) names, with and without a
+/// leading `the`, the highest. The same normalized names score 1.
+#[cfg(feature = "strsim")]
+pub fn score(query: &str, name: &str) -> f64 {
+    let (query, name) = (normalize(query), normalize(name));
+    if query.is_empty() || name.is_empty() {
+        return 0.0;
+    }
+    strsim::jaro_winkler(&query, &name).max(strsim::jaro_winkler(without_article(&query), without_article(&name)))
+}
+
+/// `items` by decreasing [`score`] of their names to `query`, the order of `items` kept for
+/// the same scores
+///
+/// ```rust
+/// # #[cfg(all(feature = "strsim", feature = "testing"))]
+/// # {
+/// use songkick::matching::rank;
+/// use songkick::resources::Artist;
+/// use songkick::testing::fixtures;
+///
+/// let artists = fixtures::ARTIST_SEARCH.load::<Artist>().unwrap();
+/// for scored in rank("placebo", artists) {
+///     println!("{:.2} {}", scored.score, scored.item.display_name);
+/// }
+/// # }
+/// ```
+#[cfg(feature = "strsim")]
+pub fn rank<T, I>(query: &str, items: I) -> Vec<Scored<T>>
+where
+    T: Named,
+    I: IntoIterator<Item = T>,
+{
+    let mut ranked: Vec<Scored<T>> = items
+        .into_iter()
+        .map(|item| {
+            let score = item.name().map_or(0.0, |name| score(query, name));
+            Scored { item, score }
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalized_names() {
+        assert_eq!("the cure", normalize("  The   CURE! "));
+        assert_eq!("rem", normalize("R.E.M."));
+        assert_eq!("simon and garfunkel", normalize("Simon & Garfunkel"));
+        assert_eq!("simon and garfunkel", normalize("Simon&Garfunkel"));
+        assert_eq!("motorhead", normalize("Motörhead"));
+        assert_eq!("sinead oconnor", normalize("Sinéad O’Connor"));
+        assert_eq!("", normalize("?!"));
+    }
+
+    #[test]
+    fn best_matches() {
+        let names = ["The Cure Tribute", "Cure", "The Cure", "the cure"];
+        let found = |query| best_match(query, &names, |name| name);
+        assert_eq!(Some(2), found("The Cure"));
+        assert_eq!(Some(2), found("the cure!"));
+        assert_eq!(Some(1), found("cure"));
+        assert_eq!(Some(0), found("the cure tribute"));
+        assert_eq!(None, found("The Cult"));
+        assert_eq!(None, found(""));
+        assert_eq!(None, best_match("The Cure", &[] as &[&str], |name| name));
+    }
+
+    #[cfg(feature = "strsim")]
+    #[test]
+    fn ranked_by_score() {
+        use crate::testing::{ArtistBuilder, VenueBuilder};
+
+        assert_eq!(1.0, score("the cure", "The Cure!"));
+        assert_eq!(1.0, score("cure", "The Cure"));
+        assert_eq!(0.0, score("", "The Cure"));
+        assert!(score("placebo", "Placebo Tribute") > score("placebo", "Coldplay"));
+
+        let artists = ["Coldplay", "The Cure Tribute", "The Cure"].iter().map(|name| ArtistBuilder::new().name(*name).build());
+        let ranked: Vec<(String, f64)> =
+            rank("The Cure", artists).into_iter().map(|scored| (scored.item.display_name, scored.score)).collect();
+        let names: Vec<&str> = ranked.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(vec!["The Cure", "The Cure Tribute", "Coldplay"], names);
+        assert_eq!(1.0, ranked[0].1);
+
+        let mut unnamed = VenueBuilder::new().unknown().build();
+        unnamed.display_name = None;
+        let venues = vec![unnamed, VenueBuilder::new().name("Brixton Academy").build()];
+        let ranked = rank("brixton", venues);
+        assert_eq!(Some("Brixton Academy"), ranked[0].item.display_name.as_deref());
+        assert_eq!(0.0, ranked[1].score);
+    }
+}
@@ -0,0 +1,77 @@
+//! Metrics of the API calls, recorded through the [`metrics`](https://docs.rs/metrics) facade
+//!
+//! Every request records, labelled with its `endpoint` (e.g. `artists.calendar`):
+//!
+//! - [`REQUESTS`], a counter of the requests sent;
+//! - [`ERRORS`], a counter of the failed requests, also labelled with the error `class`
+//!   (e.g. `rate_limited`, `decode`);
+//! - [`DURATION`], a histogram of the request latency in seconds.
+//!
+//! The metrics go to the recorder installed by the application, e.g. a Prometheus exporter.
+
+use crate::error::SkError;
+use ::metrics::{counter, describe_counter, describe_histogram, histogram, Unit};
+use std::time::Duration;
+
+/// Counter of the requests sent
+pub const REQUESTS: &str = "songkick_requests_total";
+/// Counter of the failed requests
+pub const ERRORS: &str = "songkick_errors_total";
+/// Histogram of the duration of the requests, in seconds
+pub const DURATION: &str = "songkick_request_duration_seconds";
+
+/// Register the descriptions of the metrics with the installed recorder
+pub fn describe() {
+    describe_counter!(REQUESTS, Unit::Count, "Requests sent to the SongKick API");
+    describe_counter!(ERRORS, Unit::Count, "Failed requests to the SongKick API, by error class");
+    describe_histogram!(DURATION, Unit::Seconds, "Duration of the requests to the SongKick API");
+}
+
+/// Record a request to `endpoint` that took `elapsed` and failed with `error`, if any
+pub(crate) fn record(endpoint: &str, elapsed: Duration, error: Option<&SkError>) {
+    let endpoint = endpoint.to_string();
+    counter!(REQUESTS, "endpoint" => endpoint.clone()).increment(1);
+    histogram!(DURATION, "endpoint" => endpoint.clone()).record(elapsed.as_secs_f64());
+    if let Some(error) = error {
+        counter!(ERRORS, "endpoint" => endpoint, "class" => class(error)).increment(1);
+    }
+}
+
+/// Label of the kind of `error`
+fn class(error: &SkError) -> &'static str {
+    match *error.inner() {
+        SkError::Transport(ref err) if err.is_timeout() => "timeout",
+        SkError::Transport(_) => "transport",
+        SkError::Http { status, .. } if status >= 500 => "server",
+        SkError::Http { .. } => "client",
+        SkError::NotFound { .. } => "not_found",
+        SkError::Unauthorized { .. } => "unauthorized",
+        SkError::RateLimited { .. } => "rate_limited",
+        SkError::UnexpectedContentType { .. } => "unexpected_content_type",
+        SkError::Decode { .. } => "decode",
+        SkError::Api { .. } => "api",
+        SkError::InvalidInput(_) => "invalid_input",
+        SkError::Io(_) => "io",
+        SkError::ResponseTooLarge { .. } => "response_too_large",
+        SkError::QuotaExhausted { .. } => "quota_exhausted",
+        SkError::Store(_) => "store",
+        SkError::Request { .. } => "request",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_classes() {
+        assert_eq!("server", class(&SkError::from_status(503, None, "")));
+        assert_eq!("client", class(&SkError::from_status(400, None, "")));
+        assert_eq!("rate_limited", class(&SkError::from_status(429, Some("30"), "")));
+        assert_eq!(
+            "not_found",
+            class(&SkError::from_status(404, None, "").with_request("artists.get", "http://localhost"))
+        );
+        assert_eq!("decode", class(&SkError::decode("missing field")));
+    }
+}
@@ -0,0 +1,171 @@
+//! Enrichment of artists with their [MusicBrainz](https://musicbrainz.org/doc/MusicBrainz_API) metadata
+//!
+//! MusicBrainz asks clients to send a meaningful `User-Agent` and to stay below one
+//! request per second.
+//!
+//! ```rust,no_run
+//! use songkick::SongKick;
+//! use songkick::endpoints::SkEndpoint;
+//! use songkick::musicbrainz::MusicBrainz;
+//!
+//! let sk = SongKick::new("API_KEY");
+//! let mb = MusicBrainz::new("my-concert-app/1.0 (me@example.com)");
+//!
+//! // RadioHead ID
+//! for artist in sk.artist.get(253846).unwrap() {
+//!     let enriched = mb.enrich(artist).unwrap();
+//!     if let Some(metadata) = enriched.musicbrainz {
+//!         println!("{}: {:?} from {:?}", enriched.artist, metadata.artist_type, metadata.area);
+//!     }
+//! }
+//! ```
+
+use crate::error::SkError;
+use crate::resources::artist::Artist;
+use crate::util::json::{get_opt_str, get_str, AtPath};
+use crate::SkResult;
+use reqwest::blocking::Client;
+use reqwest::header::{RETRY_AFTER, USER_AGENT};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Root of the MusicBrainz web service
+pub const BASE_URL: &str = "https://musicbrainz.org/ws/2";
+
+/// Metadata of an artist in MusicBrainz
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct MusicBrainzArtist {
+    pub mbid: String,
+    pub name: String,
+    /// Comment telling apart artists with the same name, if any
+    pub disambiguation: Option<String>,
+    /// Kind of artist (e.g. `Person`, `Group`)
+    pub artist_type: Option<String>,
+    /// Name of the area the artist is from
+    pub area: Option<String>,
+    /// ISO 3166-1 alpha-2 code of the country of the artist
+    pub country: Option<String>,
+}
+
+impl MusicBrainzArtist {
+    fn from_json(source: &Value) -> SkResult<MusicBrainzArtist> {
+        match source.as_object() {
+            Some(obj) => {
+                let area = match obj.get("area").and_then(Value::as_object) {
+                    Some(area) => get_opt_str(area, "name").at("area")?,
+                    None => None,
+                };
+                Ok(MusicBrainzArtist {
+                    mbid: get_str(obj, "id")?,
+                    name: get_str(obj, "name")?,
+                    disambiguation: get_opt_str(obj, "disambiguation")?.filter(|text| !text.is_empty()),
+                    artist_type: get_opt_str(obj, "type")?,
+                    area,
+                    country: get_opt_str(obj, "country")?,
+                })
+            }
+            None => Err(SkError::decode(format!("Expected source json to be an object {}", source))),
+        }
+    }
+}
+
+/// A SongKick artist with its MusicBrainz metadata
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct EnrichedArtist {
+    pub artist: Artist,
+    /// Metadata of the first MusicBrainz identifier of the artist, none without identifiers
+    pub musicbrainz: Option<MusicBrainzArtist>,
+}
+
+/// Client of the MusicBrainz web service
+pub struct MusicBrainz {
+    client: Client,
+    base_url: String,
+    user_agent: String,
+}
+
+impl MusicBrainz {
+    /// Client sending `user_agent`, e.g. `my-app/1.0 (contact@example.com)`
+    pub fn new<T: Into<String>>(user_agent: T) -> MusicBrainz {
+        MusicBrainz {
+            client: Client::new(),
+            base_url: String::from(BASE_URL),
+            user_agent: user_agent.into(),
+        }
+    }
+
+    /// Use another root than [`BASE_URL`], e.g. a mirror
+    pub fn with_base_url<T: Into<String>>(mut self, base_url: T) -> MusicBrainz {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Metadata of the artist with MusicBrainz ID `mbid`
+    pub fn artist(&self, mbid: &str) -> SkResult<MusicBrainzArtist> {
+        let url = format!("{}/artist/{}?fmt=json", self.base_url, mbid);
+        self.fetch(&url)
+            .and_then(|data| MusicBrainzArtist::from_json(&data))
+            .map_err(|err| err.with_request("musicbrainz.artist", &url))
+    }
+
+    /// `artist` along with the metadata of its first MusicBrainz identifier
+    pub fn enrich(&self, artist: Artist) -> SkResult<EnrichedArtist> {
+        let musicbrainz = match artist.identifiers.first() {
+            Some(identifier) => Some(self.artist(&identifier.mbid.to_string())?),
+            None => None,
+        };
+        Ok(EnrichedArtist { artist, musicbrainz })
+    }
+
+    fn fetch(&self, url: &str) -> SkResult<Value> {
+        let resp = self.client.get(url).header(USER_AGENT, self.user_agent.as_str()).send()?;
+        let status = resp.status();
+        let retry_after = resp
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|val| val.to_str().ok())
+            .map(String::from);
+        let body = resp.text()?;
+        if !status.is_success() {
+            return Err(SkError::from_status(status.as_u16(), retry_after.as_deref(), &body));
+        }
+        Ok(serde_json::from_str(&body)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::io::Read;
+
+    fn load_json(path: &str) -> Value {
+        let mut file = File::open(path).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+        serde_json::from_str(&content).unwrap()
+    }
+
+    #[test]
+    fn parse_musicbrainz_artist() {
+        let data = load_json("fixtures/musicbrainz/artist-a74b1b7f-71a5-4011-9441-d0b5e4122711.json");
+        let artist = MusicBrainzArtist::from_json(&data).unwrap();
+
+        assert_eq!("a74b1b7f-71a5-4011-9441-d0b5e4122711", artist.mbid);
+        assert_eq!("Radiohead", artist.name);
+        assert_eq!(None, artist.disambiguation);
+        assert_eq!(Some(String::from("Group")), artist.artist_type);
+        assert_eq!(Some(String::from("United Kingdom")), artist.area);
+        assert_eq!(Some(String::from("GB")), artist.country);
+    }
+
+    #[test]
+    fn parse_musicbrainz_error() {
+        let data = serde_json::json!({"id": "a74b1b7f-71a5-4011-9441-d0b5e4122711", "area": {"name": 1}});
+        assert!(matches!(MusicBrainzArtist::from_json(&data), Err(SkError::Decode { .. })));
+    }
+}
@@ -35,15 +35,72 @@
 //!
 //! ```
 
-use crate::util::encode;
+use crate::util::encode_into;
+use std::fmt::{self, Write};
 
 /// Struct used for filtering, paging and sorting options
+#[derive(Clone)]
 pub struct Options {
     paging: Option<Paging>,
     filter: Option<Filter>,
     sort: Option<Sort>,
+    /// Extra parameters, names and values not encoded
+    params: Vec<(String, String)>,
 }
 
+impl Options {
+    /// Requested page, if any
+    pub(crate) fn page(&self) -> Option<u64> {
+        self.paging.as_ref().map(|paging| paging.page)
+    }
+
+    /// Options of the page of `page`, with the filters and the order of these options
+    pub(crate) fn for_page(&self, page: Options) -> Options {
+        Options {
+            paging: page.paging,
+            filter: self.filter.clone(),
+            sort: self.sort.or(page.sort),
+            params: self.params.clone(),
+        }
+    }
+
+    /// `options`, or no options, with the filters set by `filter` too
+    pub(crate) fn filtered<F>(options: Option<Options>, filter: F) -> Options
+    where
+        F: FnOnce(&mut FilterBuilder),
+    {
+        let mut options = options.unwrap_or_else(|| OptionsBuilder::new().build());
+        let mut builder = match options.filter.take() {
+            Some(current) => FilterBuilder {
+                empty: false,
+                artist_name: current.artist_name,
+                min_date: current.min_date,
+                max_date: current.max_date,
+                location: current.location,
+            },
+            None => FilterBuilder::new(),
+        };
+        filter(&mut builder);
+        options.filter = builder.build();
+        options
+    }
+
+    /// Expected length of the query string of the options, enough unless the filters need much encoding
+    fn query_len(&self) -> usize {
+        // `&per_page=` and a 20 digits number, the longest fixed parameter
+        const PARAM_LEN: usize = 32;
+        let filter = self.filter.as_ref().map_or(0, |filter| {
+            [&filter.min_date, &filter.max_date, &filter.artist_name, &filter.location]
+                .iter()
+                .map(|value| value.as_ref().map_or(0, |value| PARAM_LEN + value.len()))
+                .sum()
+        });
+        let params: usize = self.params.iter().map(|(name, value)| 2 + name.len() + value.len()).sum();
+        filter + params + 3 * PARAM_LEN
+    }
+}
+
+#[derive(Clone)]
 struct Filter {
     artist_name: Option<String>,
     min_date: Option<String>,
@@ -51,11 +108,13 @@ struct Filter {
     location: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Sort {
     ASC,
     DESC,
 }
 
+#[derive(Clone)]
 struct Paging {
     per_page: u64,
     page: u64,
@@ -133,6 +192,13 @@ pub struct OptionsBuilder {
     filter: FilterBuilder,
     paging: Option<Paging>,
     sort: Option<Sort>,
+    params: Vec<(String, String)>,
+}
+
+impl Default for OptionsBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl OptionsBuilder {
@@ -141,13 +207,14 @@ impl OptionsBuilder {
             paging: None,
             filter: FilterBuilder::new(),
             sort: None,
+            params: Vec::new(),
         }
     }
 
     pub fn paging(mut self, page: u64, per_page: u64) -> OptionsBuilder {
         self.paging = Some(Paging {
-            per_page: per_page,
-            page: page,
+            per_page,
+            page,
         });
         self
     }
@@ -163,66 +230,104 @@ impl OptionsBuilder {
         filter(&mut self.filter);
         self
     }
+
+    /// Extra query parameter, for the parameters of the API without a dedicated option
+    ///
+    /// `name` and `value` are percent-encoded and appended after the other options, in the
+    /// order they are given. They don't replace the options of the same name.
+    ///
+    /// ```rust
+    /// use songkick::options::{format_with_options, OptionsBuilder};
+    ///
+    /// let options = OptionsBuilder::new().param("type", "festival").param("age", "18+").build();
+    /// let url = format_with_options("http://api.songkick.com/api/3.0/events.json?apikey=KEY", Some(options));
+    /// assert!(url.ends_with("&type=festival&age=18%2B"));
+    /// ```
+    pub fn param<N, V>(mut self, name: N, value: V) -> OptionsBuilder
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.params.push((name.into(), value.into()));
+        self
+    }
+
     pub fn build(self) -> Options {
         Options {
             paging: self.paging,
             filter: self.filter.build(),
             sort: self.sort,
+            params: self.params,
         }
     }
 }
 
 pub fn format_with_options(url: &str, options: Option<Options>) -> String {
-    match options {
-        Some(opts) => {
-            let mut new_url = String::from(url);
-
-            // filtering
-
-            if let Some(filter) = opts.filter {
-                if let Some(min_date) = filter.min_date {
-                    new_url = format!("{}&min_date={}", new_url, encode(&min_date));
-                }
-                if let Some(max_date) = filter.max_date {
-                    new_url = format!("{}&max_date={}", new_url, encode(&max_date));
-                }
-                if let Some(artist_name) = filter.artist_name {
-                    new_url = format!("{}&artist_name={}", new_url, encode(&artist_name));
-                }
-                if let Some(location) = filter.location {
-                    new_url = format!("{}&location={}", new_url, encode(&location));
-                }
+    let opts = match options {
+        Some(opts) => opts,
+        None => return String::from(url),
+    };
+
+    // Built in place, every request goes through here
+    let mut new_url = String::with_capacity(url.len() + opts.query_len());
+    new_url.push_str(url);
+
+    // filtering
+
+    if let Some(filter) = opts.filter {
+        let params = [
+            ("min_date", filter.min_date),
+            ("max_date", filter.max_date),
+            ("artist_name", filter.artist_name),
+            ("location", filter.location),
+        ];
+        for (name, value) in params.iter() {
+            if let Some(value) = value {
+                push_param(&mut new_url, name, "");
+                encode_into(&mut new_url, value);
             }
+        }
+    }
 
-            // pagination
-            if let Some(paging) = opts.paging {
-                new_url = format!("{}&page={}", new_url, paging.page);
-                new_url = format!("{}&per_page={}", new_url, paging.per_page)
-            }
+    // pagination
+    if let Some(paging) = opts.paging {
+        push_param(&mut new_url, "page", paging.page);
+        push_param(&mut new_url, "per_page", paging.per_page);
+    }
 
-            // sorting
+    // sorting
 
-            if let Some(sort) = opts.sort {
-                let order = match sort {
-                    Sort::ASC => String::from("asc"),
-                    Sort::DESC => String::from("desc"),
-                };
-                new_url = format!("{}&order={}", new_url, order);
-            }
+    if let Some(sort) = opts.sort {
+        let order = match sort {
+            Sort::ASC => "asc",
+            Sort::DESC => "desc",
+        };
+        push_param(&mut new_url, "order", order);
+    }
 
-            new_url
-        }
-        None => String::from(url),
+    for (name, value) in &opts.params {
+        new_url.push('&');
+        encode_into(&mut new_url, name);
+        new_url.push('=');
+        encode_into(&mut new_url, value);
     }
+
+    new_url
+}
+
+/// Append `&name=value` to `url`, `value` already encoded
+fn push_param<V: fmt::Display>(url: &mut String, name: &str, value: V) {
+    // Writing to a String never fails
+    let _ = write!(url, "&{}={}", name, value);
 }
 
 #[cfg(test)]
 mod tests {
     use crate::client::SongKickOpts;
     use crate::options::format_with_options;
+    use crate::options::Options;
     use crate::options::OptionsBuilder;
     use crate::options::Sort;
-    use std::sync::Arc;
 
     #[test]
     fn no_options() {
@@ -341,6 +446,85 @@ mod tests {
         assert_eq!("http://api.songkick.com/api/3.0/artists/253846/calendar.json?apikey=DUMMY&min_date=2017%2D06%2D06&max_date=2017%2D06%2D09&page=1&per_page=5&order=desc", format_with_options(&url, Some(options)));
     }
 
+    #[test]
+    fn url_built_in_one_allocation() {
+        let url = "http://api.songkick.com/api/3.0/events.json?apikey=DUMMY";
+        let options = OptionsBuilder::new()
+            .filter(|f| {
+                f.artist_name("Sigur Rós").location("geo:51.5,-0.1").min_date("2017-06-06");
+            })
+            .paging(100, 50)
+            .sort(Sort::ASC)
+            .build();
+        let capacity = url.len() + options.query_len();
+
+        let new_url = format_with_options(url, Some(options));
+        assert_eq!("http://api.songkick.com/api/3.0/events.json?apikey=DUMMY&min_date=2017%2D06%2D06&artist_name=Sigur%20R%C3%B3s&location=geo:51.5,%2D0.1&page=100&per_page=50&order=asc", new_url);
+        assert_eq!(capacity, new_url.capacity());
+    }
+
+    #[test]
+    fn filters_added_to_options() {
+        let url = "http://api.songkick.com/api/3.0/events.json?apikey=DUMMY";
+        let options = OptionsBuilder::new()
+            .paging(2, 10)
+            .filter(|f| {
+                f.location("clientip").artist_name("Placebo");
+            })
+            .build();
+        let options = Options::filtered(Some(options), |f| {
+            f.artist_name("Radiohead");
+        });
+        assert_eq!(
+            format!("{}&artist_name=Radiohead&location=clientip&page=2&per_page=10", url),
+            format_with_options(url, Some(options))
+        );
+
+        let options = Options::filtered(None, |f| {
+            f.min_date("2017-06-06");
+        });
+        assert_eq!(format!("{}&min_date=2017%2D06%2D06", url), format_with_options(url, Some(options)));
+    }
+
+    #[test]
+    fn extra_params() {
+        let url = "http://api.songkick.com/api/3.0/events.json?apikey=DUMMY";
+        let options = OptionsBuilder::new()
+            .param("type", "festival")
+            .sort(Sort::ASC)
+            .param("a&b", "Simon & Garfunkel=1+1")
+            .build();
+        let capacity = url.len() + options.query_len();
+        let page = options.for_page(OptionsBuilder::new().paging(2, 50).build());
+
+        let new_url = format_with_options(url, Some(options));
+        assert_eq!(
+            format!("{}&order=asc&type=festival&a%26b=Simon%20%26%20Garfunkel%3D1%2B1", url),
+            new_url
+        );
+        assert_eq!(capacity, new_url.capacity());
+        assert_eq!(
+            format!("{}&page=2&per_page=50&order=asc&type=festival&a%26b=Simon%20%26%20Garfunkel%3D1%2B1", url),
+            format_with_options(url, Some(page))
+        );
+    }
+
+    #[test]
+    fn literal_percent_signs() {
+        let url = "http://api.songkick.com/api/3.0/events.json?apikey=DUMMY";
+        let options = OptionsBuilder::new()
+            .filter(|f| {
+                f.artist_name("100% Funk");
+            })
+            .param("50%off", "%2F")
+            .build();
+
+        assert_eq!(
+            format!("{}&artist_name=100%25%20Funk&50%25off=%252F", url),
+            format_with_options(url, Some(options))
+        );
+    }
+
     fn mock_sk_options() -> SongKickOpts {
         SongKickOpts::new(String::from("DUMMY"), "http://api.songkick.com/api/3.0")
     }
@@ -0,0 +1,171 @@
+//! Daily request budget of an API key, kept on disk
+//!
+//! See [`SongKickBuilder::daily_quota`](crate::SongKickBuilder::daily_quota). The file
+//! counts the requests of the day of each API key, keyed by a digest of the key rather
+//! than the key itself, so that several clients and processes can share it:
+//!
+//! ```json
+//! {"keys": {"9f3c5a0e12b4d7c8": {"day": "2026-10-14", "used": 42}}}
+//! ```
+//!
+//! Days are UTC days, the budgets are renewed at midnight UTC. The file is locked with
+//! [`File::lock`], which needs Rust 1.89.
+
+use crate::analysis::civil_date;
+use crate::error::SkError;
+use crate::SkResult;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Content of the file of the budgets
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct QuotaFile {
+    #[serde(default)]
+    keys: BTreeMap<String, Usage>,
+}
+
+/// Requests of an API key on `day`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Usage {
+    day: String,
+    used: u64,
+}
+
+/// Budget of `limit` requests a day for an API key, counted in the file at `path`
+#[derive(Debug)]
+pub(crate) struct DailyQuota {
+    limit: u64,
+    path: PathBuf,
+    key: String,
+    /// Serializes the updates of the file within the process, the file lock across processes
+    lock: Mutex<()>,
+}
+
+impl DailyQuota {
+    pub(crate) fn new(api_key: &str, limit: u64, path: PathBuf) -> DailyQuota {
+        DailyQuota {
+            limit,
+            path,
+            key: digest(api_key),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Count a request, or fail with [`SkError::QuotaExhausted`] once the budget of the day is spent
+    ///
+    /// Called right before sending the request, whatever its outcome.
+    pub(crate) fn spend(&self) -> SkResult<()> {
+        self.spend_at(SystemTime::now())
+    }
+
+    fn spend_at(&self, now: SystemTime) -> SkResult<()> {
+        let _guard = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(&self.path)?;
+        file.lock()?;
+
+        let mut quotas = read(&mut file)?;
+        let day = now.duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs() / SECONDS_PER_DAY);
+        let (year, month, date) = civil_date(day as i64);
+        let today = format!("{:04}-{:02}-{:02}", year, month, date);
+
+        let usage = quotas.keys.entry(self.key.clone()).or_insert_with(|| Usage {
+            day: today.clone(),
+            used: 0,
+        });
+        if usage.day != today {
+            *usage = Usage { day: today, used: 0 };
+        }
+        if usage.used >= self.limit {
+            return Err(SkError::QuotaExhausted {
+                resets_at: UNIX_EPOCH + Duration::from_secs((day + 1) * SECONDS_PER_DAY),
+            });
+        }
+        usage.used += 1;
+        write(&mut file, &quotas)?;
+        Ok(())
+    }
+}
+
+fn read(file: &mut File) -> SkResult<QuotaFile> {
+    let mut content = String::new();
+    file.read_to_string(&mut content)?;
+    if content.trim().is_empty() {
+        return Ok(QuotaFile::default());
+    }
+    serde_json::from_str(&content).map_err(|err| SkError::from(err).at("quota"))
+}
+
+fn write(file: &mut File, quotas: &QuotaFile) -> SkResult<()> {
+    let content = serde_json::to_vec(quotas).map_err(io::Error::from)?;
+    file.seek(SeekFrom::Start(0))?;
+    file.set_len(0)?;
+    file.write_all(&content)?;
+    file.sync_data()?;
+    Ok(())
+}
+
+/// FNV-1a digest of an API key, in hexadecimal
+fn digest(api_key: &str) -> String {
+    let hash = api_key
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3));
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    fn quota_path(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("songkick-quota-{}-{}.json", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn spent_budget() {
+        let path = quota_path("spent");
+        // 2026-10-14T12:00:00Z
+        let noon = UNIX_EPOCH + Duration::from_secs(20_740 * SECONDS_PER_DAY + 43_200);
+        let quota = DailyQuota::new("KEY", 2, path.clone());
+        quota.spend_at(noon).unwrap();
+
+        // Another client of the same key shares the budget
+        let other = DailyQuota::new("KEY", 2, path.clone());
+        other.spend_at(noon).unwrap();
+        match quota.spend_at(noon) {
+            Err(SkError::QuotaExhausted { resets_at }) => {
+                assert_eq!(UNIX_EPOCH + Duration::from_secs(20_741 * SECONDS_PER_DAY), resets_at)
+            }
+            result => panic!("expected QuotaExhausted, got {:?}", result),
+        }
+        DailyQuota::new("OTHER KEY", 2, path.clone()).spend_at(noon).unwrap();
+
+        let written: QuotaFile = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(2, written.keys.len());
+        assert_eq!(Usage { day: String::from("2026-10-14"), used: 2 }, written.keys[&digest("KEY")]);
+        assert!(!fs::read_to_string(&path).unwrap().contains("KEY"));
+
+        // Renewed the next day
+        quota.spend_at(noon + Duration::from_secs(SECONDS_PER_DAY)).unwrap();
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn unreadable_file() {
+        let path = quota_path("unreadable");
+        fs::write(&path, "not json").unwrap();
+        let err = DailyQuota::new("KEY", 2, path.clone()).spend().unwrap_err();
+        assert!(matches!(err, SkError::Decode { .. }), "{}", err);
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,19 +1,36 @@
 use crate::error::SkError;
 use crate::resources::identifier::Identifier;
-use crate::resources::Resource;
-use crate::util::json::{get_str, get_u64};
+use crate::resources::ids::ArtistId;
+use crate::resources::{Resource, TopLevelResource};
+#[cfg(feature = "url-types")]
+use crate::resources::uri;
+use crate::util::json::{get_arr, get_opt_str, get_str, get_u64, extra_fields, nested_fields, unknown_fields, AtPath};
 use crate::SkResult;
+use serde::Serialize;
 use serde_json::Value;
+use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 
 /// Represent a SongKick Artist Resource
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct Artist {
     /// Display name
     pub display_name: String,
     pub uri: String,
     /// ID
-    pub id: u64,
+    pub id: ArtistId,
+    #[serde(rename = "identifier")]
     pub identifiers: Vec<Identifier>,
+    /// Date until the Artist is on tour, if any
+    pub on_tour_until: Option<String>,
+    /// Fields not modelled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 impl Resource for Artist {
@@ -24,41 +41,94 @@ impl Resource for Artist {
 
                 let uri = get_str(obj, "uri")?;
 
-                let id = get_u64(obj, "id")?;
+                let id = ArtistId(get_u64(obj, "id")?);
 
                 let mut identifiers = Vec::new();
 
-                if let Some(arr) = obj.get("identifier") {
-                    for a in arr.as_array().unwrap() {
-                        let identi = Identifier::from_json(&a)?;
+                if obj.contains_key("identifier") {
+                    for (i, a) in get_arr(obj, "identifier")?.iter().enumerate() {
+                        let identi = Identifier::from_json(a).at(&format!("identifier[{}]", i))?;
                         identifiers.push(identi);
                     }
                 }
 
+                let on_tour_until = get_opt_str(obj, "onTourUntil")?;
+
                 let artist = Artist {
-                    id: id,
-                    uri: uri,
-                    display_name: display_name,
-                    identifiers: identifiers,
+                    id,
+                    uri,
+                    display_name,
+                    identifiers,
+                    on_tour_until,
+                    extra: extra_fields(obj, &["displayName", "uri", "id", "identifier", "onTourUntil"]),
                 };
                 Ok(artist)
             }
-            None => Err(SkError::JsonError(format!(
-                "Expected source json to be an object {}",
-                &source
-            ))),
+            None => Err(SkError::decode(format!("Expected source json to be an object {}", source))),
+        }
+    }
+    fn unknown_fields(&self) -> Vec<String> {
+        let mut fields = unknown_fields(&self.extra);
+        for (i, identifier) in self.identifiers.iter().enumerate() {
+            fields.extend(nested_fields(&format!("identifier[{}]", i), identifier.unknown_fields()));
         }
+        fields
     }
+}
+
+impl TopLevelResource for Artist {
     fn marker() -> &'static str {
         "artist"
     }
 }
 
+#[cfg(feature = "url-types")]
+impl Artist {
+    /// `uri` parsed into an URL
+    pub fn url(&self) -> Result<uri::Url, uri::ParseError> {
+        uri::Url::parse(&self.uri)
+    }
+}
+
+impl fmt::Display for Artist {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
+
+/// Artists are identified by their ID
+impl PartialEq for Artist {
+    fn eq(&self, other: &Artist) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Artist {}
+
+impl Hash for Artist {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Artists are ordered by ID, as they are compared
+impl Ord for Artist {
+    fn cmp(&self, other: &Artist) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl PartialOrd for Artist {
+    fn partial_cmp(&self, other: &Artist) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 #[allow(unused_imports)]
 #[allow(dead_code)]
-
 mod tests {
     use crate::resources::artist::Artist;
+    use crate::resources::ids::ArtistId;
     use crate::resources::Resource;
     use serde_json;
     use serde_json::Value;
@@ -85,10 +155,44 @@ mod tests {
             "http://www.songkick.com/artists/253846-radiohead?utm_source=24619&utm_medium=partner",
             artist.uri
         );
-        assert_eq!(253846, artist.id);
+        assert_eq!(ArtistId(253846), artist.id);
         assert_eq!(
             "a74b1b7f-71a5-4011-9441-d0b5e4122711",
+            artist.identifiers[0].mbid.to_string()
+        );
+    }
+
+    #[test]
+    fn test_artist_eq_ord() {
+        let artist = load_artist("fixtures/artist/artist-253846.json");
+        let mut data = serde_json::to_value(&artist).unwrap();
+        data["displayName"] = Value::from("AAA Renamed");
+        let renamed = Artist::from_json(&data).unwrap();
+        data["id"] = Value::from(1);
+        data["displayName"] = Value::from("Zzz");
+        let other = Artist::from_json(&data).unwrap();
+
+        assert!(artist == renamed);
+        assert_eq!(std::cmp::Ordering::Equal, artist.cmp(&renamed));
+        let mut artists = [renamed, other];
+        artists.sort();
+        assert_eq!(ArtistId(1), artists[0].id);
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_artist_identifier_uuid() {
+        use uuid::Uuid;
+
+        let artist = load_artist("fixtures/artist/artist-253846.json");
+
+        assert_eq!(
+            Uuid::parse_str("a74b1b7f-71a5-4011-9441-d0b5e4122711").unwrap(),
             artist.identifiers[0].mbid
         );
+        assert_eq!(
+            "http://api.songkick.com/api/3.0/artists/mbid:a74b1b7f-71a5-4011-9441-d0b5e4122711.json",
+            artist.identifiers[0].href
+        );
     }
 }
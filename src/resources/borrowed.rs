@@ -0,0 +1,295 @@
+//! Resources borrowing their strings from the body of the response
+//!
+//! [`decode_borrowed`] decodes a page without copying the names, URIs and dates of the
+//! resources: they are [`Cow::Borrowed`] slices of the body, unless their JSON has escape
+//! sequences. This cuts most of the allocations of bulk decoding, at the cost of fewer
+//! fields than the owned resources and of values tied to the lifetime of the body.
+//!
+//! ```rust
+//! use songkick::resources::borrowed::{decode_borrowed, EventRef};
+//!
+//! let body = std::fs::read_to_string("fixtures/event/artist-324967-calendar.json").unwrap();
+//! let page = decode_borrowed::<EventRef>(&body).unwrap();
+//! for event in &page.results {
+//!     println!("{} {}", event.start.date.as_deref().unwrap_or("?"), event.display_name);
+//! }
+//! ```
+
+use crate::error::SkError;
+use crate::resources::ids::{ArtistId, EventId, MetroAreaId, VenueId};
+use crate::SkResult;
+use serde::{Deserialize, Deserializer};
+use serde_json::value::RawValue;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Resource decoded by [`decode_borrowed`]
+pub trait BorrowedResource<'a>: Deserialize<'a> {
+    /// Key of the resource in the results of a page
+    fn marker() -> &'static str;
+}
+
+/// Optional string borrowed from the body when possible
+///
+/// `Option<Cow<str>>` fields deserialize to owned strings, the wrapper borrows them.
+fn borrow_opt<'de: 'a, 'a, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Cow<'a, str>>, D::Error> {
+    #[derive(Deserialize)]
+    struct Borrowed<'a>(#[serde(borrow)] Cow<'a, str>);
+
+    Ok(Option::<Borrowed>::deserialize(deserializer)?.map(|Borrowed(value)| value))
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct CountryRef<'a> {
+    #[serde(borrow)]
+    pub display_name: Cow<'a, str>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct MetroAreaRef<'a> {
+    pub id: MetroAreaId,
+    #[serde(borrow)]
+    pub display_name: Cow<'a, str>,
+    #[serde(borrow)]
+    pub uri: Cow<'a, str>,
+    #[serde(borrow)]
+    pub country: CountryRef<'a>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct VenueRef<'a> {
+    pub id: Option<VenueId>,
+    #[serde(borrow, default, deserialize_with = "borrow_opt")]
+    pub display_name: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "borrow_opt")]
+    pub uri: Option<Cow<'a, str>>,
+    pub lat: Option<f64>,
+    pub lng: Option<f64>,
+    #[serde(borrow, default)]
+    pub metro_area: Option<MetroAreaRef<'a>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ArtistRef<'a> {
+    pub id: ArtistId,
+    #[serde(borrow)]
+    pub display_name: Cow<'a, str>,
+    #[serde(borrow)]
+    pub uri: Cow<'a, str>,
+    #[serde(borrow, default, deserialize_with = "borrow_opt")]
+    pub on_tour_until: Option<Cow<'a, str>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct PerformanceRef<'a> {
+    pub id: u64,
+    #[serde(borrow)]
+    pub display_name: Cow<'a, str>,
+    #[serde(borrow)]
+    pub billing: Cow<'a, str>,
+    pub billing_index: u64,
+    #[serde(borrow)]
+    pub artist: ArtistRef<'a>,
+}
+
+/// Start or end of an [`EventRef`], as the raw strings of SongKick
+#[derive(Debug, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct WhenRef<'a> {
+    #[serde(borrow, default, deserialize_with = "borrow_opt")]
+    pub date: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "borrow_opt")]
+    pub time: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "borrow_opt")]
+    pub datetime: Option<Cow<'a, str>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct LocationRef<'a> {
+    #[serde(borrow)]
+    pub city: Cow<'a, str>,
+    pub lat: Option<f64>,
+    pub lng: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct EventRef<'a> {
+    pub id: EventId,
+    #[serde(rename = "type", borrow)]
+    pub event_type: Cow<'a, str>,
+    #[serde(borrow)]
+    pub display_name: Cow<'a, str>,
+    #[serde(borrow)]
+    pub status: Cow<'a, str>,
+    #[serde(borrow)]
+    pub uri: Cow<'a, str>,
+    pub popularity: f64,
+    #[serde(borrow, default, deserialize_with = "borrow_opt")]
+    pub age_restriction: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    pub start: WhenRef<'a>,
+    #[serde(borrow, default)]
+    pub end: Option<WhenRef<'a>>,
+    #[serde(borrow, default)]
+    pub location: Option<LocationRef<'a>>,
+    #[serde(borrow)]
+    pub venue: VenueRef<'a>,
+    #[serde(rename = "performance", borrow)]
+    pub performances: Vec<PerformanceRef<'a>>,
+}
+
+impl<'a> BorrowedResource<'a> for ArtistRef<'a> {
+    fn marker() -> &'static str {
+        "artist"
+    }
+}
+
+impl<'a> BorrowedResource<'a> for EventRef<'a> {
+    fn marker() -> &'static str {
+        "event"
+    }
+}
+
+impl<'a> BorrowedResource<'a> for VenueRef<'a> {
+    fn marker() -> &'static str {
+        "venue"
+    }
+}
+
+/// Page of resources decoded by [`decode_borrowed`]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BorrowedPage<T> {
+    pub status: String,
+    pub page: u64,
+    pub per_page: u64,
+    pub total_entries: u64,
+    pub results: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct Envelope<'a> {
+    #[serde(rename = "resultsPage", borrow)]
+    page: RawPage<'a>,
+}
+
+#[derive(Deserialize)]
+struct ApiError {
+    message: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPage<'a> {
+    status: String,
+    page: Option<u64>,
+    per_page: Option<u64>,
+    total_entries: Option<u64>,
+    error: Option<ApiError>,
+    /// Results left undecoded until their marker is known
+    #[serde(borrow, default)]
+    results: HashMap<&'a str, &'a RawValue>,
+}
+
+/// Decode a page of the API, the strings of its resources borrowed from `body`
+pub fn decode_borrowed<'a, T: BorrowedResource<'a>>(body: &'a str) -> SkResult<BorrowedPage<T>> {
+    let envelope: Envelope = serde_json::from_str(body)?;
+    let page = envelope.page;
+    if page.status == "error" {
+        let message = page.error.and_then(|error| error.message);
+        return Err(SkError::Api {
+            message: message.unwrap_or_else(|| String::from("unknown error")),
+        });
+    }
+
+    let results = match page.results.get(T::marker()) {
+        Some(raw) => {
+            let json = raw.get();
+            let decoded = if json.starts_with('[') {
+                serde_json::from_str(json)
+            } else if json.starts_with('{') {
+                serde_json::from_str(json).map(|result| vec![result])
+            } else {
+                Ok(Vec::new())
+            };
+            decoded.map_err(|err| SkError::from(err).at(&format!("resultsPage.results.{}", T::marker())))?
+        }
+        None => Vec::new(),
+    };
+
+    Ok(BorrowedPage {
+        status: page.status,
+        page: page.page.unwrap_or(1),
+        per_page: page.per_page.unwrap_or(50),
+        total_entries: page.total_entries.unwrap_or(1),
+        results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::event::Event;
+    use crate::result::SkResultSet;
+    use serde_json::Value;
+    use std::fs;
+
+    #[test]
+    fn borrow_calendar() {
+        let body = fs::read_to_string("fixtures/event/artist-324967-calendar.json").unwrap();
+        let page = decode_borrowed::<EventRef>(&body).unwrap();
+        let data: Value = serde_json::from_str(&body).unwrap();
+        let events: Vec<Event> = SkResultSet::from_json(&data).unwrap().collect();
+
+        assert_eq!(33, page.total_entries);
+        assert_eq!(events.len(), page.results.len());
+        for (borrowed, owned) in page.results.iter().zip(&events) {
+            assert_eq!(owned.id, borrowed.id);
+            assert_eq!(owned.display_name, borrowed.display_name);
+            assert_eq!(owned.venue.id, borrowed.venue.id);
+            assert_eq!(owned.performances.len(), borrowed.performances.len());
+            assert!(matches!(borrowed.display_name, Cow::Borrowed(_)));
+            assert!(matches!(borrowed.start.date, Some(Cow::Borrowed(_))));
+        }
+    }
+
+    #[test]
+    fn escaped_strings_are_owned() {
+        let body = r#"{"resultsPage": {"status": "ok", "results": {"artist": {"id": 1,
+            "displayName": "Sigur R\u00f3s", "uri": "http://www.songkick.com/artists/1"}}}}"#;
+        let page = decode_borrowed::<ArtistRef>(body).unwrap();
+        let artist = &page.results[0];
+        assert_eq!("Sigur Rós", artist.display_name);
+        assert!(matches!(artist.display_name, Cow::Owned(_)));
+        assert!(matches!(artist.uri, Cow::Borrowed(_)));
+        assert_eq!(None, artist.on_tour_until);
+    }
+
+    #[test]
+    fn borrowed_errors() {
+        let body = fs::read_to_string("fixtures/error/invalid-key.json").unwrap();
+        assert!(matches!(decode_borrowed::<EventRef>(&body), Err(SkError::Api { .. })));
+
+        let body = r#"{"resultsPage": {"status": "ok", "results": {"event": [{"id": "1"}]}}}"#;
+        match decode_borrowed::<EventRef>(body) {
+            Err(SkError::Decode { ref path, .. }) => assert_eq!("resultsPage.results.event", path),
+            _ => panic!("expected a Decode error"),
+        }
+
+        let body = fs::read_to_string("fixtures/empty_search.json").unwrap();
+        assert!(decode_borrowed::<ArtistRef>(&body).unwrap().results.is_empty());
+    }
+}
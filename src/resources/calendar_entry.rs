@@ -0,0 +1,123 @@
+use crate::error::SkError;
+use crate::resources::artist::Artist;
+use crate::resources::event::Event;
+use crate::resources::{Resource, TopLevelResource};
+use crate::util::json::{extra_fields, get_arr, get_opt_str, get_value, nested_fields, unknown_fields, AtPath};
+use crate::SkResult;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Why an Event is in the calendar of a user
+#[derive(Clone, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Reason {
+    /// Artists of the Event tracked by the user
+    pub tracked_artist: Vec<Artist>,
+    /// Attendance of the user (e.g. `i_might_go`, `im_going`)
+    pub attendance: Option<String>,
+    /// Fields not modelled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// Entry of the calendar of a user
+#[derive(Clone, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct CalendarEntry {
+    pub reason: Reason,
+    pub event: Event,
+    /// Fields not modelled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl Resource for Reason {
+    fn from_json(source: &Value) -> SkResult<Reason> where Self: Sized {
+        match source.as_object() {
+            Some(obj) => {
+                let mut tracked_artist = Vec::new();
+                if obj.contains_key("trackedArtist") {
+                    for (i, a) in get_arr(obj, "trackedArtist")?.iter().enumerate() {
+                        tracked_artist.push(Artist::from_json(a).at(&format!("trackedArtist[{}]", i))?);
+                    }
+                }
+                let attendance = get_opt_str(obj, "attendance")?;
+
+                Ok(Reason {
+                    tracked_artist,
+                    attendance,
+                    extra: extra_fields(obj, &["trackedArtist", "attendance"]),
+                })
+            }
+            None => Err(SkError::decode(format!("Expected source json to be an object {}", source))),
+        }
+    }
+
+    fn unknown_fields(&self) -> Vec<String> {
+        let mut fields = unknown_fields(&self.extra);
+        for (i, artist) in self.tracked_artist.iter().enumerate() {
+            fields.extend(nested_fields(&format!("trackedArtist[{}]", i), artist.unknown_fields()));
+        }
+        fields
+    }
+}
+
+impl Resource for CalendarEntry {
+    fn from_json(source: &Value) -> SkResult<CalendarEntry> where Self: Sized {
+        match source.as_object() {
+            Some(obj) => {
+                let reason = Reason::from_json(get_value(obj, "reason")?).at("reason")?;
+                let event = Event::from_json(get_value(obj, "event")?).at("event")?;
+
+                Ok(CalendarEntry {
+                    reason,
+                    event,
+                    extra: extra_fields(obj, &["reason", "event"]),
+                })
+            }
+            None => Err(SkError::decode(format!("Expected source json to be an object {}", source))),
+        }
+    }
+
+    fn unknown_fields(&self) -> Vec<String> {
+        let mut fields = unknown_fields(&self.extra);
+        fields.extend(nested_fields("reason", self.reason.unknown_fields()));
+        fields.extend(nested_fields("event", self.event.unknown_fields()));
+        fields
+    }
+}
+
+impl TopLevelResource for CalendarEntry {
+    fn marker() -> &'static str {
+        "calendarEntry"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::SkResultSet;
+    use std::fs::File;
+    use std::io::Read;
+
+    #[test]
+    fn test_user_calendar_json() {
+        let mut file = File::open("fixtures/user/user-calendar-placebo-fan.json").unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+        let data: Value = serde_json::from_str(&content).unwrap();
+
+        let entries: Vec<CalendarEntry> = SkResultSet::from_json(&data).unwrap().collect();
+        assert_eq!(3, entries.len());
+        assert_eq!("Placebo", entries[0].reason.tracked_artist[0].display_name);
+        assert_eq!(None, entries[0].reason.attendance);
+        assert!(entries[2].reason.tracked_artist.is_empty());
+        assert_eq!(Some(String::from("i_might_go")), entries[2].reason.attendance);
+        assert!(entries.iter().all(|entry| entry.unknown_fields().is_empty()));
+    }
+}
@@ -1,11 +1,28 @@
 use crate::error::SkError;
 use crate::resources::Resource;
+use serde::Serialize;
 use serde_json::Value;
-use crate::util::json::get_str;
+use std::collections::HashMap;
+use crate::util::json::{extra_fields, get_str, unknown_fields};
 use crate::SkResult;
 
+#[derive(Clone, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct Country {
     pub display_name: String,
+    /// Fields not modelled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl Country {
+    /// ISO 3166-1 alpha-2 code of the Country, if its display name is known
+    #[cfg(feature = "country-codes")]
+    pub fn iso_code(&self) -> Option<&'static str> {
+        crate::resources::country_codes::iso_alpha2(&self.display_name)
+    }
 }
 
 impl Resource for Country {
@@ -17,17 +34,40 @@ impl Resource for Country {
             Some(obj) => {
                 let display_name = get_str(obj, "displayName")?;
                 Ok(Country {
-                    display_name: display_name,
+                    display_name,
+                    extra: extra_fields(obj, &["displayName"]),
                 })
             }
-            None => Err(SkError::JsonError(format!(
-                "Expected source json to be an object {}",
-                &source
-            ))),
+            None => Err(SkError::decode(format!("Expected source json to be an object {}", source))),
         }
     }
 
-    fn marker() -> &'static str {
-        unimplemented!()
+    fn unknown_fields(&self) -> Vec<String> {
+        unknown_fields(&self.extra)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_country() {
+        let country = Country::from_json(&json!({"displayName": "UK"})).unwrap();
+        assert_eq!("UK", country.display_name);
+        assert!(country.unknown_fields().is_empty());
+    }
+
+    #[cfg(feature = "country-codes")]
+    #[test]
+    fn iso_code_from_display_name() {
+        let code = |name: &str| Country::from_json(&json!({ "displayName": name })).unwrap().iso_code();
+        assert_eq!(Some("GB"), code("UK"));
+        assert_eq!(Some("US"), code("US"));
+        assert_eq!(Some("FR"), code("France"));
+        assert_eq!(Some("RU"), code("Russian Federation"));
+        assert_eq!(Some("CZ"), code("Czech Republic"));
+        assert_eq!(None, code("Atlantis"));
     }
 }
@@ -0,0 +1,239 @@
+//! Mapping from SongKick country display names to ISO 3166-1 alpha-2 codes.
+
+/// Display names as returned by SongKick, paired with their alpha-2 code.
+/// Some countries appear under more than one name (e.g. `UK` and `United Kingdom`).
+const COUNTRY_CODES: &[(&str, &str)] = &[
+    ("Afghanistan", "AF"),
+    ("Åland Islands", "AX"),
+    ("Albania", "AL"),
+    ("Algeria", "DZ"),
+    ("American Samoa", "AS"),
+    ("Andorra", "AD"),
+    ("Angola", "AO"),
+    ("Anguilla", "AI"),
+    ("Antarctica", "AQ"),
+    ("Antigua and Barbuda", "AG"),
+    ("Argentina", "AR"),
+    ("Armenia", "AM"),
+    ("Aruba", "AW"),
+    ("Australia", "AU"),
+    ("Austria", "AT"),
+    ("Azerbaijan", "AZ"),
+    ("Bahamas", "BS"),
+    ("Bahrain", "BH"),
+    ("Bangladesh", "BD"),
+    ("Barbados", "BB"),
+    ("Belarus", "BY"),
+    ("Belgium", "BE"),
+    ("Belize", "BZ"),
+    ("Benin", "BJ"),
+    ("Bermuda", "BM"),
+    ("Bhutan", "BT"),
+    ("Bolivia", "BO"),
+    ("Bosnia and Herzegovina", "BA"),
+    ("Botswana", "BW"),
+    ("Brazil", "BR"),
+    ("Brunei Darussalam", "BN"),
+    ("Brunei", "BN"),
+    ("Bulgaria", "BG"),
+    ("Burkina Faso", "BF"),
+    ("Burundi", "BI"),
+    ("Cambodia", "KH"),
+    ("Cameroon", "CM"),
+    ("Canada", "CA"),
+    ("Cape Verde", "CV"),
+    ("Cayman Islands", "KY"),
+    ("Central African Republic", "CF"),
+    ("Chad", "TD"),
+    ("Chile", "CL"),
+    ("China", "CN"),
+    ("Colombia", "CO"),
+    ("Comoros", "KM"),
+    ("Congo", "CG"),
+    ("Democratic Republic of the Congo", "CD"),
+    ("Costa Rica", "CR"),
+    ("Côte d'Ivoire", "CI"),
+    ("Cote d'Ivoire", "CI"),
+    ("Croatia", "HR"),
+    ("Cuba", "CU"),
+    ("Curaçao", "CW"),
+    ("Cyprus", "CY"),
+    ("Czech Republic", "CZ"),
+    ("Czechia", "CZ"),
+    ("Denmark", "DK"),
+    ("Djibouti", "DJ"),
+    ("Dominica", "DM"),
+    ("Dominican Republic", "DO"),
+    ("Ecuador", "EC"),
+    ("Egypt", "EG"),
+    ("El Salvador", "SV"),
+    ("Equatorial Guinea", "GQ"),
+    ("Eritrea", "ER"),
+    ("Estonia", "EE"),
+    ("Ethiopia", "ET"),
+    ("Faroe Islands", "FO"),
+    ("Fiji", "FJ"),
+    ("Finland", "FI"),
+    ("France", "FR"),
+    ("French Guiana", "GF"),
+    ("French Polynesia", "PF"),
+    ("Gabon", "GA"),
+    ("Gambia", "GM"),
+    ("Georgia", "GE"),
+    ("Germany", "DE"),
+    ("Ghana", "GH"),
+    ("Gibraltar", "GI"),
+    ("Greece", "GR"),
+    ("Greenland", "GL"),
+    ("Grenada", "GD"),
+    ("Guadeloupe", "GP"),
+    ("Guam", "GU"),
+    ("Guatemala", "GT"),
+    ("Guernsey", "GG"),
+    ("Guinea", "GN"),
+    ("Guinea-Bissau", "GW"),
+    ("Guyana", "GY"),
+    ("Haiti", "HT"),
+    ("Honduras", "HN"),
+    ("Hong Kong", "HK"),
+    ("Hungary", "HU"),
+    ("Iceland", "IS"),
+    ("India", "IN"),
+    ("Indonesia", "ID"),
+    ("Iran", "IR"),
+    ("Iraq", "IQ"),
+    ("Ireland", "IE"),
+    ("Isle of Man", "IM"),
+    ("Israel", "IL"),
+    ("Italy", "IT"),
+    ("Jamaica", "JM"),
+    ("Japan", "JP"),
+    ("Jersey", "JE"),
+    ("Jordan", "JO"),
+    ("Kazakhstan", "KZ"),
+    ("Kenya", "KE"),
+    ("South Korea", "KR"),
+    ("Korea, Republic of", "KR"),
+    ("North Korea", "KP"),
+    ("Kosovo", "XK"),
+    ("Kuwait", "KW"),
+    ("Kyrgyzstan", "KG"),
+    ("Laos", "LA"),
+    ("Latvia", "LV"),
+    ("Lebanon", "LB"),
+    ("Lesotho", "LS"),
+    ("Liberia", "LR"),
+    ("Libya", "LY"),
+    ("Liechtenstein", "LI"),
+    ("Lithuania", "LT"),
+    ("Luxembourg", "LU"),
+    ("Macau", "MO"),
+    ("Macedonia", "MK"),
+    ("North Macedonia", "MK"),
+    ("Madagascar", "MG"),
+    ("Malawi", "MW"),
+    ("Malaysia", "MY"),
+    ("Maldives", "MV"),
+    ("Mali", "ML"),
+    ("Malta", "MT"),
+    ("Martinique", "MQ"),
+    ("Mauritania", "MR"),
+    ("Mauritius", "MU"),
+    ("Mexico", "MX"),
+    ("Moldova", "MD"),
+    ("Monaco", "MC"),
+    ("Mongolia", "MN"),
+    ("Montenegro", "ME"),
+    ("Morocco", "MA"),
+    ("Mozambique", "MZ"),
+    ("Myanmar", "MM"),
+    ("Namibia", "NA"),
+    ("Nepal", "NP"),
+    ("Netherlands", "NL"),
+    ("New Caledonia", "NC"),
+    ("New Zealand", "NZ"),
+    ("Nicaragua", "NI"),
+    ("Niger", "NE"),
+    ("Nigeria", "NG"),
+    ("Norway", "NO"),
+    ("Oman", "OM"),
+    ("Pakistan", "PK"),
+    ("Palestine", "PS"),
+    ("Panama", "PA"),
+    ("Papua New Guinea", "PG"),
+    ("Paraguay", "PY"),
+    ("Peru", "PE"),
+    ("Philippines", "PH"),
+    ("Poland", "PL"),
+    ("Portugal", "PT"),
+    ("Puerto Rico", "PR"),
+    ("Qatar", "QA"),
+    ("Réunion", "RE"),
+    ("Romania", "RO"),
+    ("Russian Federation", "RU"),
+    ("Russia", "RU"),
+    ("Rwanda", "RW"),
+    ("Saint Kitts and Nevis", "KN"),
+    ("Saint Lucia", "LC"),
+    ("Saint Vincent and the Grenadines", "VC"),
+    ("Samoa", "WS"),
+    ("San Marino", "SM"),
+    ("Saudi Arabia", "SA"),
+    ("Senegal", "SN"),
+    ("Serbia", "RS"),
+    ("Seychelles", "SC"),
+    ("Sierra Leone", "SL"),
+    ("Singapore", "SG"),
+    ("Slovakia", "SK"),
+    ("Slovenia", "SI"),
+    ("Solomon Islands", "SB"),
+    ("Somalia", "SO"),
+    ("South Africa", "ZA"),
+    ("South Sudan", "SS"),
+    ("Spain", "ES"),
+    ("Sri Lanka", "LK"),
+    ("Sudan", "SD"),
+    ("Suriname", "SR"),
+    ("Swaziland", "SZ"),
+    ("Eswatini", "SZ"),
+    ("Sweden", "SE"),
+    ("Switzerland", "CH"),
+    ("Syria", "SY"),
+    ("Taiwan", "TW"),
+    ("Tajikistan", "TJ"),
+    ("Tanzania", "TZ"),
+    ("Thailand", "TH"),
+    ("Timor-Leste", "TL"),
+    ("Togo", "TG"),
+    ("Tonga", "TO"),
+    ("Trinidad and Tobago", "TT"),
+    ("Tunisia", "TN"),
+    ("Turkey", "TR"),
+    ("Turkmenistan", "TM"),
+    ("Uganda", "UG"),
+    ("Ukraine", "UA"),
+    ("United Arab Emirates", "AE"),
+    ("UK", "GB"),
+    ("United Kingdom", "GB"),
+    ("US", "US"),
+    ("United States", "US"),
+    ("Uruguay", "UY"),
+    ("Uzbekistan", "UZ"),
+    ("Vanuatu", "VU"),
+    ("Vatican City", "VA"),
+    ("Venezuela", "VE"),
+    ("Vietnam", "VN"),
+    ("Virgin Islands, U.S.", "VI"),
+    ("Virgin Islands, British", "VG"),
+    ("Yemen", "YE"),
+    ("Zambia", "ZM"),
+    ("Zimbabwe", "ZW"),
+];
+
+/// Look up the ISO 3166-1 alpha-2 code for a country display name
+pub(crate) fn iso_alpha2(display_name: &str) -> Option<&'static str> {
+    COUNTRY_CODES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(display_name))
+        .map(|(_, code)| *code)
+}
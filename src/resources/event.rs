@@ -1,26 +1,52 @@
 use crate::SkResult;
-use crate::resources::Resource;
+use crate::resources::{Resource, TopLevelResource};
+#[cfg(feature = "url-types")]
+use crate::resources::uri;
+use serde::Serialize;
 use serde_json::Value;
+use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use url::Url;
 use crate::error::SkError;
 use crate::resources::venue::Venue;
 use crate::resources::artist::Artist;
-use crate::util::json::{get_str, get_u64, get_f64, get_arr};
-
-#[derive(Debug, PartialEq)]
-
-
+use crate::resources::ids::EventId;
+use crate::util::json::{get_str, get_u64, get_f64, get_arr, get_bool, get_opt_str, get_value, extra_fields, nested_fields, unknown_fields, AtPath};
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+
+/// Start or end of an Event.
+///
+/// The fields hold the raw strings returned by SongKick, the `chrono` feature adds
+/// `datetime_parsed`, `time_parsed` and `date_parsed` to read them as chrono types.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct When {
     pub datetime: Option<String>,
     pub time: Option<String>,
-    pub date: Option<String>
+    pub date: Option<String>,
+    /// Fields not modelled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
+#[derive(Clone, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct Performance {
     pub billing: String,
     pub billing_index: u64,
     pub id: u64,
     pub display_name: String,
     pub artist: Artist,
+    /// Fields not modelled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 impl Resource for Performance {
@@ -31,24 +57,25 @@ impl Resource for Performance {
                 let billing = get_str(obj, "billing")?;
                 let id = get_u64(obj, "id")?;
                 let billing_index = get_u64(obj, "billingIndex")?;
-                let artist = obj.get("artist").unwrap();
-
-                let artist = Artist::from_json(&artist)?;
+                let artist = Artist::from_json(get_value(obj, "artist")?).at("artist")?;
 
                 Ok(Performance {
-                    id: id,
-                    display_name: display_name,
-                    billing: billing,
-                    billing_index: billing_index,
-                    artist: artist
+                    id,
+                    display_name,
+                    billing,
+                    billing_index,
+                    artist,
+                    extra: extra_fields(obj, &["displayName", "billing", "id", "billingIndex", "artist"]),
                 })
             },
-            None => Err(SkError::JsonError(format!("Expected source json to be an object {}", &source)))
+            None => Err(SkError::decode(format!("Expected source json to be an object {}", source)))
         }
     }
 
-    fn marker() -> &'static str {
-        unimplemented!()
+    fn unknown_fields(&self) -> Vec<String> {
+        let mut fields = unknown_fields(&self.extra);
+        fields.extend(nested_fields("artist", self.artist.unknown_fields()));
+        fields
     }
 }
 
@@ -90,32 +117,204 @@ impl Resource for When {
 
 
                 Ok(When {
-                    date: date,
-                    datetime: datetime,
-                    time: time,
+                    date,
+                    datetime,
+                    time,
+                    extra: extra_fields(obj, &["datetime", "time", "date"]),
                 })
             }
-            None => Err(SkError::JsonError(format!("Expected source json to be an object {}", &source)))
+            None => Err(SkError::decode(format!("Expected source json to be an object {}", source)))
         }
     }
 
-    fn marker() -> &'static str {
-        unimplemented!()
+    fn unknown_fields(&self) -> Vec<String> {
+        unknown_fields(&self.extra)
+    }
+}
+
+/// Point in time of an Event, as precise as SongKick knows it
+#[cfg(feature = "chrono")]
+#[derive(Debug, PartialEq)]
+pub enum EventTime<Tz: TimeZone> {
+    /// Full timestamp, converted to the requested time zone
+    DateTime(DateTime<Tz>),
+    /// Date and time are known but SongKick did not provide an offset
+    Floating(NaiveDateTime),
+    /// Only the date is known (`time` and `datetime` are null)
+    Date(NaiveDate),
+}
+
+/// Span of an Event, from its first to its last day
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    /// First day of the Event
+    pub start_date: NaiveDate,
+    /// Last day of the Event, the same as `start_date` for single-day events
+    pub end_date: NaiveDate,
+    /// Exact start, when known
+    pub start: Option<DateTime<FixedOffset>>,
+    /// Exact end, when known
+    pub end: Option<DateTime<FixedOffset>>,
+}
+
+#[cfg(feature = "chrono")]
+impl Interval {
+    /// Number of calendar days covered, both ends included
+    pub fn days(&self) -> i64 {
+        (self.end_date - self.start_date).num_days() + 1
+    }
+
+    /// Whether the Event spans more than one day (e.g. festivals)
+    pub fn is_multi_day(&self) -> bool {
+        self.end_date > self.start_date
+    }
+
+    /// Whether the Event takes place on `date`
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start_date <= date && date <= self.end_date
+    }
+
+    /// Exact duration, when both start and end times are known
+    pub fn duration(&self) -> Option<Duration> {
+        match (self.start, self.end) {
+            (Some(start), Some(end)) => Some(end - start),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl When {
+    /// `datetime` as a timestamp, `None` when missing or malformed
+    pub fn datetime_parsed(&self) -> Option<DateTime<FixedOffset>> {
+        let datetime = self.datetime.as_ref()?;
+        // SongKick sends offsets without a colon (e.g. `+0200`)
+        DateTime::parse_from_str(datetime, "%Y-%m-%dT%H:%M:%S%z")
+            .or_else(|_| DateTime::parse_from_rfc3339(datetime))
+            .ok()
+    }
+
+    /// `time` as a local time of day, `None` when missing or malformed
+    pub fn time_parsed(&self) -> Option<NaiveTime> {
+        NaiveTime::parse_from_str(self.time.as_ref()?, "%H:%M:%S").ok()
+    }
+
+    /// `date` as a calendar date, `None` when missing or malformed
+    pub fn date_parsed(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(self.date.as_ref()?, "%Y-%m-%d").ok()
+    }
+
+    /// Resolve this `When` and convert it to the given time zone
+    ///
+    /// Returns `None` when SongKick did not provide any date.
+    pub fn in_timezone<Tz: TimeZone>(&self, tz: &Tz) -> Option<EventTime<Tz>> {
+        match (self.datetime_parsed(), self.date_parsed(), self.time_parsed()) {
+            (Some(datetime), _, _) => Some(EventTime::DateTime(datetime.with_timezone(tz))),
+            (None, Some(date), Some(time)) => Some(EventTime::Floating(date.and_time(time))),
+            (None, Some(date), None) => Some(EventTime::Date(date)),
+            (None, None, _) => None,
+        }
+    }
+}
+
+/// Location of an Event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct Location {
+    pub city: String,
+    pub lat: Option<f64>,
+    pub lng: Option<f64>,
+    /// Fields not modelled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl Resource for Location {
+    fn from_json(source: &Value) -> SkResult<Location> where Self: Sized {
+        match source.as_object() {
+            Some(obj) => {
+                let city = get_str(obj, "city")?;
+                let lat = obj.get("lat").and_then(|val| val.as_f64());
+                let lng = obj.get("lng").and_then(|val| val.as_f64());
+
+                Ok(Location {
+                    city,
+                    lat,
+                    lng,
+                    extra: extra_fields(obj, &["city", "lat", "lng"]),
+                })
+            }
+            None => Err(SkError::decode(format!("Expected source json to be an object {}", source)))
+        }
+    }
+
+    fn unknown_fields(&self) -> Vec<String> {
+        unknown_fields(&self.extra)
+    }
+}
+
+/// Series a festival Event belongs to
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Series {
+    pub display_name: String,
+    /// Fields not modelled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl Resource for Series {
+    fn from_json(source: &Value) -> SkResult<Series> where Self: Sized {
+        match source.as_object() {
+            Some(obj) => {
+                let display_name = get_str(obj, "displayName")?;
+
+                Ok(Series {
+                    display_name,
+                    extra: extra_fields(obj, &["displayName"]),
+                })
+            }
+            None => Err(SkError::decode(format!("Expected source json to be an object {}", source)))
+        }
+    }
+
+    fn unknown_fields(&self) -> Vec<String> {
+        unknown_fields(&self.extra)
     }
 }
 
 // Event Resource
+#[derive(Clone, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct Event {
-    pub id: u64,
+    pub id: EventId,
+    #[serde(rename = "type")]
     pub event_type: String,
     pub display_name: String,
     pub status: String,
     pub uri: String,
+    /// Popularity score, useful to rank search results
     pub popularity: f64,
+    /// Age restriction (e.g. `18+`), if any
+    pub age_restriction: Option<String>,
+    /// Whether the Event has been flagged as ended
+    pub flagged_as_ended: bool,
     pub venue: Venue,
     pub start: When,
     pub end: Option<When>,
-    pub performances: Vec<Performance>
+    pub location: Option<Location>,
+    pub series: Option<Series>,
+    #[serde(rename = "performance")]
+    pub performances: Vec<Performance>,
+    /// Fields not modelled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 
@@ -123,7 +322,7 @@ impl Resource for Event {
     fn from_json(source: &Value) -> SkResult<Self> where Self: Sized {
         match source.as_object() {
             Some(obj) => {
-                let id = get_u64(obj, "id")?;
+                let id = EventId(get_u64(obj, "id")?);
                 let display_name = get_str(obj, "displayName")?;
 
                 let uri = get_str(obj, "uri")?;
@@ -131,51 +330,225 @@ impl Resource for Event {
 
                 let status = get_str(obj, "status")?;
                 let popularity = get_f64(obj, "popularity")?;
+                let age_restriction = get_opt_str(obj, "ageRestriction")?;
+                let flagged_as_ended = match obj.get("flaggedAsEnded") {
+                    Some(_) => get_bool(obj, "flaggedAsEnded")?,
+                    None => false
+                };
 
 
-                let start = obj.get("start").unwrap();
-                let start = When::from_json(&start)?;
+                let start = When::from_json(get_value(obj, "start")?).at("start")?;
 
                 let mut end = None;
-                if let Some(ref e) = obj.get("end") {
-                    end = Some(When::from_json(&e)?);
+                if let Some(e) = obj.get("end").filter(|e| !e.is_null()) {
+                    end = Some(When::from_json(e).at("end")?);
                 }
 
-                let venue = obj.get("venue").unwrap();
-                let venue = Venue::from_json(&venue)?;
+                let location = match obj.get("location").filter(|l| !l.is_null()) {
+                    Some(l) => Some(Location::from_json(l).at("location")?),
+                    None => None
+                };
+
+                let series = match obj.get("series").filter(|s| !s.is_null()) {
+                    Some(s) => Some(Series::from_json(s).at("series")?),
+                    None => None
+                };
+
+                let venue = Venue::from_json(get_value(obj, "venue")?).at("venue")?;
 
                 let mut performances = Vec::new();
                 let performance = get_arr(obj, "performance")?;
 
-                for p in performance {
-                    let model = Performance::from_json(&p)?;
+                for (i, p) in performance.iter().enumerate() {
+                    let model = Performance::from_json(p).at(&format!("performance[{}]", i))?;
                     performances.push(model);
                 }
 
                 Ok(Event {
-                    id: id,
-                    event_type: event_type,
-                    display_name: display_name,
-                    status: status,
-                    uri: uri,
-                    popularity: popularity,
-                    venue: venue,
-                    start: start,
-                    end: end,
-                    performances: performances
+                    id,
+                    event_type,
+                    display_name,
+                    status,
+                    uri,
+                    popularity,
+                    age_restriction,
+                    flagged_as_ended,
+                    venue,
+                    start,
+                    end,
+                    location,
+                    series,
+                    performances,
+                    extra: extra_fields(obj, &["id", "displayName", "uri", "type", "status", "popularity", "ageRestriction", "flaggedAsEnded", "start", "end", "location", "series", "venue", "performance"]),
                 })
             },
-            None => Err(SkError::JsonError(format!("Expected source json to be an object {}", &source)))
+            None => Err(SkError::decode(format!("Expected source json to be an object {}", source)))
+        }
+    }
+
+    fn unknown_fields(&self) -> Vec<String> {
+        let mut fields = unknown_fields(&self.extra);
+        fields.extend(nested_fields("start", self.start.unknown_fields()));
+        if let Some(ref end) = self.end {
+            fields.extend(nested_fields("end", end.unknown_fields()));
+        }
+        if let Some(ref location) = self.location {
+            fields.extend(nested_fields("location", location.unknown_fields()));
+        }
+        if let Some(ref series) = self.series {
+            fields.extend(nested_fields("series", series.unknown_fields()));
         }
+        fields.extend(nested_fields("venue", self.venue.unknown_fields()));
+        for (i, performance) in self.performances.iter().enumerate() {
+            fields.extend(nested_fields(&format!("performance[{}]", i), performance.unknown_fields()));
+        }
+        fields
     }
+}
 
+impl TopLevelResource for Event {
     fn marker() -> &'static str {
         "event"
     }
 }
 
+#[cfg(feature = "chrono")]
+impl Event {
+    /// Start of the Event in the venue local time zone, as reported by SongKick
+    pub fn start_local(&self) -> Option<EventTime<FixedOffset>> {
+        let offset = self
+            .start
+            .datetime_parsed()
+            .map_or_else(|| FixedOffset::east_opt(0).unwrap(), |datetime| *datetime.offset());
+        self.start_in(&offset)
+    }
+
+    /// Start of the Event in UTC
+    pub fn start_utc(&self) -> Option<EventTime<Utc>> {
+        self.start_in(&Utc)
+    }
+
+    /// Span of the Event, `None` when SongKick did not provide a start date
+    ///
+    /// Events without an end are considered to last their start day.
+    pub fn interval(&self) -> Option<Interval> {
+        let start_date = self.start.date_parsed()?;
+        let end = self.end.as_ref();
+        let end_date = end
+            .and_then(When::date_parsed)
+            .filter(|end_date| *end_date >= start_date)
+            .unwrap_or(start_date);
+
+        Some(Interval {
+            start_date,
+            end_date,
+            start: self.start.datetime_parsed(),
+            end: end.and_then(When::datetime_parsed),
+        })
+    }
+
+    /// Start of the Event converted to the given time zone
+    pub fn start_in<Tz: TimeZone>(&self, tz: &Tz) -> Option<EventTime<Tz>> {
+        self.start.in_timezone(tz)
+    }
+}
+
+#[cfg(feature = "url-types")]
+impl Event {
+    /// `uri` parsed into an URL
+    pub fn url(&self) -> Result<uri::Url, uri::ParseError> {
+        uri::Url::parse(&self.uri)
+    }
+}
+
+impl fmt::Display for Event {
+    /// One-line summary, e.g. `Radiohead at O2 Arena, London — 2024-06-06`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.performances.first() {
+            Some(headliner) => write!(f, "{} at {}", headliner.display_name, self.venue)?,
+            None => write!(f, "{}", self.display_name)?,
+        }
+        if let Some(ref date) = self.start.date {
+            write!(f, " — {}", date)?;
+        }
+        Ok(())
+    }
+}
+
+impl Event {
+    /// Key of the chronological order: start date, start time, then ID, the undated
+    /// events first
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "testing")]
+    /// # {
+    /// use songkick::resources::Event;
+    /// use songkick::testing::fixtures;
+    ///
+    /// let mut events: Vec<Event> = fixtures::ARTIST_GIGOGRAPHY.load().unwrap().collect();
+    /// events.sort_by_key(Event::start_key);
+    /// # }
+    /// ```
+    pub fn start_key(&self) -> (Option<String>, Option<String>, EventId) {
+        (self.start.date.clone(), self.start.time.clone(), self.id)
+    }
+
+    /// Public songkick.com page of the Event, without the partner tracking parameters
+    pub fn page_url(&self) -> Option<String> {
+        let mut url = Url::parse(&self.uri).ok()?;
+        url.set_query(None);
+        url.set_fragment(None);
+        url.set_scheme("https").ok()?;
+        Some(url.into_string())
+    }
+
+    /// Short form of `page_url` with the slug removed
+    /// (e.g. `https://www.songkick.com/concerts/26486224`)
+    pub fn short_url(&self) -> Option<String> {
+        let mut url = Url::parse(&self.page_url()?).ok()?;
+        let mut segments: Vec<String> = url.path_segments()?.map(String::from).collect();
+        let slug = segments.pop()?;
+        let id: String = slug.chars().take_while(|c| c.is_ascii_digit()).collect();
+        if id.is_empty() {
+            return None;
+        }
+        segments.push(id);
+        url.set_path(&segments.join("/"));
+        Some(url.into_string())
+    }
+}
+
+/// Events are identified by their ID
+impl PartialEq for Event {
+    fn eq(&self, other: &Event) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Event {}
 
+impl Hash for Event {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
 
+/// Events of the same ID are equal, the others are ordered by [`start_key`](Event::start_key):
+/// start date and time, then ID
+impl Ord for Event {
+    fn cmp(&self, other: &Event) -> Ordering {
+        if self.id == other.id {
+            return Ordering::Equal;
+        }
+        self.start_key().cmp(&other.start_key())
+    }
+}
+
+impl PartialOrd for Event {
+    fn partial_cmp(&self, other: &Event) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -183,6 +556,8 @@ mod tests {
     use std::io::Read;
     use crate::resources::event::Event;
     use crate::resources::event::Performance;
+    use crate::resources::venue::Venue;
+    use crate::resources::ids::{EventId, MetroAreaId, VenueId};
     use crate::resources::{Resource};
     use serde_json::Value;
     use serde_json;
@@ -206,17 +581,19 @@ mod tests {
 
         assert_eq!("Placebo with The Mirror Trap at Sentrum Scene (October 16, 2016)", event.display_name);
         assert_eq!("http://www.songkick.com/concerts/26486224-placebo-at-sentrum-scene?utm_source=24619&utm_medium=partner", event.uri);
-        assert_eq!(26486224, event.id);
+        assert_eq!(EventId(26486224), event.id);
         assert_eq!("ok", event.status);
         assert_eq!("Concert", event.event_type);
 
         assert_eq!(0.156595, event.popularity);
+        assert_eq!(None, event.age_restriction);
+        assert!(!event.flagged_as_ended);
 
 
         assert_eq!(Some(String::from("2016-10-16T19:00:00+0000")), event.start.datetime);
         // Assert Venue
         assert_eq!(Some(String::from("Sentrum Scene")), event.venue.display_name);
-        assert_eq!(Some(33495), event.venue.id);
+        assert_eq!(Some(VenueId(33495)), event.venue.id);
         assert_eq!(Some(59.9155184), event.venue.lat);
         assert_eq!(Some(10.7518051), event.venue.lng);
         assert_eq!(Some(String::from("http://www.songkick.com/venues/33495-sentrum-scene?utm_source=24619&utm_medium=partner")), event.venue.uri);
@@ -225,7 +602,7 @@ mod tests {
 
         let metro = event.venue.metro_area.unwrap();
         assert_eq!("Oslo", metro.display_name);
-        assert_eq!(31422, metro.id);
+        assert_eq!(MetroAreaId(31422), metro.id);
         assert_eq!("http://www.songkick.com/metro_areas/31422-norway-oslo?utm_source=24619&utm_medium=partner", metro.uri);
         assert_eq!("Norway", metro.country.display_name);
 
@@ -233,7 +610,7 @@ mod tests {
 
         assert_eq!(2, event.performances.len());
 
-        let ref performance : Performance = event.performances[0];
+        let performance: &Performance = &event.performances[0];
         assert_eq!(51721634, performance.id);
         assert_eq!("Placebo", performance.display_name);
         assert_eq!("headline", performance.billing);
@@ -243,4 +620,296 @@ mod tests {
         assert_eq!("support", event.performances[1].billing);
         assert_eq!(2, event.performances[1].billing_index);
     }
-}
\ No newline at end of file
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_event_chrono_when() {
+        use chrono::{DateTime, NaiveDate, NaiveTime};
+
+        let event = load_event("fixtures/event/single-event-artist-324967.json");
+
+        assert_eq!(Some(DateTime::parse_from_rfc3339("2016-10-16T19:00:00+00:00").unwrap()), event.start.datetime_parsed());
+        assert_eq!(NaiveDate::from_ymd_opt(2016, 10, 16), event.start.date_parsed());
+        assert_eq!(NaiveTime::from_hms_opt(19, 0, 0), event.start.time_parsed());
+
+        let mut malformed = event.start.clone();
+        malformed.date = Some(String::from("16/10/2016"));
+        assert_eq!(None, malformed.date_parsed());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_event_start_time_zones() {
+        use crate::resources::event::{EventTime, When};
+        use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
+
+        let event = load_event("fixtures/event/single-event-artist-324967.json");
+
+        assert_eq!(
+            Some(EventTime::DateTime(Utc.with_ymd_and_hms(2016, 10, 16, 19, 0, 0).unwrap())),
+            event.start_utc()
+        );
+
+        let cet = FixedOffset::east_opt(3600).unwrap();
+        assert_eq!(
+            Some(EventTime::DateTime(cet.with_ymd_and_hms(2016, 10, 16, 20, 0, 0).unwrap())),
+            event.start_in(&cet)
+        );
+
+        let date_only = When {
+            datetime: None,
+            time: None,
+            date: Some(String::from("2016-10-29")),
+            ..Default::default()
+        };
+        assert_eq!(
+            Some(EventTime::Date(NaiveDate::from_ymd_opt(2016, 10, 29).unwrap())),
+            date_only.in_timezone(&Utc)
+        );
+
+        let floating = When {
+            datetime: None,
+            time: Some(String::from("21:00:00")),
+            date: Some(String::from("2016-10-29")),
+            ..Default::default()
+        };
+        assert_eq!(
+            Some(EventTime::Floating(NaiveDate::from_ymd_opt(2016, 10, 29).unwrap().and_hms_opt(21, 0, 0).unwrap())),
+            floating.in_timezone(&Utc)
+        );
+
+        let unknown = When::default();
+        assert_eq!(None, unknown.in_timezone(&Utc));
+    }
+
+    #[test]
+    fn test_event_age_restriction_and_flagged_as_ended() {
+        let sample_str = {
+            let mut file = File::open("fixtures/event/single-event-artist-324967.json").unwrap();
+            let mut ret = String::new();
+            file.read_to_string(&mut ret).unwrap();
+            ret
+        };
+        let mut data: Value = serde_json::from_str(&sample_str).unwrap();
+        let obj = data.as_object_mut().unwrap();
+        obj.insert(String::from("ageRestriction"), Value::from("18+"));
+        obj.insert(String::from("flaggedAsEnded"), Value::from(true));
+
+        let event = Event::from_json(&data).unwrap();
+
+        assert_eq!(Some(String::from("18+")), event.age_restriction);
+        assert!(event.flagged_as_ended);
+    }
+
+    #[test]
+    fn test_event_serialize_round_trip() {
+        let event = load_event("fixtures/event/single-event-artist-324967.json");
+
+        let serialized = serde_json::to_value(&event).unwrap();
+        let obj = serialized.as_object().unwrap();
+
+        assert_eq!(Some(&Value::from("Concert")), obj.get("type"));
+        assert_eq!(Some(&Value::from(26486224)), obj.get("id"));
+        assert!(obj.contains_key("displayName"));
+        assert!(obj.get("venue").unwrap().get("metroArea").is_some());
+        assert_eq!(2, obj.get("performance").unwrap().as_array().unwrap().len());
+
+        let reloaded = Event::from_json(&serialized).unwrap();
+
+        assert_eq!(serialized, serde_json::to_value(&reloaded).unwrap());
+    }
+
+    #[test]
+    fn test_event_extra_fields() {
+        let event = load_event("fixtures/event/single-event-artist-324967.json");
+
+        assert!(event.extra.is_empty());
+        assert!(event.venue.extra.is_empty());
+
+        let mut data = serde_json::to_value(&event).unwrap();
+        data.as_object_mut().unwrap().insert(String::from("newField"), Value::from(42));
+
+        let event = Event::from_json(&data).unwrap();
+        assert_eq!(Some(&Value::from(42)), event.extra.get("newField"));
+        assert!(!event.extra.contains_key("displayName"));
+
+        let serialized = serde_json::to_value(&event).unwrap();
+        assert_eq!(Some(&Value::from(42)), serialized.get("newField"));
+    }
+
+    #[test]
+    fn test_event_display() {
+        let event = load_event("fixtures/event/single-event-artist-324967.json");
+
+        assert_eq!("Placebo at Sentrum Scene, Oslo — 2016-10-16", event.to_string());
+        assert_eq!("Sentrum Scene, Oslo", event.venue.to_string());
+        assert_eq!("Placebo", event.performances[0].artist.to_string());
+    }
+
+    #[test]
+    fn test_event_eq_hash_ord() {
+        use std::collections::HashSet;
+
+        let event = load_event("fixtures/event/single-event-artist-324967.json");
+        let mut data = serde_json::to_value(&event).unwrap();
+        data["displayName"] = Value::from("Renamed");
+        let same = Event::from_json(&data).unwrap();
+        data["id"] = Value::from(2);
+        data["start"]["date"] = Value::from("2016-10-15");
+        let earlier = Event::from_json(&data).unwrap();
+
+        assert!(event == same);
+        assert!(event != earlier);
+
+        let set: HashSet<&Event> = vec![&event, &same, &earlier].into_iter().collect();
+        assert_eq!(2, set.len());
+
+        // Same start, ties broken by ID
+        data["id"] = Value::from(1);
+        data["start"]["date"] = serde_json::to_value(event.start.date.as_ref()).unwrap();
+        let same_day = Event::from_json(&data).unwrap();
+        assert_eq!(std::cmp::Ordering::Less, same_day.cmp(&event));
+
+        // Same ID, equal whatever the start
+        data["start"]["date"] = Value::from("2016-10-17");
+        let moved = Event::from_json(&data).unwrap();
+        assert!(moved == same_day);
+        assert_eq!(std::cmp::Ordering::Equal, moved.cmp(&same_day));
+
+        let id = event.id;
+        let mut events = [event, same_day, earlier];
+        events.sort();
+        assert_eq!(vec![EventId(2), EventId(1), id], events.iter().map(|event| event.id).collect::<Vec<_>>());
+        events.reverse();
+        events.sort_by_key(Event::start_key);
+        assert_eq!(EventId(2), events[0].id);
+    }
+
+    #[test]
+    fn test_venue_eq_hash_ord() {
+        use std::collections::HashSet;
+
+        let venue = load_event("fixtures/event/single-event-artist-324967.json").venue;
+        let mut data = serde_json::to_value(&venue).unwrap();
+        data["displayName"] = Value::from("Stale name");
+        let renamed = Venue::from_json(&data).unwrap();
+        data["id"] = Value::Null;
+        let unknown = Venue::from_json(&data).unwrap();
+        data["displayName"] = Value::from("Other venue");
+        let other_unknown = Venue::from_json(&data).unwrap();
+        data["displayName"] = Value::from("Stale name");
+        data["metroArea"]["id"] = Value::from(1);
+        let other_metro_area = Venue::from_json(&data).unwrap();
+
+        assert!(venue == renamed);
+        assert!(unknown == Venue::from_json(&serde_json::to_value(&unknown).unwrap()).unwrap());
+        assert!(unknown != other_unknown);
+        assert!(unknown != other_metro_area);
+        let set: HashSet<&Venue> = vec![&venue, &renamed, &unknown, &other_unknown, &other_metro_area].into_iter().collect();
+        assert_eq!(4, set.len());
+        let mut venues = [venue, unknown];
+        venues.sort();
+        assert_eq!(None, venues[0].id);
+    }
+
+    #[cfg(feature = "geo")]
+    #[test]
+    fn test_venue_point() {
+        use geo_types::Point;
+
+        let event = load_event("fixtures/event/single-event-artist-324967.json");
+
+        assert_eq!(Some(Point::new(10.7518051, 59.9155184)), event.venue.point());
+        assert_eq!(None, event.venue.metro_area.unwrap().point());
+    }
+
+    #[test]
+    fn test_venue_distance_to() {
+        let event = load_event("fixtures/event/single-event-artist-324967.json");
+
+        // Cirkus, Stockholm
+        let distance = event.venue.distance_to(59.3245767, 18.0996982).unwrap();
+        assert!((distance - 418_174.0).abs() < 1.0, "{}", distance);
+        assert_eq!(Some(0.0), event.venue.distance_to(59.9155184, 10.7518051));
+
+        assert_eq!(None, event.venue.metro_area.unwrap().distance_to(59.3245767, 18.0996982));
+    }
+
+    #[test]
+    fn test_event_page_urls() {
+        let event = load_event("fixtures/event/single-event-artist-324967.json");
+
+        assert_eq!(
+            Some(String::from("https://www.songkick.com/concerts/26486224-placebo-at-sentrum-scene")),
+            event.page_url()
+        );
+        assert_eq!(
+            Some(String::from("https://www.songkick.com/concerts/26486224")),
+            event.short_url()
+        );
+
+        let mut data = serde_json::to_value(&event).unwrap();
+        data["uri"] = Value::from("http://www.songkick.com/festivals/287608/id/27081999-pitchfork-music-festival-paris-2016?utm_source=24619&utm_medium=partner");
+        let festival = Event::from_json(&data).unwrap();
+
+        assert_eq!(
+            Some(String::from("https://www.songkick.com/festivals/287608/id/27081999")),
+            festival.short_url()
+        );
+    }
+
+    #[cfg(feature = "url-types")]
+    #[test]
+    fn test_event_url() {
+        let event = load_event("fixtures/event/single-event-artist-324967.json");
+
+        assert_eq!("www.songkick.com", event.url().unwrap().host_str().unwrap());
+        assert_eq!(Some("www.songkick.com"), event.venue.url().unwrap().unwrap().host_str());
+
+        let mut data = serde_json::to_value(&event).unwrap();
+        data["uri"] = Value::from("not an uri");
+        let malformed = Event::from_json(&data).unwrap();
+
+        assert_eq!("not an uri", malformed.uri);
+        assert!(malformed.url().is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_event_interval() {
+        use chrono::{Duration, NaiveDate};
+
+        let event = load_event("fixtures/event/single-event-artist-324967.json");
+        let interval = event.interval().unwrap();
+
+        assert_eq!(NaiveDate::from_ymd_opt(2016, 10, 16).unwrap(), interval.start_date);
+        assert_eq!(interval.start_date, interval.end_date);
+        assert_eq!(1, interval.days());
+        assert!(!interval.is_multi_day());
+        assert_eq!(None, interval.duration());
+
+        let mut data = serde_json::to_value(&event).unwrap();
+        data["end"] = serde_json::json!({"date": "2016-10-18", "time": "01:00:00", "datetime": "2016-10-18T01:00:00+0000"});
+        let festival = Event::from_json(&data).unwrap();
+        let interval = festival.interval().unwrap();
+
+        assert_eq!(3, interval.days());
+        assert!(interval.is_multi_day());
+        assert!(interval.contains(NaiveDate::from_ymd_opt(2016, 10, 17).unwrap()));
+        assert!(!interval.contains(NaiveDate::from_ymd_opt(2016, 10, 19).unwrap()));
+        assert_eq!(Some(Duration::hours(30)), interval.duration());
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_event_json_schema() {
+        let event = load_event("fixtures/event/single-event-artist-324967.json");
+        let schema = serde_json::to_value(schemars::schema_for!(Event)).unwrap();
+        let properties = schema["properties"].as_object().unwrap();
+
+        for field in serde_json::to_value(&event).unwrap().as_object().unwrap().keys() {
+            assert!(properties.contains_key(field), "{} missing from the schema", field);
+        }
+        assert!(schema["required"].as_array().unwrap().contains(&Value::from("displayName")));
+    }
+}
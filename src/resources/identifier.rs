@@ -1,15 +1,32 @@
 use crate::error::SkError;
-use crate::resources::Resource;
-use crate::util::json::get_str;
+use crate::resources::{Resource, TopLevelResource};
+use crate::util::json::{get_opt_str, get_str, extra_fields, unknown_fields};
 use crate::SkResult;
+use serde::Serialize;
 use serde_json::Value;
+use std::collections::HashMap;
 
-#[derive(Debug, PartialEq)]
+#[cfg(feature = "uuid")]
+use uuid::Uuid;
+
+/// MusicBrainz identifier of an Artist
+///
+/// With the `uuid` feature enabled `mbid` is parsed into a `Uuid`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct Identifier {
     pub href: String,
     pub events_href: Option<String>,
     pub setlists_href: Option<String>,
+    #[cfg(not(feature = "uuid"))]
     pub mbid: String,
+    #[cfg(feature = "uuid")]
+    pub mbid: Uuid,
+    /// Fields not modelled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 impl Resource for Identifier {
@@ -18,31 +35,40 @@ impl Resource for Identifier {
             Some(obj) => {
                 let href = get_str(obj, "href")?;
 
-                let mut events_href = None;
-                if let Some(ref evt) = obj.get("eventsHref") {
-                    events_href = Some(String::from(evt.as_str().unwrap()));
-                }
-                let mut setlists_href = None;
-                if let Some(ref set) = obj.get("setlistsHref") {
-                    setlists_href = Some(String::from(set.as_str().unwrap()));
-                }
+                let events_href = get_opt_str(obj, "eventsHref")?;
+                let setlists_href = get_opt_str(obj, "setlistsHref")?;
 
-                let mbid = get_str(obj, "mbid")?;
+                let mbid = parse_mbid(get_str(obj, "mbid")?)?;
 
                 Ok(Identifier {
-                    mbid: mbid,
-                    events_href: events_href,
-                    setlists_href: setlists_href,
-                    href: href,
+                    mbid,
+                    events_href,
+                    setlists_href,
+                    href,
+                    extra: extra_fields(obj, &["href", "eventsHref", "setlistsHref", "mbid"]),
                 })
             }
-            None => Err(SkError::JsonError(format!(
-                "Expected source json to be an object {}",
-                &source
-            ))),
+            None => Err(SkError::decode(format!("Expected source json to be an object {}", source))),
         }
     }
+    fn unknown_fields(&self) -> Vec<String> {
+        unknown_fields(&self.extra)
+    }
+}
+
+impl TopLevelResource for Identifier {
     fn marker() -> &'static str {
         "identifier"
     }
 }
+
+#[cfg(not(feature = "uuid"))]
+fn parse_mbid(raw: String) -> SkResult<String> {
+    Ok(raw)
+}
+
+#[cfg(feature = "uuid")]
+fn parse_mbid(raw: String) -> SkResult<Uuid> {
+    Uuid::parse_str(&raw)
+        .map_err(|err| SkError::decode_with(format!("invalid mbid {}", raw), err).at("mbid"))
+}
@@ -0,0 +1,170 @@
+//! Typed IDs for SongKick resources
+//!
+//! Every ID is a distinct type, so an artist ID can't be passed where an
+//! event or venue ID is expected.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use url::Url;
+
+macro_rules! sk_id {
+    ($(#[$doc:meta])* $name:ident) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+        #[serde(transparent)]
+        pub struct $name(pub u64);
+
+        impl From<u64> for $name {
+            fn from(id: u64) -> $name {
+                $name(id)
+            }
+        }
+
+        impl From<$name> for u64 {
+            fn from(id: $name) -> u64 {
+                id.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    };
+}
+
+sk_id!(
+    /// ID of an Artist
+    ArtistId
+);
+sk_id!(
+    /// ID of an Event
+    EventId
+);
+sk_id!(
+    /// ID of a Venue
+    VenueId
+);
+sk_id!(
+    /// ID of a Metro Area
+    MetroAreaId
+);
+
+impl ArtistId {
+    /// Extract the ID from a songkick.com artist page URL
+    ///
+    /// ```rust
+    /// use songkick::resources::ArtistId;
+    ///
+    /// let id = ArtistId::from_songkick_url("https://www.songkick.com/artists/253846-radiohead");
+    /// assert_eq!(Some(ArtistId(253846)), id);
+    /// ```
+    pub fn from_songkick_url(url: &str) -> Option<ArtistId> {
+        id_after(&path_segments(url)?, "artists").map(ArtistId)
+    }
+}
+
+impl EventId {
+    /// Extract the ID from a songkick.com concert or festival page URL
+    pub fn from_songkick_url(url: &str) -> Option<EventId> {
+        let segments = path_segments(url)?;
+        match segments.first().map(String::as_str) {
+            Some("concerts") => id_after(&segments, "concerts").map(EventId),
+            // e.g. /festivals/287608/id/27081999-pitchfork-music-festival-paris-2016
+            Some("festivals") => id_after(&segments, "id").map(EventId),
+            _ => None,
+        }
+    }
+}
+
+impl VenueId {
+    /// Extract the ID from a songkick.com venue page URL
+    pub fn from_songkick_url(url: &str) -> Option<VenueId> {
+        id_after(&path_segments(url)?, "venues").map(VenueId)
+    }
+}
+
+impl MetroAreaId {
+    /// Extract the ID from a songkick.com metro area page URL
+    pub fn from_songkick_url(url: &str) -> Option<MetroAreaId> {
+        let segments = path_segments(url)?;
+        id_after(&segments, "metro_areas")
+            .or_else(|| id_after(&segments, "metro-areas"))
+            .map(MetroAreaId)
+    }
+}
+
+/// Path segments of a songkick.com URL, the scheme being optional
+fn path_segments(url: &str) -> Option<Vec<String>> {
+    let parsed = Url::parse(url)
+        .or_else(|_| Url::parse(&format!("https://{}", url)))
+        .ok()?;
+    let host = parsed.host_str()?;
+    if host != "songkick.com" && !host.ends_with(".songkick.com") {
+        return None;
+    }
+    Some(parsed.path_segments()?.map(String::from).collect())
+}
+
+/// Numeric prefix of the segment following `keyword` (e.g. `253846-radiohead`)
+fn id_after(segments: &[String], keyword: &str) -> Option<u64> {
+    let position = segments.iter().position(|segment| segment == keyword)?;
+    let segment = segments.get(position + 1)?;
+    let digits: String = segment.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resources::ids::{ArtistId, EventId, MetroAreaId, VenueId};
+
+    #[test]
+    fn artist_from_songkick_url() {
+        assert_eq!(
+            Some(ArtistId(253846)),
+            ArtistId::from_songkick_url("https://www.songkick.com/artists/253846-radiohead")
+        );
+        assert_eq!(
+            Some(ArtistId(324967)),
+            ArtistId::from_songkick_url("http://www.songkick.com/artists/324967-placebo?utm_source=24619&utm_medium=partner")
+        );
+        assert_eq!(
+            Some(ArtistId(253846)),
+            ArtistId::from_songkick_url("www.songkick.com/artists/253846-radiohead/calendar")
+        );
+        assert_eq!(None, ArtistId::from_songkick_url("https://www.songkick.com/venues/33495-sentrum-scene"));
+        assert_eq!(None, ArtistId::from_songkick_url("https://example.com/artists/253846-radiohead"));
+        assert_eq!(None, ArtistId::from_songkick_url("not an url"));
+    }
+
+    #[test]
+    fn event_from_songkick_url() {
+        assert_eq!(
+            Some(EventId(26486224)),
+            EventId::from_songkick_url("http://www.songkick.com/concerts/26486224-placebo-at-sentrum-scene")
+        );
+        assert_eq!(
+            Some(EventId(27081999)),
+            EventId::from_songkick_url("http://www.songkick.com/festivals/287608/id/27081999-pitchfork-music-festival-paris-2016")
+        );
+        assert_eq!(None, EventId::from_songkick_url("https://www.songkick.com/artists/253846-radiohead"));
+    }
+
+    #[test]
+    fn venue_and_metro_area_from_songkick_url() {
+        assert_eq!(
+            Some(VenueId(33495)),
+            VenueId::from_songkick_url("http://www.songkick.com/venues/33495-sentrum-scene")
+        );
+        assert_eq!(
+            Some(MetroAreaId(31422)),
+            MetroAreaId::from_songkick_url("http://www.songkick.com/metro_areas/31422-norway-oslo")
+        );
+        assert_eq!(
+            Some(MetroAreaId(24426)),
+            MetroAreaId::from_songkick_url("https://www.songkick.com/metro-areas/24426-uk-london")
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use crate::error::SkError;
+use crate::resources::country::Country;
+use crate::resources::metro_area::MetroArea;
+use crate::resources::{Resource, TopLevelResource};
+use crate::util::json::{extra_fields, get_str, get_value, nested_fields, unknown_fields, AtPath};
+use crate::SkResult;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// City matched by a location search
+#[derive(Clone, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct City {
+    pub display_name: String,
+    pub country: Country,
+    pub lat: Option<f64>,
+    pub lng: Option<f64>,
+    /// Fields not modelled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// Result of a [location search](https://www.songkick.com/developer/location-search), a city and its Metro Area
+#[derive(Clone, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct LocationMatch {
+    pub city: City,
+    pub metro_area: MetroArea,
+    /// Fields not modelled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl Resource for City {
+    fn from_json(source: &Value) -> SkResult<City> where Self: Sized {
+        match source.as_object() {
+            Some(obj) => {
+                let display_name = get_str(obj, "displayName")?;
+                let country = Country::from_json(get_value(obj, "country")?).at("country")?;
+
+                Ok(City {
+                    display_name,
+                    country,
+                    lat: obj.get("lat").and_then(Value::as_f64),
+                    lng: obj.get("lng").and_then(Value::as_f64),
+                    extra: extra_fields(obj, &["displayName", "country", "lat", "lng"]),
+                })
+            }
+            None => Err(SkError::decode(format!("Expected source json to be an object {}", source))),
+        }
+    }
+
+    fn unknown_fields(&self) -> Vec<String> {
+        let mut fields = unknown_fields(&self.extra);
+        fields.extend(nested_fields("country", self.country.unknown_fields()));
+        fields
+    }
+}
+
+impl Resource for LocationMatch {
+    fn from_json(source: &Value) -> SkResult<LocationMatch> where Self: Sized {
+        match source.as_object() {
+            Some(obj) => {
+                let city = City::from_json(get_value(obj, "city")?).at("city")?;
+                let metro_area = MetroArea::from_json(get_value(obj, "metroArea")?).at("metroArea")?;
+
+                Ok(LocationMatch {
+                    city,
+                    metro_area,
+                    extra: extra_fields(obj, &["city", "metroArea"]),
+                })
+            }
+            None => Err(SkError::decode(format!("Expected source json to be an object {}", source))),
+        }
+    }
+
+    fn unknown_fields(&self) -> Vec<String> {
+        let mut fields = unknown_fields(&self.extra);
+        fields.extend(nested_fields("city", self.city.unknown_fields()));
+        fields.extend(nested_fields("metroArea", self.metro_area.unknown_fields()));
+        fields
+    }
+}
+
+impl TopLevelResource for LocationMatch {
+    fn marker() -> &'static str {
+        "location"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::ids::MetroAreaId;
+    use crate::result::SkResultSet;
+    use std::fs::File;
+    use std::io::Read;
+
+    #[test]
+    fn test_location_search_json() {
+        let mut file = File::open("fixtures/location/location-search-clientip.json").unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+        let data: Value = serde_json::from_str(&content).unwrap();
+
+        let locations: Vec<LocationMatch> = SkResultSet::from_json(&data).unwrap().collect();
+        assert_eq!(2, locations.len());
+        assert_eq!("London", locations[0].city.display_name);
+        assert_eq!("UK", locations[0].city.country.display_name);
+        assert_eq!("Wandsworth", locations[1].city.display_name);
+        assert_eq!(MetroAreaId(24426), locations[1].metro_area.id);
+        assert_eq!(Some(51.5078), locations[1].metro_area.lat);
+        assert!(locations.iter().all(|location| location.unknown_fields().is_empty()));
+    }
+}
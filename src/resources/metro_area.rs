@@ -1,15 +1,34 @@
 use crate::SkResult;
-use crate::resources::Resource;
+use crate::resources::{Resource, TopLevelResource};
+use serde::Serialize;
 use serde_json::Value;
+use std::collections::HashMap;
+#[cfg(feature = "geo")]
+use geo_types::Point;
 use crate::resources::country::Country;
+use crate::resources::ids::MetroAreaId;
 use crate::error::SkError;
-use crate::util::json::{get_str, get_u64};
+use crate::util::haversine;
+use crate::util::json::{get_str, get_u64, get_value, extra_fields, nested_fields, unknown_fields, AtPath};
 
+#[derive(Clone, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct MetroArea {
-    pub id: u64,
+    pub id: MetroAreaId,
     pub display_name: String,
     pub uri: String,
-    pub country: Country
+    pub country: Country,
+    /// Latitude, returned by location searches only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lat: Option<f64>,
+    /// Longitude, returned by location searches only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lng: Option<f64>,
+    /// Fields not modelled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 
@@ -18,26 +37,59 @@ impl Resource for MetroArea {
         match source.as_object() {
             Some(obj) => {
 
-                let id = get_u64(obj, "id")?;
+                let id = MetroAreaId(get_u64(obj, "id")?);
                 let display_name = get_str(obj, "displayName")?;
                 let uri = get_str(obj, "uri")?;
 
-                let country = obj.get("country").unwrap();
+                let country = Country::from_json(get_value(obj, "country")?).at("country")?;
 
-                let country = Country::from_json(&country)?;
+                let lat = obj.get("lat").and_then(|val| val.as_f64());
+                let lng = obj.get("lng").and_then(|val| val.as_f64());
 
                 Ok(MetroArea {
-                    id: id,
-                    display_name: display_name,
-                    uri: uri,
-                    country: country
+                    id,
+                    display_name,
+                    uri,
+                    country,
+                    lat,
+                    lng,
+                    extra: extra_fields(obj, &["id", "displayName", "uri", "country", "lat", "lng"]),
                 })
             }
-            None => Err(SkError::JsonError(format!("Expected source json to be an object {}", &source)))
+            None => Err(SkError::decode(format!("Expected source json to be an object {}", source)))
         }
     }
 
+    fn unknown_fields(&self) -> Vec<String> {
+        let mut fields = unknown_fields(&self.extra);
+        fields.extend(nested_fields("country", self.country.unknown_fields()));
+        fields
+    }
+}
+
+impl TopLevelResource for MetroArea {
     fn marker() -> &'static str {
-        unimplemented!()
+        "metroArea"
+    }
+}
+
+impl MetroArea {
+    /// Distance in meters from the Metro Area to the given coordinates, if its own are known
+    pub fn distance_to(&self, lat: f64, lng: f64) -> Option<f64> {
+        match (self.lat, self.lng) {
+            (Some(own_lat), Some(own_lng)) => Some(haversine(own_lat, own_lng, lat, lng)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "geo")]
+impl MetroArea {
+    /// Coordinates of the Metro Area, if known
+    pub fn point(&self) -> Option<Point<f64>> {
+        match (self.lng, self.lat) {
+            (Some(lng), Some(lat)) => Some(Point::new(lng, lat)),
+            _ => None,
+        }
     }
-}
\ No newline at end of file
+}
@@ -3,19 +3,39 @@ use serde_json::Value;
 use std::marker::Sized;
 
 pub mod artist;
+pub mod borrowed;
+pub mod calendar_entry;
 pub mod identifier;
+pub mod ids;
+pub mod location;
 pub mod event;
 pub mod venue;
 pub mod metro_area;
 pub mod country;
+#[cfg(feature = "country-codes")]
+mod country_codes;
+#[cfg(feature = "url-types")]
+pub mod uri;
 
 pub use crate::resources::artist::Artist as Artist;
+pub use crate::resources::calendar_entry::CalendarEntry as CalendarEntry;
 pub use crate::resources::event::Event as Event;
+pub use crate::resources::country::Country as Country;
+pub use crate::resources::location::LocationMatch as LocationMatch;
+pub use crate::resources::ids::{ArtistId, EventId, MetroAreaId, VenueId};
 
 pub trait Resource {
 
     fn from_json(source: &Value) -> SkResult<Self> where Self: Sized;
 
+    /// Paths of the JSON fields not modelled by the resource, nested resources included
+    fn unknown_fields(&self) -> Vec<String>;
+}
+
+/// Resource listed in the `results` of a page, the nested-only ones (e.g. `Country`) are not
+pub trait TopLevelResource: Resource {
+
+    /// Key of the resources in the `results` of a page, e.g. `event`
     fn marker() -> &'static str;
 }
 
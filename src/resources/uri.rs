@@ -0,0 +1,6 @@
+//! Parsed `uri` fields, with the `url-types` feature
+//!
+//! Resources keep the raw `uri` strings returned by SongKick, their `url()` accessors parse
+//! them into the `Url` of the url 2 crate.
+
+pub use url2::{ParseError, Url};
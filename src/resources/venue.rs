@@ -1,16 +1,56 @@
 use crate::SkResult;
-use crate::resources::Resource;
+use crate::resources::{Resource, TopLevelResource};
+#[cfg(feature = "url-types")]
+use crate::resources::uri;
 use crate::resources::metro_area::MetroArea;
+use crate::resources::ids::{MetroAreaId, VenueId};
+use serde::Serialize;
 use serde_json::Value;
+use std::collections::HashMap;
+#[cfg(feature = "geo")]
+use geo_types::Point;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use crate::error::SkError;
+use crate::util::haversine;
+use crate::util::json::{extra_fields, get_opt_str, get_u64, nested_fields, unknown_fields, AtPath};
 
+#[derive(Clone, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct Venue {
-    pub id: Option<u64>,
+    pub id: Option<VenueId>,
     pub display_name: Option<String>,
     pub uri: Option<String>,
     pub lat: Option<f64>,
     pub lng: Option<f64>,
-    pub metro_area: Option<MetroArea>
+    pub metro_area: Option<MetroArea>,
+    /// City of the Venue, only in the full venue representation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<MetroArea>,
+    /// Capacity, only in the full venue representation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capacity: Option<u64>,
+    /// Street address, only in the full venue representation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub street: Option<String>,
+    /// Postal code, only in the full venue representation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zip: Option<String>,
+    /// Phone number, only in the full venue representation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    /// Website, only in the full venue representation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
+    /// Description, only in the full venue representation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Fields not modelled by this crate yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 
@@ -20,7 +60,8 @@ impl Resource for Venue {
             Some(obj) => {
 
                 let id = obj.get("id")
-                    .and_then(|val| val.as_u64());
+                    .and_then(|val| val.as_u64())
+                    .map(VenueId);
 
                 let lat = obj.get("lat")
                     .and_then(|val| val.as_f64());
@@ -30,38 +71,150 @@ impl Resource for Venue {
 
 
                 let uri = obj.get("uri")
-                    .and_then(|val| val.as_str())
-                    .and_then(|str| Some(String::from(str)));
+                    .and_then(|val| val.as_str()).map(String::from);
 
                 let display_name = obj.get("displayName")
-                    .and_then(|val| val.as_str())
-                    .and_then(|str| Some(String::from(str)));
+                    .and_then(|val| val.as_str()).map(String::from);
 
 
 
-                let metro_area = match obj.get("metroArea") {
+                let metro_area = match obj.get("metroArea").filter(|m| !m.is_null()) {
                     Some(val) => {
-                        let m = MetroArea::from_json(&val)?;
+                        let m = MetroArea::from_json(val).at("metroArea")?;
                         Some(m)
                     }
                     None => None
                 };
 
+                let city = match obj.get("city").filter(|c| !c.is_null()) {
+                    Some(val) => Some(MetroArea::from_json(val).at("city")?),
+                    None => None
+                };
+
+                let capacity = match obj.get("capacity").filter(|c| !c.is_null()) {
+                    Some(_) => Some(get_u64(obj, "capacity")?),
+                    None => None
+                };
+                let street = get_opt_str(obj, "street")?;
+                let zip = get_opt_str(obj, "zip")?;
+                let phone = get_opt_str(obj, "phone")?;
+                let website = get_opt_str(obj, "website")?;
+                let description = get_opt_str(obj, "description")?;
 
                 Ok(Venue {
-                    id: id,
-                    display_name: display_name,
-                    uri: uri,
-                    lat: lat,
-                    lng: lng,
-                    metro_area: metro_area,
+                    id,
+                    display_name,
+                    uri,
+                    lat,
+                    lng,
+                    metro_area,
+                    city,
+                    capacity,
+                    street,
+                    zip,
+                    phone,
+                    website,
+                    description,
+                    extra: extra_fields(obj, &["id", "displayName", "uri", "lat", "lng", "metroArea", "city", "capacity", "street", "zip", "phone", "website", "description"]),
                 })
             }
-            None => Err(SkError::JsonError(format!("Expected source json to be an object {}", &source)))
+            None => Err(SkError::decode(format!("Expected source json to be an object {}", source)))
         }
     }
 
+    fn unknown_fields(&self) -> Vec<String> {
+        let mut fields = unknown_fields(&self.extra);
+        if let Some(ref metro_area) = self.metro_area {
+            fields.extend(nested_fields("metroArea", metro_area.unknown_fields()));
+        }
+        if let Some(ref city) = self.city {
+            fields.extend(nested_fields("city", city.unknown_fields()));
+        }
+        fields
+    }
+}
+
+impl TopLevelResource for Venue {
     fn marker() -> &'static str {
-        unimplemented!()
+        "venue"
+    }
+}
+
+impl Venue {
+    /// Distance in meters from the Venue to the given coordinates, if its own are known
+    pub fn distance_to(&self, lat: f64, lng: f64) -> Option<f64> {
+        match (self.lat, self.lng) {
+            (Some(own_lat), Some(own_lng)) => Some(haversine(own_lat, own_lng, lat, lng)),
+            _ => None,
+        }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(feature = "geo")]
+impl Venue {
+    /// Coordinates of the Venue, if known
+    pub fn point(&self) -> Option<Point<f64>> {
+        match (self.lng, self.lat) {
+            (Some(lng), Some(lat)) => Some(Point::new(lng, lat)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "url-types")]
+impl Venue {
+    /// `uri` parsed into an URL, `None` when SongKick did not provide one
+    pub fn url(&self) -> Option<Result<uri::Url, uri::ParseError>> {
+        self.uri.as_deref().map(uri::Url::parse)
+    }
+}
+
+impl fmt::Display for Venue {
+    /// Venue name followed by its metro area, e.g. `O2 Arena, London`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name.as_deref().unwrap_or("Unknown venue"))?;
+        if let Some(ref metro_area) = self.metro_area {
+            write!(f, ", {}", metro_area.display_name)?;
+        }
+        Ok(())
+    }
+}
+
+impl Venue {
+    /// ID of the Venue, or its name and metro area for the unknown venues, without ID
+    fn identity(&self) -> (Option<VenueId>, Option<&str>, Option<MetroAreaId>) {
+        match self.id {
+            Some(id) => (Some(id), None, None),
+            None => (None, self.display_name.as_deref(), self.metro_area.as_ref().map(|metro_area| metro_area.id)),
+        }
+    }
+}
+
+/// Venues are identified by their ID, the unknown venues, without ID, by their name and
+/// metro area
+impl PartialEq for Venue {
+    fn eq(&self, other: &Venue) -> bool {
+        self.identity() == other.identity()
+    }
+}
+
+impl Eq for Venue {}
+
+impl Hash for Venue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.identity().hash(state);
+    }
+}
+
+/// Venues are ordered as they are compared, the unknown venues first
+impl Ord for Venue {
+    fn cmp(&self, other: &Venue) -> Ordering {
+        self.identity().cmp(&other.identity())
+    }
+}
+
+impl PartialOrd for Venue {
+    fn partial_cmp(&self, other: &Venue) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -1,11 +1,20 @@
+use crate::client::ParseMode;
 use crate::error::SkError;
-use crate::resources::Resource;
+use crate::resources::TopLevelResource;
+use crate::transport::ResponseHeaders;
+use crate::util::json::{get_obj, get_str, get_u64, nested_fields, AtPath};
 use crate::SkResult;
-use serde_json::Value;
+use serde::de::value::{MapAccessDeserializer, SeqAccessDeserializer};
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::cell::RefCell;
+use std::fmt;
+use std::io::BufRead;
 use std::vec::IntoIter;
 
 /// Struct for handling response from API calls
-pub struct SkResultSet<M: Resource> {
+pub struct SkResultSet<M: TopLevelResource> {
     /// Status of the request
     pub status: String,
     iter: IntoIter<M>,
@@ -15,81 +24,441 @@ pub struct SkResultSet<M: Resource> {
     pub per_page: u64,
     /// Total Entries
     pub total_entries: u64,
+    headers: ResponseHeaders,
 }
 
 impl<M> SkResultSet<M>
 where
-    M: Resource,
+    M: TopLevelResource,
 {
+    /// Monitoring headers of the response, e.g. its [rate limit](ResponseHeaders::rate_limit)
+    pub fn headers(&self) -> &ResponseHeaders {
+        &self.headers
+    }
+
+    pub(crate) fn with_headers(mut self, headers: ResponseHeaders) -> SkResultSet<M> {
+        self.headers = headers;
+        self
+    }
+
     #[doc(hidden)]
     pub fn from_json(source: &Value) -> SkResult<SkResultSet<M>> {
-        let obj = source
-            .as_object()
-            .unwrap()
-            .get("resultsPage")
-            .unwrap()
-            .as_object()
-            .unwrap();
+        SkResultSet::from_json_with(source, ParseMode::Lenient)
+    }
+
+    #[doc(hidden)]
+    pub fn from_json_with(source: &Value, parse_mode: ParseMode) -> SkResult<SkResultSet<M>> {
+        let envelope = Envelope::from_json(source)?;
+
+        let mut results: Vec<M> = Vec::new();
+
+        if let Some(r) = envelope.results.get(M::marker()) {
+            let path = format!("resultsPage.results.{}", M::marker());
+            if r.is_object() {
+                let model = M::from_json(r).at(&path)?;
+                results.push(model)
+            } else if r.is_array() {
+                for (i, res) in r.as_array().unwrap().iter().enumerate() {
+                    let model = M::from_json(res).at(&format!("{}[{}]", path, i))?;
+                    results.push(model);
+                }
+            }
+        }
+
+        envelope.into_result_set(results, parse_mode)
+    }
+
+    /// Decode the response read from `reader` as it comes
+    ///
+    /// Only one result is held as JSON at a time, each one being decoded as soon as it is
+    /// read, instead of the whole page. `reader` is read a byte at a time, hence buffered.
+    #[doc(hidden)]
+    pub fn from_reader<R: BufRead>(reader: R, parse_mode: ParseMode) -> SkResult<SkResultSet<M>> {
+        let mut results = Vec::new();
+        let envelope = decode_stream(reader, &mut |model, _: &str| {
+            results.push(model);
+            Ok(())
+        })?;
+        Envelope::from_json(&envelope)?.into_result_set(results, parse_mode)
+    }
+
+    /// Decode the response read from `reader` as it comes, handing each result to `f` as
+    /// soon as it is decoded
+    ///
+    /// The first results are handed before the rest of the page is read. The returned set
+    /// has the paging fields of the response, and no results. In strict mode each result is
+    /// checked before being handed, the first one with unknown fields failing the page.
+    #[doc(hidden)]
+    pub fn from_reader_each<R, F>(reader: R, parse_mode: ParseMode, mut f: F) -> SkResult<SkResultSet<M>>
+    where
+        R: BufRead,
+        F: FnMut(M),
+    {
+        let envelope = decode_stream(reader, &mut |model: M, path: &str| {
+            if parse_mode == ParseMode::Strict {
+                let unknown = nested_fields(path, model.unknown_fields());
+                if !unknown.is_empty() {
+                    return Err(unknown_fields_error::<M>(&unknown));
+                }
+            }
+            f(model);
+            Ok(())
+        })?;
+        Envelope::from_json(&envelope)?.into_result_set(Vec::new(), parse_mode)
+    }
 
-        let status = String::from(obj.get("status").unwrap().as_str().unwrap());
+    /// Hand the results not iterated yet to `f`, for the pages read whole
+    pub(crate) fn drain_into<F: FnMut(M)>(mut self, f: F) -> SkResultSet<M> {
+        self.iter.by_ref().for_each(f);
+        self
+    }
+}
 
-        if status == "error" {
-            let error = obj.get("error").unwrap().as_object().unwrap();
+/// Decode the response read from `reader`, the results handed to `sink` with their path
+/// and left out of the returned value
+fn decode_stream<R, M>(reader: R, sink: &mut dyn FnMut(M, &str) -> SkResult<()>) -> SkResult<Value>
+where
+    R: BufRead,
+    M: TopLevelResource,
+{
+    let failure = RefCell::new(None);
+    let mut de = serde_json::Deserializer::from_reader(reader);
+    let root = StreamSeed {
+        sink,
+        failure: &failure,
+    }
+    .deserialize(&mut de)
+    .and_then(|root| de.end().map(|_| root));
 
-            let message = error.get("message").unwrap().as_str().unwrap();
+    root.map_err(|err| failure.into_inner().unwrap_or_else(|| decode_error(err)))
+}
 
-            return Err(SkError::BadRequest(String::from(message)));
+/// Strict mode error of the results with `unknown` fields
+fn unknown_fields_error<M: TopLevelResource>(unknown: &[String]) -> SkError {
+    SkError::Decode {
+        path: format!("resultsPage.results.{}", M::marker()),
+        message: format!("unknown fields in strict mode: {}", unknown.join(", ")),
+        source: None,
+    }
+}
+
+/// Fields of the `resultsPage` envelope of a response
+struct Envelope<'a> {
+    status: String,
+    page: u64,
+    per_page: u64,
+    total_entries: u64,
+    results: &'a Map<String, Value>,
+}
+
+impl<'a> Envelope<'a> {
+    fn from_json(source: &'a Value) -> SkResult<Envelope<'a>> {
+        if let Some(err) = api_error(source) {
+            return Err(err);
         }
 
+        let root = source.as_object().ok_or_else(|| {
+            SkError::decode(format!("Expected source json to be an object {}", source))
+        })?;
+        let obj = get_obj(root, "resultsPage")?;
+
+        let status = get_str(obj, "status").at("resultsPage")?;
+
         let mut page = 1;
         let mut per_page = 50;
         let mut total_entries = 1;
 
-        if let Some(ref p) = obj.get("page") {
-            page = p.as_u64().unwrap();
+        if obj.contains_key("page") {
+            page = get_u64(obj, "page").at("resultsPage")?;
         }
-        if let Some(ref p) = obj.get("perPage") {
-            per_page = p.as_u64().unwrap();
+        if obj.contains_key("perPage") {
+            per_page = get_u64(obj, "perPage").at("resultsPage")?;
         }
 
-        if let Some(ref p) = obj.get("totalEntries") {
-            total_entries = p.as_u64().unwrap();
+        if obj.contains_key("totalEntries") {
+            total_entries = get_u64(obj, "totalEntries").at("resultsPage")?;
         }
 
-        let result = obj.get("results").unwrap().as_object().unwrap();
+        let results = get_obj(obj, "results").at("resultsPage")?;
 
-        let mut results: Vec<M> = Vec::new();
+        Ok(Envelope {
+            status,
+            page,
+            per_page,
+            total_entries,
+            results,
+        })
+    }
 
-        if let Some(ref r) = result.get(M::marker()) {
-            if r.is_object() {
-                let model = M::from_json(&r)?;
-                results.push(model)
-            } else if r.is_array() {
-                for res in r.as_array().unwrap() {
-                    let model = M::from_json(&res)?;
-                    results.push(model);
-                }
+    fn into_result_set<M: TopLevelResource>(self, results: Vec<M>, parse_mode: ParseMode) -> SkResult<SkResultSet<M>> {
+        if parse_mode == ParseMode::Strict {
+            let unknown: Vec<String> = results
+                .iter()
+                .enumerate()
+                .flat_map(|(i, model)| {
+                    let path = format!("resultsPage.results.{}[{}]", M::marker(), i);
+                    nested_fields(&path, model.unknown_fields())
+                })
+                .collect();
+
+            if !unknown.is_empty() {
+                return Err(unknown_fields_error::<M>(&unknown));
             }
         }
         Ok(SkResultSet {
             iter: results.into_iter(),
-            status: status,
-            page: page,
-            per_page: per_page,
-            total_entries: total_entries,
+            status: self.status,
+            page: self.page,
+            per_page: self.per_page,
+            total_entries: self.total_entries,
+            headers: ResponseHeaders::default(),
         })
     }
 }
 
+/// Errors of the JSON of a response read as it comes, the failures to read it kept apart
+fn decode_error(err: serde_json::Error) -> SkError {
+    if err.is_io() {
+        SkError::Io(err.into())
+    } else {
+        SkError::from(err)
+    }
+}
+
+/// Response read by [`SkResultSet::from_reader`] and [`SkResultSet::from_reader_each`]
+///
+/// The response is decoded as a JSON value, except the results listed under the marker
+/// of `M`: these are decoded one at a time and handed to `sink`, and left out of the value.
+/// The first of them failing to decode, or rejected by `sink`, is kept in `failure`, and
+/// aborts the reading.
+struct StreamSeed<'a, M> {
+    sink: &'a mut dyn FnMut(M, &str) -> SkResult<()>,
+    failure: &'a RefCell<Option<SkError>>,
+}
+
+impl<'a, M> StreamSeed<'a, M> {
+    fn nested(&mut self) -> StreamSeed<'_, M> {
+        StreamSeed {
+            sink: &mut *self.sink,
+            failure: self.failure,
+        }
+    }
+
+    /// Keep the failure of a result, and abort with a placeholder error
+    fn fail<E: de::Error>(&self, err: SkError) -> E {
+        *self.failure.borrow_mut() = Some(err);
+        E::custom("invalid result")
+    }
+
+    fn push(&mut self, source: &Value, path: &str) -> Result<(), SkError>
+    where
+        M: TopLevelResource,
+    {
+        let model = M::from_json(source).at(path)?;
+        (self.sink)(model, path)
+    }
+}
+
+/// Level of the response being read by a [`StreamSeed`]
+struct Level<'a, M> {
+    seed: StreamSeed<'a, M>,
+    depth: Depth,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Depth {
+    Root,
+    Page,
+    Results,
+    Items,
+}
+
+impl<'de, 'a, M: TopLevelResource> DeserializeSeed<'de> for StreamSeed<'a, M> {
+    type Value = Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        Level {
+            seed: self,
+            depth: Depth::Root,
+        }
+        .deserialize(deserializer)
+    }
+}
+
+impl<'de, 'a, M: TopLevelResource> DeserializeSeed<'de> for Level<'a, M> {
+    type Value = Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, 'a, M: TopLevelResource> Visitor<'de> for Level<'a, M> {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> Result<Value, A::Error> {
+        if self.depth == Depth::Items {
+            let source = Value::deserialize(MapAccessDeserializer::new(map))?;
+            let path = format!("resultsPage.results.{}", M::marker());
+            return match self.seed.push(&source, &path) {
+                Ok(()) => Ok(Value::Null),
+                Err(err) => Err(self.seed.fail(err)),
+            };
+        }
+
+        let mut obj = Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let depth = match (self.depth, key.as_str()) {
+                (Depth::Root, "resultsPage") => Some(Depth::Page),
+                (Depth::Page, "results") => Some(Depth::Results),
+                (Depth::Results, marker) if marker == M::marker() => Some(Depth::Items),
+                _ => None,
+            };
+            let value = match depth {
+                Some(depth) => map.next_value_seed(Level {
+                    seed: self.seed.nested(),
+                    depth,
+                })?,
+                None => map.next_value()?,
+            };
+            // The results are in `results`, not in the value
+            if depth != Some(Depth::Items) {
+                obj.insert(key, value);
+            }
+        }
+        Ok(Value::Object(obj))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<Value, A::Error> {
+        if self.depth != Depth::Items {
+            return Value::deserialize(SeqAccessDeserializer::new(seq));
+        }
+        let mut i = 0;
+        while let Some(source) = seq.next_element::<Value>()? {
+            let path = format!("resultsPage.results.{}[{}]", M::marker(), i);
+            if let Err(err) = self.seed.push(&source, &path) {
+                return Err(self.seed.fail(err));
+            }
+            i += 1;
+        }
+        Ok(Value::Null)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Value, E> {
+        Ok(Value::String(v))
+    }
+}
+
+/// Error reported by the API in the `resultsPage` envelope, if `source` is one
+///
+/// Failing requests answer with
+/// `{"resultsPage":{"status":"error","error":{"message":...}}}`, whatever the shape
+/// of the expected results.
+pub(crate) fn api_error(source: &Value) -> Option<SkError> {
+    let page = source.get("resultsPage")?;
+    if page.get("status").and_then(Value::as_str) != Some("error") {
+        return None;
+    }
+    let message = page
+        .get("error")
+        .and_then(|error| error.get("message"))
+        .and_then(Value::as_str)
+        .unwrap_or("unknown error");
+
+    Some(SkError::Api {
+        message: String::from(message),
+    })
+}
+
 impl<M> Iterator for SkResultSet<M>
 where
-    M: Resource,
+    M: TopLevelResource,
 {
     type Item = M;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.iter.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<M> ExactSizeIterator for SkResultSet<M> where M: TopLevelResource {}
+
+/// Parallel iteration over the resources, with the `rayon` feature
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "rayon")]
+/// # {
+/// use rayon::prelude::*;
+/// use songkick::SongKick;
+///
+/// let sk = SongKick::new("API_KEY");
+/// // RadioHead ID
+/// let events = sk.artist.calendar(253846, None).unwrap();
+/// let names: Vec<String> = events.into_par_iter().map(|event| event.display_name).collect();
+/// # }
+/// ```
+#[cfg(feature = "rayon")]
+impl<M> rayon::iter::IntoParallelIterator for SkResultSet<M>
+where
+    M: TopLevelResource + Send,
+{
+    type Iter = rayon::vec::IntoIter<M>;
+    type Item = M;
+
+    fn into_par_iter(self) -> Self::Iter {
+        // Reuses the allocation of the results
+        self.iter.collect::<Vec<M>>().into_par_iter()
+    }
+}
+
+/// Parallel iteration over the resources not iterated yet, with the `rayon` feature
+#[cfg(feature = "rayon")]
+impl<'a, M> rayon::iter::IntoParallelIterator for &'a SkResultSet<M>
+where
+    M: TopLevelResource + Sync,
+{
+    type Iter = rayon::slice::Iter<'a, M>;
+    type Item = &'a M;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.iter.as_slice().into_par_iter()
+    }
 }
 
 #[allow(unused_imports)]
@@ -99,23 +468,28 @@ mod tests {
     use crate::resources::artist::Artist;
     use crate::resources::event::Event;
     use crate::resources::event::When;
-    use crate::resources::Resource;
+    use crate::resources::ids::{ArtistId, EventId};
+    use crate::resources::{Resource, TopLevelResource};
     use crate::result::SkResultSet;
-    use serde_json;
+    use crate::client::ParseMode;
+    
     use serde_json::Value;
     use std::fs::File;
     use std::io::Read;
     use crate::SkResult;
 
-    fn load_result<M: Resource>(path: &str) -> SkResult<SkResultSet<M>> {
+    fn load_json(path: &str) -> Value {
         let sample_str = {
             let mut file = File::open(path).unwrap();
             let mut ret = String::new();
             file.read_to_string(&mut ret).unwrap();
             ret
         };
-        let data: Value = serde_json::from_str(&sample_str).unwrap();
-        SkResultSet::from_json(&data)
+        serde_json::from_str(&sample_str).unwrap()
+    }
+
+    fn load_result<M: TopLevelResource>(path: &str) -> SkResult<SkResultSet<M>> {
+        SkResultSet::from_json(&load_json(path))
     }
 
     #[test]
@@ -130,15 +504,15 @@ mod tests {
 
         let artist = res.next();
 
-        assert_eq!(artist.is_none(), false);
+        assert!(artist.is_some());
 
         let artist = artist.unwrap();
 
         assert_eq!(artist.display_name, "Placebo");
-        assert_eq!(artist.id, 324967);
+        assert_eq!(artist.id, ArtistId(324967));
 
         assert_eq!(
-            artist.identifiers[0].mbid,
+            artist.identifiers[0].mbid.to_string(),
             "81b9963b-7ff7-47f7-9afb-fe454d8db43c"
         );
     }
@@ -158,10 +532,10 @@ mod tests {
         assert_eq!(artists.len(), 10);
 
         assert_eq!(artists[0].display_name, "Placebo");
-        assert_eq!(artists[0].id, 324967);
+        assert_eq!(artists[0].id, ArtistId(324967));
 
         assert_eq!(
-            artists[0].identifiers[0].mbid,
+            artists[0].identifiers[0].mbid.to_string(),
             "81b9963b-7ff7-47f7-9afb-fe454d8db43c"
         );
     }
@@ -184,20 +558,21 @@ mod tests {
             "Placebo with The Mirror Trap at Cirkus (October 18, 2016)",
             events[0].display_name
         );
-        assert_eq!(26486294, events[0].id);
+        assert_eq!(EventId(26486294), events[0].id);
 
         match events[0].venue.metro_area {
             Some(ref m) => {
                 assert_eq!("Stockholm", m.display_name);
             }
-            None => assert!(false),
+            None => panic!("expected a metro area"),
         }
 
         assert_eq!(
             When {
                 datetime: Some(String::from("2016-10-18T19:30:00+0200")),
                 time: Some(String::from("19:30:00")),
-                date: Some(String::from("2016-10-18"))
+                date: Some(String::from("2016-10-18")),
+                ..Default::default()
             },
             events[0].start
         );
@@ -223,19 +598,25 @@ mod tests {
             events[0].display_name
         );
         assert_eq!("Festival", events[0].event_type);
-        assert_eq!(27081999, events[0].id);
+        assert_eq!(EventId(27081999), events[0].id);
 
         match events[0].venue.metro_area {
             Some(ref m) => {
                 assert_eq!("Paris", m.display_name);
             }
-            None => assert!(false),
+            None => panic!("expected a metro area"),
         }
 
         assert_eq!(
             Some(String::from("Grande Halle de la Villette")),
             events[0].venue.display_name
         );
+        assert_eq!(None, events[0].venue.capacity);
+        assert_eq!(Some(String::from("211, avenue Jean Jaurès")), events[0].venue.street);
+        assert_eq!(Some(String::from("75019")), events[0].venue.zip);
+        assert_eq!(Some(String::from("01 40 03 75 75")), events[0].venue.phone);
+        assert_eq!(Some(String::from("http://www.villette.com/")), events[0].venue.website);
+        assert_eq!(Some("Paris"), events[0].venue.city.as_ref().map(|c| c.display_name.as_str()));
 
         assert_eq!(
             Some(String::from("2016-10-27T17:00:00+0200")),
@@ -248,7 +629,8 @@ mod tests {
             Some(When {
                 datetime: None,
                 time: None,
-                date: Some(String::from("2016-10-29"))
+                date: Some(String::from("2016-10-29")),
+                ..Default::default()
             }),
             events[0].end
         );
@@ -270,17 +652,238 @@ mod tests {
 
     #[test]
     fn invalid_api_key() {
-        let res = load_result::<Artist>("fixtures/invalid_key.json");
+        let res = load_result::<Artist>("fixtures/error/invalid-key.json");
 
-        assert_eq!(true, res.is_err());
+        assert!(res.is_err());
 
         let err = res.err().unwrap();
 
         match err {
-            SkError::BadRequest(ref err) => {
-                assert_eq!("Invalid or missing apikey", err);
+            SkError::Api { ref message } => {
+                assert_eq!("Invalid or missing apikey", message);
+            }
+            _ => panic!("expected an Api error"),
+        }
+    }
+
+    #[test]
+    fn api_error_envelope() {
+        let data: Value = serde_json::from_str(
+            r#"{"resultsPage":{"status":"error","error":{"message":"Artist not found"}}}"#,
+        )
+        .unwrap();
+        match SkResultSet::<Event>::from_json(&data) {
+            Err(SkError::Api { ref message }) => assert_eq!("Artist not found", message),
+            _ => panic!("expected an Api error"),
+        }
+
+        let data: Value = serde_json::from_str(r#"{"resultsPage":{"status":"error"}}"#).unwrap();
+        match super::api_error(&data) {
+            Some(SkError::Api { ref message }) => assert_eq!("unknown error", message),
+            _ => panic!("expected an Api error"),
+        }
+
+        assert!(super::api_error(&load_json("fixtures/event/artist-324967-calendar.json")).is_none());
+    }
+
+    #[test]
+    fn strict_mode_accepts_modelled_fields() {
+        let data = load_json("fixtures/event/artist-324967-calendar.json");
+        let res = SkResultSet::<Event>::from_json_with(&data, ParseMode::Strict).unwrap();
+        assert_eq!(33, res.count());
+
+        let data = load_json("fixtures/event/single-event-festival-27081999.json");
+        let res = SkResultSet::<Event>::from_json_with(&data, ParseMode::Strict).unwrap();
+        assert_eq!(1, res.count());
+
+        let data = load_json("fixtures/artist/artist-search-placebo.json");
+        let res = SkResultSet::<Artist>::from_json_with(&data, ParseMode::Strict).unwrap();
+        assert_eq!(10, res.count());
+    }
+
+    #[test]
+    fn strict_mode_rejects_unknown_fields() {
+        let mut data = load_json("fixtures/event/artist-324967-calendar.json");
+        data["resultsPage"]["results"]["event"][3]["venue"]
+            .as_object_mut()
+            .unwrap()
+            .insert(String::from("newField"), Value::from(true));
+
+        assert!(SkResultSet::<Event>::from_json_with(&data, ParseMode::Lenient).is_ok());
+
+        match SkResultSet::<Event>::from_json_with(&data, ParseMode::Strict) {
+            Err(SkError::Decode { ref path, ref message, .. }) => {
+                assert_eq!("resultsPage.results.event", path);
+                assert!(message.contains("event[3].venue.newField"), "{}", message);
+            }
+            _ => panic!("expected a Decode error"),
+        }
+    }
+
+    #[test]
+    fn decode_error_reports_path() {
+        let mut data = load_json("fixtures/event/artist-324967-calendar.json");
+        data["resultsPage"]["results"]["event"][3]["venue"]["metroArea"]["id"] = Value::from("32252");
+
+        match SkResultSet::<Event>::from_json(&data) {
+            Err(SkError::Decode { ref path, ref message, .. }) => {
+                assert_eq!("resultsPage.results.event[3].venue.metroArea.id", path);
+                assert_eq!("invalid type: string, expected an unsigned integer", message);
+            }
+            _ => panic!("expected a Decode error"),
+        }
+
+        let mut data = load_json("fixtures/event/artist-324967-calendar.json");
+        data["resultsPage"]["results"]["event"][0]["performance"][1]
+            .as_object_mut()
+            .unwrap()
+            .remove("artist");
+
+        match SkResultSet::<Event>::from_json(&data) {
+            Err(SkError::Decode { ref path, ref message, .. }) => {
+                assert_eq!("resultsPage.results.event[0].performance[1].artist", path);
+                assert_eq!("missing field", message);
+            }
+            _ => panic!("expected a Decode error"),
+        }
+    }
+
+    #[cfg(feature = "country-codes")]
+    #[test]
+    fn calendar_countries_have_iso_codes() {
+        let events: Vec<Event> = load_result("fixtures/event/artist-324967-calendar.json")
+            .unwrap()
+            .collect();
+        for event in events {
+            if let Some(metro_area) = event.venue.metro_area {
+                assert!(
+                    metro_area.country.iso_code().is_some(),
+                    "no code for {}",
+                    metro_area.country.display_name
+                );
             }
-            _ => assert!(false),
         }
     }
+
+    #[test]
+    fn stream_fixtures() {
+        use crate::resources::calendar_entry::CalendarEntry;
+        use crate::resources::location::LocationMatch;
+        use crate::testing::fixtures;
+
+        fn same<M: TopLevelResource + serde::Serialize>(fixture: fixtures::Fixture) {
+            let read = SkResultSet::<M>::from_reader(fixture.json().as_bytes(), ParseMode::Strict).unwrap();
+            let parsed = SkResultSet::<M>::from_json_with(&fixture.value(), ParseMode::Strict).unwrap();
+            assert_eq!((parsed.page, parsed.per_page, parsed.total_entries), (read.page, read.per_page, read.total_entries));
+            let read: Vec<Value> = read.map(|m| serde_json::to_value(m).unwrap()).collect();
+            let parsed: Vec<Value> = parsed.map(|m| serde_json::to_value(m).unwrap()).collect();
+            assert_eq!(parsed, read, "{}", fixture.name());
+        }
+
+        same::<Artist>(fixtures::ARTIST);
+        same::<Artist>(fixtures::ARTIST_SEARCH);
+        same::<Event>(fixtures::ARTIST_CALENDAR);
+        same::<Event>(fixtures::FESTIVAL);
+        same::<CalendarEntry>(fixtures::USER_CALENDAR);
+        same::<LocationMatch>(fixtures::LOCATION_SEARCH);
+
+        let empty = SkResultSet::<Event>::from_reader(fixtures::ARTIST_SEARCH.json().as_bytes(), ParseMode::Lenient);
+        assert_eq!(0, empty.unwrap().count());
+        assert!(matches!(
+            SkResultSet::<Artist>::from_reader(fixtures::INVALID_KEY.json().as_bytes(), ParseMode::Lenient),
+            Err(SkError::Api { .. })
+        ));
+    }
+
+    #[test]
+    fn stream_errors() {
+        let mut data = load_json("fixtures/event/artist-324967-calendar.json");
+        data["resultsPage"]["results"]["event"][0]["performance"][1]
+            .as_object_mut()
+            .unwrap()
+            .remove("artist");
+        match SkResultSet::<Event>::from_reader(data.to_string().as_bytes(), ParseMode::Lenient) {
+            Err(SkError::Decode { ref path, .. }) => {
+                assert_eq!("resultsPage.results.event[0].performance[1].artist", path)
+            }
+            _ => panic!("expected a Decode error"),
+        }
+
+        let truncated = r#"{"resultsPage": {"status": "ok", "results": {"event": ["#;
+        assert!(matches!(
+            SkResultSet::<Event>::from_reader(truncated.as_bytes(), ParseMode::Lenient),
+            Err(SkError::Decode { .. })
+        ));
+        let missing = r#"{"resultsPage": {"results": {}}}"#;
+        assert!(matches!(
+            SkResultSet::<Event>::from_reader(missing.as_bytes(), ParseMode::Lenient),
+            Err(SkError::Decode { .. })
+        ));
+    }
+
+    #[test]
+    fn stream_each_result() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        /// Reader counting the bytes read
+        struct Counted(&'static [u8], Rc<Cell<usize>>);
+
+        impl Read for Counted {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let read = self.0.read(buf)?;
+                self.1.set(self.1.get() + read);
+                Ok(read)
+            }
+        }
+
+        let fixture = crate::testing::fixtures::ARTIST_CALENDAR;
+        let read = Rc::new(Cell::new(0));
+        let mut handed = Vec::new();
+        let page = SkResultSet::<Event>::from_reader_each(
+            std::io::BufReader::new(Counted(fixture.json().as_bytes(), read.clone())),
+            ParseMode::Strict,
+            |event| handed.push((event.id, read.get())),
+        )
+        .unwrap();
+
+        assert_eq!((1, 50, 33), (page.page, page.per_page, page.total_entries));
+        assert_eq!(0, page.count());
+        let ids: Vec<EventId> = fixture.load::<Event>().unwrap().map(|event| event.id).collect();
+        assert_eq!(ids, handed.iter().map(|(id, _)| *id).collect::<Vec<_>>());
+        // Handed while the rest of the page is still unread
+        assert!(handed[0].1 < fixture.json().len());
+
+        let mut data = load_json("fixtures/event/artist-324967-calendar.json");
+        data["resultsPage"]["results"]["event"][3]["venue"]
+            .as_object_mut()
+            .unwrap()
+            .insert(String::from("newField"), Value::from(true));
+        let mut count = 0;
+        let json = data.to_string();
+        assert!(SkResultSet::<Event>::from_reader_each(json.as_bytes(), ParseMode::Lenient, |_| count += 1).is_ok());
+        assert_eq!(33, count);
+        count = 0;
+        match SkResultSet::<Event>::from_reader_each(json.as_bytes(), ParseMode::Strict, |_| count += 1) {
+            Err(SkError::Decode { ref message, .. }) => assert!(message.contains("event[3].venue.newField"), "{}", message),
+            _ => panic!("expected a Decode error"),
+        }
+        assert_eq!(3, count);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_iteration() {
+        use rayon::prelude::*;
+
+        let data = load_json("fixtures/event/artist-324967-calendar.json");
+        let mut events = SkResultSet::<Event>::from_json(&data).unwrap();
+        let first = events.next().unwrap();
+        let performances: usize = (&events).into_par_iter().map(|event| event.performances.len()).sum();
+        let ids: Vec<_> = events.into_par_iter().map(|event| event.id).collect();
+
+        assert_eq!(32, ids.len());
+        assert!(!ids.contains(&first.id));
+        assert!(performances >= ids.len());
+    }
 }
@@ -0,0 +1,170 @@
+//! Scheduling of the requests of a client by priority
+//!
+//! A client can cap the requests in flight at a time, with
+//! [`SongKickBuilder::max_concurrent_requests`], and space its requests out, with
+//! [`SongKickBuilder::min_request_interval`]. Once a limit is reached the requests wait
+//! their turn, the [foreground](Priority::Foreground) ones first: a bulk sync running
+//! through a [background](Priority::Background) handle of the client doesn't hold up the
+//! interactive calls sharing its limits.
+//!
+//! ```rust,no_run
+//! use songkick::schedule::Priority;
+//! use songkick::SongKickBuilder;
+//! use std::time::Duration;
+//!
+//! let sk = SongKickBuilder::new("API_KEY")
+//!     .max_concurrent_requests(4)
+//!     .min_request_interval(Duration::from_millis(100))
+//!     .build();
+//! let background = sk.with_priority(Priority::Background);
+//! // Radiohead ID, the gigography fetched in bulk
+//! let gigography = background.artist.gigography(253846, None).unwrap();
+//! let artists = sk.artist.search_by_name("Placebo").unwrap();
+//! ```
+//!
+//! [`SongKickBuilder::max_concurrent_requests`]: crate::SongKickBuilder::max_concurrent_requests
+//! [`SongKickBuilder::min_request_interval`]: crate::SongKickBuilder::min_request_interval
+
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Priority of the requests of a client, see [`SongKick::with_priority`](crate::SongKick::with_priority)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Priority {
+    /// Interactive requests, served first
+    #[default]
+    Foreground,
+    /// Bulk requests, served once no foreground request waits
+    Background,
+}
+
+/// Limits of the requests of a client, shared by its handles of every priority
+#[derive(Debug, Default)]
+pub(crate) struct Scheduler {
+    max_in_flight: Option<usize>,
+    min_interval: Option<Duration>,
+    state: Mutex<State>,
+    turn: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    in_flight: usize,
+    /// Foreground requests waiting for their turn
+    waiting_foreground: usize,
+    /// Earliest start of the next request
+    next_start: Option<Instant>,
+}
+
+/// Turn of a request, held until the request is over
+pub(crate) struct Permit<'a> {
+    scheduler: Option<&'a Scheduler>,
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        if let Some(scheduler) = self.scheduler {
+            scheduler.lock().in_flight -= 1;
+            scheduler.turn.notify_all();
+        }
+    }
+}
+
+impl Scheduler {
+    /// Scheduler of `max_in_flight` requests at a time, started `min_interval` apart
+    pub(crate) fn new(max_in_flight: Option<usize>, min_interval: Option<Duration>) -> Scheduler {
+        Scheduler {
+            max_in_flight: max_in_flight.map(|max| max.max(1)),
+            min_interval: min_interval.filter(|interval| !interval.is_zero()),
+            ..Scheduler::default()
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Wait for the turn of a request of `priority`
+    pub(crate) fn acquire(&self, priority: Priority) -> Permit<'_> {
+        if self.max_in_flight.is_none() && self.min_interval.is_none() {
+            return Permit { scheduler: None };
+        }
+        let foreground = priority == Priority::Foreground;
+        let mut state = self.lock();
+        if foreground {
+            state.waiting_foreground += 1;
+        }
+        loop {
+            let full = self.max_in_flight.is_some_and(|max| state.in_flight >= max);
+            if full || (!foreground && state.waiting_foreground > 0) {
+                state = self.turn.wait(state).unwrap_or_else(PoisonError::into_inner);
+                continue;
+            }
+            let now = Instant::now();
+            match state.next_start {
+                Some(start) if start > now => {
+                    state = self.turn.wait_timeout(state, start - now).unwrap_or_else(PoisonError::into_inner).0;
+                }
+                _ => {
+                    if foreground {
+                        state.waiting_foreground -= 1;
+                    }
+                    state.in_flight += 1;
+                    state.next_start = self.min_interval.map(|interval| now + interval);
+                    // Background requests may be free to go with no foreground one left
+                    self.turn.notify_all();
+                    return Permit { scheduler: Some(self) };
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn foreground_first() {
+        let scheduler = Arc::new(Scheduler::new(Some(1), None));
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let permit = scheduler.acquire(Priority::Foreground);
+
+        let waiters: Vec<_> = [Priority::Background, Priority::Background, Priority::Foreground]
+            .iter()
+            .map(|&priority| {
+                let (scheduler, order) = (scheduler.clone(), order.clone());
+                let waiter = thread::spawn(move || {
+                    let _permit = scheduler.acquire(priority);
+                    order.lock().unwrap().push(priority);
+                    thread::sleep(Duration::from_millis(10));
+                });
+                thread::sleep(Duration::from_millis(50));
+                waiter
+            })
+            .collect();
+        drop(permit);
+        for waiter in waiters {
+            waiter.join().unwrap();
+        }
+
+        let order = order.lock().unwrap();
+        assert_eq!(vec![Priority::Foreground, Priority::Background, Priority::Background], *order);
+        assert_eq!(0, scheduler.lock().in_flight);
+    }
+
+    #[test]
+    fn spaced_requests() {
+        let scheduler = Scheduler::new(None, Some(Duration::from_millis(30)));
+        let start = Instant::now();
+        for _ in 0..4 {
+            scheduler.acquire(Priority::Background);
+        }
+        assert!(start.elapsed() >= Duration::from_millis(90));
+
+        let unlimited = Scheduler::new(None, Some(Duration::ZERO));
+        assert!(unlimited.acquire(Priority::Foreground).scheduler.is_none());
+    }
+}
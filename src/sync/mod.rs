@@ -0,0 +1,368 @@
+//! Mirror of calendars and gigographies into a local store
+//!
+//! Each [`Subscription`] is fetched and written through a [`SyncStore`]:
+//! events seen for the first time are created, the others are updated in place,
+//! so running the same sync again keeps the store current. [`SqliteStore`] is
+//! shipped with the `sqlite` feature, other databases only need to implement the trait.
+//!
+//! The store remembers the last successful run of each subscription, so later runs
+//! fetch only what changed since:
+//!
+//! - gigographies are fetched from the day before the last run, older past events
+//!   don't change;
+//! - calendars are walked from their furthest date, where new events are announced,
+//!   and the walk stops at the first page without events unknown to the last run.
+//!
+//! Changes to events already known and beyond the first page (e.g. a cancellation
+//! of a concert next week) are picked up by [`sync_full`], which fetches everything
+//! again and should run from time to time.
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "sqlite")]
+//! # {
+//! use songkick::SongKick;
+//! use songkick::sync::{self, SqliteStore, Subscription};
+//!
+//! let sk = SongKick::new("API_KEY");
+//! let mut store = SqliteStore::open("concerts.db").unwrap();
+//!
+//! // RadioHead ID
+//! let report = sync::sync(&sk, &mut store, Subscription::ArtistCalendar(253846.into())).unwrap();
+//! println!("{} new events, {} updated", report.created, report.updated);
+//! # }
+//! ```
+
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+#[cfg(feature = "sqlite")]
+pub use crate::sync::sqlite::SqliteStore;
+
+use crate::endpoints::Pages;
+use crate::options::Sort;
+use crate::resources::artist::Artist;
+use crate::resources::event::Event;
+use crate::resources::ids::{ArtistId, EventId, MetroAreaId};
+use crate::{SkResult, SongKick};
+use std::collections::HashSet;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A calendar mirrored by [`sync`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Subscription {
+    /// Upcoming events of the artist
+    ArtistCalendar(ArtistId),
+    /// Past events of the artist
+    ArtistGigography(ArtistId),
+    /// Upcoming events in the metro area
+    MetroAreaCalendar(MetroAreaId),
+}
+
+impl Subscription {
+    /// Pages of the subscribed calendar
+    fn pages(self, sk: &SongKick) -> Pages<'_, Event> {
+        match self {
+            Subscription::ArtistCalendar(id) => sk.artist.calendar_pages(id),
+            Subscription::ArtistGigography(id) => sk.artist.gigography_pages(id),
+            Subscription::MetroAreaCalendar(id) => sk.metro_area.calendar_pages(id),
+        }
+    }
+}
+
+/// Path of the subscribed calendar in the API (e.g. `artists/253846/calendar`), usable as a store key
+impl fmt::Display for Subscription {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Subscription::ArtistCalendar(id) => write!(f, "artists/{}/calendar", id),
+            Subscription::ArtistGigography(id) => write!(f, "artists/{}/gigography", id),
+            Subscription::MetroAreaCalendar(id) => write!(f, "metro_areas/{}/calendar", id),
+        }
+    }
+}
+
+/// What a store remembers of the last successful sync of a subscription
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct SyncState {
+    /// End of the run
+    pub last_run: SystemTime,
+    /// Events in the calendar at that time
+    pub seen: Vec<EventId>,
+}
+
+impl SyncState {
+    pub fn new(last_run: SystemTime, seen: Vec<EventId>) -> SyncState {
+        SyncState { last_run, seen }
+    }
+}
+
+/// Storage the sync engine writes through
+pub trait SyncStore {
+    /// Create or update `event` and its venue, returns whether the event was created
+    ///
+    /// The artists of the line-up are upserted beforehand.
+    fn upsert_event(&mut self, event: &Event) -> SkResult<bool>;
+
+    /// Create or update `artist`
+    fn upsert_artist(&mut self, artist: &Artist) -> SkResult<()>;
+
+    /// Record a successful sync of `subscription`, which returned `events`
+    fn mark_seen(&mut self, subscription: Subscription, events: &[EventId]) -> SkResult<()>;
+
+    /// State recorded by the last [`mark_seen`](SyncStore::mark_seen) of `subscription`, if any
+    fn load_state(&mut self, subscription: Subscription) -> SkResult<Option<SyncState>>;
+}
+
+/// Outcome of a sync run
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SyncReport {
+    /// Events not in the store before the run
+    pub created: u64,
+    /// Events already in the store, updated with the fetched data
+    pub updated: u64,
+    /// Pages requested to the API
+    pub pages: u64,
+}
+
+/// Fetch what changed in the calendar of `subscription` since its last sync, and write it to `store`
+///
+/// The first sync of a subscription fetches the whole calendar.
+pub fn sync<S>(sk: &SongKick, store: &mut S, subscription: Subscription) -> SkResult<SyncReport>
+where
+    S: SyncStore + ?Sized,
+{
+    let state = store.load_state(subscription)?;
+    sync_pages(store, subscription, subscription.pages(sk), state)
+}
+
+/// Fetch the whole calendar of `subscription` and write its events to `store`
+pub fn sync_full<S>(sk: &SongKick, store: &mut S, subscription: Subscription) -> SkResult<SyncReport>
+where
+    S: SyncStore + ?Sized,
+{
+    sync_pages(store, subscription, subscription.pages(sk), None)
+}
+
+fn sync_pages<S>(
+    store: &mut S,
+    subscription: Subscription,
+    mut pages: Pages<'_, Event>,
+    state: Option<SyncState>,
+) -> SkResult<SyncReport>
+where
+    S: SyncStore + ?Sized,
+{
+    let (known, last_run): (HashSet<EventId>, _) = match state {
+        Some(state) => (state.seen.into_iter().collect(), Some(state.last_run)),
+        None => (HashSet::new(), None),
+    };
+    let gigography = matches!(subscription, Subscription::ArtistGigography(_));
+    if let Some(last_run) = last_run {
+        pages = if gigography {
+            let min_date = day_before(last_run);
+            pages.filter(move |filter| {
+                filter.min_date(min_date.as_str());
+            })
+        } else {
+            pages.sort(Sort::DESC)
+        };
+    }
+
+    let mut report = SyncReport::default();
+    let mut seen = HashSet::new();
+    // Whether the fetched events are the whole calendar, or only its changes
+    let mut complete = last_run.is_none() || !gigography;
+    for page in pages {
+        let events: Vec<Event> = page?.collect();
+        let page_report = store_events(store, &events)?;
+        report.created += page_report.created;
+        report.updated += page_report.updated;
+        report.pages += 1;
+
+        let unknown = events.iter().any(|event| !known.contains(&event.id));
+        seen.extend(events.iter().map(|event| event.id));
+        if last_run.is_some() && !gigography && !unknown {
+            complete = false;
+            break;
+        }
+    }
+    if !complete {
+        seen.extend(known);
+    }
+
+    let mut seen: Vec<EventId> = seen.into_iter().collect();
+    seen.sort();
+    store.mark_seen(subscription, &seen)?;
+    Ok(report)
+}
+
+/// `YYYY-MM-DD` date of the day before `time`, in UTC
+fn day_before(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).unwrap_or(Duration::from_secs(0)).as_secs();
+    let days = (secs / 86_400) as i64 - 1;
+    // Civil date from days since the epoch, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Write `events`, with the artists of their line-up, to `store`
+pub fn store_events<S>(store: &mut S, events: &[Event]) -> SkResult<SyncReport>
+where
+    S: SyncStore + ?Sized,
+{
+    let mut report = SyncReport::default();
+    for event in events {
+        for performance in &event.performances {
+            store.upsert_artist(&performance.artist)?;
+        }
+        if store.upsert_event(event)? {
+            report.created += 1;
+        } else {
+            report.updated += 1;
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::format_with_options;
+    use crate::result::SkResultSet;
+    use serde_json::Value;
+    use std::collections::{HashMap, HashSet};
+    use std::fs::File;
+    use std::io::Read;
+
+    /// Store keeping the IDs only, as a custom implementation would
+    #[derive(Default)]
+    struct MemoryStore {
+        events: HashSet<EventId>,
+        artists: HashSet<ArtistId>,
+        states: HashMap<Subscription, SyncState>,
+    }
+
+    impl SyncStore for MemoryStore {
+        fn upsert_event(&mut self, event: &Event) -> SkResult<bool> {
+            assert!(event.performances.iter().all(|p| self.artists.contains(&p.artist.id)));
+            Ok(self.events.insert(event.id))
+        }
+
+        fn upsert_artist(&mut self, artist: &Artist) -> SkResult<()> {
+            self.artists.insert(artist.id);
+            Ok(())
+        }
+
+        fn mark_seen(&mut self, subscription: Subscription, events: &[EventId]) -> SkResult<()> {
+            self.states.insert(subscription, SyncState::new(SystemTime::now(), events.to_vec()));
+            Ok(())
+        }
+
+        fn load_state(&mut self, subscription: Subscription) -> SkResult<Option<SyncState>> {
+            Ok(self.states.get(&subscription).cloned())
+        }
+    }
+
+    fn load_json(path: &str) -> Value {
+        let mut file = File::open(path).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+        serde_json::from_str(&content).unwrap()
+    }
+
+    fn load_events(path: &str) -> Vec<Event> {
+        SkResultSet::<Event>::from_json(&load_json(path)).unwrap().collect()
+    }
+
+    /// Pages of the calendar fixture, claiming `total_entries` results; `check` sees the URL query of each page
+    fn calendar_pages<'a, F>(total_entries: u64, check: F) -> Pages<'a, Event>
+    where
+        F: Fn(&str) + 'a,
+    {
+        let mut data = load_json("fixtures/event/artist-324967-calendar.json");
+        data["resultsPage"]["totalEntries"] = Value::from(total_entries);
+        Pages::new(move |options| {
+            check(&format_with_options("", Some(options)));
+            SkResultSet::from_json(&data)
+        })
+    }
+
+    #[test]
+    fn store_events_through_trait() {
+        let events = load_events("fixtures/event/artist-324967-calendar.json");
+        let mut store = MemoryStore::default();
+
+        let report = store_events(&mut store, &events).unwrap();
+        assert_eq!(events.len() as u64, report.created);
+        let report = store_events(&mut store, &events).unwrap();
+        assert_eq!(0, report.created);
+        assert_eq!(events.len() as u64, report.updated);
+    }
+
+    #[test]
+    fn subscription_keys() {
+        assert_eq!("artists/253846/calendar", Subscription::ArtistCalendar(ArtistId(253846)).to_string());
+        assert_eq!("artists/253846/gigography", Subscription::ArtistGigography(ArtistId(253846)).to_string());
+        assert_eq!("metro_areas/24426/calendar", Subscription::MetroAreaCalendar(MetroAreaId(24426)).to_string());
+    }
+
+    #[test]
+    fn first_sync_fetches_everything() {
+        let mut store = MemoryStore::default();
+        let subscription = Subscription::ArtistCalendar(ArtistId(324967));
+        let pages = calendar_pages(66, |query| assert!(!query.contains("order=")));
+
+        let report = sync_pages(&mut store, subscription, pages, None).unwrap();
+        assert_eq!(2, report.pages);
+        assert_eq!(33, report.created);
+        assert_eq!(33, store.load_state(subscription).unwrap().unwrap().seen.len());
+    }
+
+    #[test]
+    fn calendar_walk_stops_at_known_page() {
+        let mut store = MemoryStore::default();
+        let subscription = Subscription::ArtistCalendar(ArtistId(324967));
+        sync_pages(&mut store, subscription, calendar_pages(33, |_| ()), None).unwrap();
+        let state = store.load_state(subscription).unwrap();
+
+        let pages = calendar_pages(99, |query| assert!(query.contains("order=desc")));
+        let report = sync_pages(&mut store, subscription, pages, state).unwrap();
+        assert_eq!(1, report.pages);
+        assert_eq!(0, report.created);
+        assert_eq!(33, report.updated);
+    }
+
+    #[test]
+    fn gigography_fetches_since_last_run() {
+        let mut store = MemoryStore::default();
+        let subscription = Subscription::ArtistGigography(ArtistId(324967));
+        let last_run = UNIX_EPOCH + Duration::from_secs(1_456_790_400);
+        let state = SyncState::new(last_run, vec![EventId(1)]);
+
+        let pages = calendar_pages(33, |query| assert!(query.contains("min_date=2016%2D02%2D29")));
+        let report = sync_pages(&mut store, subscription, pages, Some(state)).unwrap();
+        assert_eq!(33, report.created);
+        // Events before the last run are kept
+        let seen = store.load_state(subscription).unwrap().unwrap().seen;
+        assert_eq!(34, seen.len());
+        assert!(seen.contains(&EventId(1)));
+    }
+
+    #[test]
+    fn days_before() {
+        assert_eq!("2016-02-29", day_before(UNIX_EPOCH + Duration::from_secs(1_456_790_400)));
+        assert_eq!("1970-01-01", day_before(UNIX_EPOCH + Duration::from_secs(86_400 + 3_600)));
+        assert_eq!("2000-12-31", day_before(UNIX_EPOCH + Duration::from_secs(978_307_200)));
+    }
+}
@@ -0,0 +1,326 @@
+use crate::resources::artist::Artist;
+use crate::resources::event::{Event, When};
+use crate::resources::ids::{EventId, VenueId};
+use crate::resources::venue::Venue;
+use crate::sync::{Subscription, SyncState, SyncStore};
+use crate::SkResult;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS artists (
+    id INTEGER PRIMARY KEY,
+    display_name TEXT NOT NULL,
+    uri TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS venues (
+    id INTEGER PRIMARY KEY,
+    display_name TEXT,
+    metro_area_id INTEGER,
+    metro_area TEXT,
+    country TEXT,
+    lat REAL,
+    lng REAL,
+    uri TEXT
+);
+CREATE TABLE IF NOT EXISTS events (
+    id INTEGER PRIMARY KEY,
+    display_name TEXT NOT NULL,
+    type TEXT NOT NULL,
+    status TEXT NOT NULL,
+    start_date TEXT,
+    start_time TEXT,
+    start_datetime TEXT,
+    end_date TEXT,
+    venue_id INTEGER REFERENCES venues (id),
+    uri TEXT NOT NULL,
+    popularity REAL NOT NULL,
+    synced_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE TABLE IF NOT EXISTS performances (
+    event_id INTEGER NOT NULL REFERENCES events (id) ON DELETE CASCADE,
+    artist_id INTEGER NOT NULL REFERENCES artists (id),
+    billing TEXT NOT NULL,
+    billing_index INTEGER NOT NULL,
+    PRIMARY KEY (event_id, artist_id)
+);
+CREATE TABLE IF NOT EXISTS sync_state (
+    subscription TEXT PRIMARY KEY,
+    last_run INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS sync_seen (
+    subscription TEXT NOT NULL REFERENCES sync_state (subscription) ON DELETE CASCADE,
+    event_id INTEGER NOT NULL,
+    PRIMARY KEY (subscription, event_id)
+);
+CREATE INDEX IF NOT EXISTS events_start_date ON events (start_date);
+CREATE INDEX IF NOT EXISTS performances_artist ON performances (artist_id);
+";
+
+/// SQLite database holding the synced events, with their venues and artists
+///
+/// Tables are `events`, `venues`, `artists` and `performances`, the line-up
+/// of each event, plus `sync_state` and `sync_seen` for the state of the
+/// subscriptions; they are created on open when missing.
+pub struct SqliteStore {
+    conn: Connection,
+}
+
+impl SqliteStore {
+    /// Open, or create, the database at `path`
+    pub fn open<P: AsRef<Path>>(path: P) -> SkResult<SqliteStore> {
+        SqliteStore::with_connection(Connection::open(path)?)
+    }
+
+    /// Database living in memory only, dropped with the store
+    pub fn in_memory() -> SkResult<SqliteStore> {
+        SqliteStore::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> SkResult<SqliteStore> {
+        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(SqliteStore { conn })
+    }
+
+    /// Connection to the database, e.g. to query the synced events
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+}
+
+impl SyncStore for SqliteStore {
+    fn upsert_event(&mut self, event: &Event) -> SkResult<bool> {
+        let tx = self.conn.transaction()?;
+        let venue_id = upsert_venue(&tx, &event.venue)?;
+        let created = tx
+            .query_row("SELECT 1 FROM events WHERE id = ?1", params![event.id.0], |_| Ok(()))
+            .optional()?
+            .is_none();
+        tx.execute(
+            "INSERT INTO events (id, display_name, type, status, start_date, start_time, start_datetime,
+                                 end_date, venue_id, uri, popularity)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+             ON CONFLICT (id) DO UPDATE SET
+                 display_name = excluded.display_name, type = excluded.type, status = excluded.status,
+                 start_date = excluded.start_date, start_time = excluded.start_time,
+                 start_datetime = excluded.start_datetime, end_date = excluded.end_date,
+                 venue_id = excluded.venue_id, uri = excluded.uri, popularity = excluded.popularity,
+                 synced_at = CURRENT_TIMESTAMP",
+            params![
+                event.id.0,
+                event.display_name,
+                event.event_type,
+                event.status,
+                date(&event.start),
+                time(&event.start),
+                datetime(&event.start),
+                event.end.as_ref().and_then(date),
+                venue_id.map(|id| id.0),
+                event.uri,
+                event.popularity,
+            ],
+        )?;
+        tx.execute("DELETE FROM performances WHERE event_id = ?1", params![event.id.0])?;
+        for performance in &event.performances {
+            tx.execute(
+                "INSERT OR REPLACE INTO performances (event_id, artist_id, billing, billing_index)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    event.id.0,
+                    performance.artist.id.0,
+                    performance.billing,
+                    performance.billing_index,
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(created)
+    }
+
+    fn upsert_artist(&mut self, artist: &Artist) -> SkResult<()> {
+        self.conn.execute(
+            "INSERT INTO artists (id, display_name, uri) VALUES (?1, ?2, ?3)
+             ON CONFLICT (id) DO UPDATE SET display_name = excluded.display_name, uri = excluded.uri",
+            params![artist.id.0, artist.display_name, artist.uri],
+        )?;
+        Ok(())
+    }
+
+    fn mark_seen(&mut self, subscription: Subscription, events: &[EventId]) -> SkResult<()> {
+        let key = subscription.to_string();
+        let last_run = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO sync_state (subscription, last_run) VALUES (?1, ?2)
+             ON CONFLICT (subscription) DO UPDATE SET last_run = excluded.last_run",
+            params![key, last_run],
+        )?;
+        tx.execute("DELETE FROM sync_seen WHERE subscription = ?1", params![key])?;
+        for id in events {
+            tx.execute(
+                "INSERT OR IGNORE INTO sync_seen (subscription, event_id) VALUES (?1, ?2)",
+                params![key, id.0],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn load_state(&mut self, subscription: Subscription) -> SkResult<Option<SyncState>> {
+        let key = subscription.to_string();
+        let last_run: Option<u64> = self
+            .conn
+            .query_row("SELECT last_run FROM sync_state WHERE subscription = ?1", params![key], |row| row.get(0))
+            .optional()?;
+        let last_run = match last_run {
+            Some(secs) => UNIX_EPOCH + Duration::from_secs(secs),
+            None => return Ok(None),
+        };
+        let mut stmt = self
+            .conn
+            .prepare("SELECT event_id FROM sync_seen WHERE subscription = ?1 ORDER BY event_id")?;
+        let seen = stmt
+            .query_map(params![key], |row| row.get(0).map(EventId))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Some(SyncState::new(last_run, seen)))
+    }
+}
+
+/// Venues without an ID are not stored
+fn upsert_venue(conn: &Connection, venue: &Venue) -> SkResult<Option<VenueId>> {
+    let id = match venue.id {
+        Some(id) => id,
+        None => return Ok(None),
+    };
+    let metro_area = venue.metro_area.as_ref();
+    conn.execute(
+        "INSERT INTO venues (id, display_name, metro_area_id, metro_area, country, lat, lng, uri)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+         ON CONFLICT (id) DO UPDATE SET
+             display_name = excluded.display_name, metro_area_id = excluded.metro_area_id,
+             metro_area = excluded.metro_area, country = excluded.country,
+             lat = excluded.lat, lng = excluded.lng, uri = excluded.uri",
+        params![
+            id.0,
+            venue.display_name,
+            metro_area.map(|metro_area| metro_area.id.0),
+            metro_area.map(|metro_area| &metro_area.display_name),
+            metro_area.map(|metro_area| &metro_area.country.display_name),
+            venue.lat,
+            venue.lng,
+            venue.uri,
+        ],
+    )?;
+    Ok(Some(id))
+}
+
+fn date(when: &When) -> Option<String> {
+    when.date.clone()
+}
+
+fn time(when: &When) -> Option<String> {
+    when.time.clone()
+}
+
+fn datetime(when: &When) -> Option<String> {
+    when.datetime.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::event::Event;
+    use crate::result::SkResultSet;
+    use crate::resources::ids::ArtistId;
+    use crate::sync::store_events;
+    use serde_json::Value;
+    use std::fs::File;
+    use std::io::Read;
+
+    fn load_events(path: &str) -> Vec<Event> {
+        let mut file = File::open(path).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+        let json: Value = serde_json::from_str(&content).unwrap();
+        SkResultSet::<Event>::from_json(&json).unwrap().collect()
+    }
+
+    fn count(store: &SqliteStore, table: &str) -> u64 {
+        store
+            .connection()
+            .query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn store_calendar() {
+        let events = load_events("fixtures/event/artist-324967-calendar.json");
+        let mut store = SqliteStore::in_memory().unwrap();
+
+        let report = store_events(&mut store, &events).unwrap();
+        assert_eq!(events.len() as u64, report.created);
+        assert_eq!(0, report.updated);
+        assert_eq!(events.len() as u64, count(&store, "events"));
+        assert!(count(&store, "venues") > 0);
+        assert!(count(&store, "artists") > 0);
+
+        let status: String = store
+            .connection()
+            .query_row("SELECT status FROM events WHERE id = ?1", params![events[0].id.0], |row| row.get(0))
+            .unwrap();
+        assert_eq!(events[0].status, status);
+    }
+
+    #[test]
+    fn store_again_updates() {
+        let events = load_events("fixtures/event/artist-324967-calendar.json");
+        let mut store = SqliteStore::in_memory().unwrap();
+        store_events(&mut store, &events).unwrap();
+        let performances = count(&store, "performances");
+
+        let report = store_events(&mut store, &events).unwrap();
+        assert_eq!(0, report.created);
+        assert_eq!(events.len() as u64, report.updated);
+        assert_eq!(events.len() as u64, count(&store, "events"));
+        assert_eq!(performances, count(&store, "performances"));
+    }
+
+    #[test]
+    fn store_festival_line_up() {
+        let events = load_events("fixtures/event/single-event-festival-27081999.json");
+        let mut store = SqliteStore::in_memory().unwrap();
+        store_events(&mut store, &events).unwrap();
+
+        assert_eq!(events[0].performances.len() as u64, count(&store, "performances"));
+        let headliner: String = store
+            .connection()
+            .query_row(
+                "SELECT artists.display_name FROM performances JOIN artists ON artists.id = performances.artist_id
+                 WHERE performances.event_id = ?1 ORDER BY billing_index LIMIT 1",
+                params![events[0].id.0],
+                |row| row.get(0),
+            )
+            .unwrap();
+        let first = events[0].performances.iter().min_by_key(|p| p.billing_index).unwrap();
+        assert_eq!(first.artist.display_name, headliner);
+    }
+
+    #[test]
+    fn sync_state_round_trip() {
+        let mut store = SqliteStore::in_memory().unwrap();
+        let subscription = Subscription::ArtistCalendar(ArtistId(324967));
+        assert_eq!(None, store.load_state(subscription).unwrap());
+
+        store.mark_seen(subscription, &[EventId(2), EventId(1)]).unwrap();
+        store.mark_seen(subscription, &[EventId(3), EventId(1)]).unwrap();
+        let state = store.load_state(subscription).unwrap().unwrap();
+        assert_eq!(vec![EventId(1), EventId(3)], state.seen);
+        assert!(state.last_run <= SystemTime::now());
+
+        assert_eq!(None, store.load_state(Subscription::ArtistGigography(ArtistId(324967))).unwrap());
+    }
+}
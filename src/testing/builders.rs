@@ -0,0 +1,465 @@
+use crate::resources::artist::Artist;
+use crate::resources::event::Event;
+use crate::resources::venue::Venue;
+use crate::resources::Resource;
+use serde_json::{json, Map, Value};
+
+/// Link of a resource on songkick.com
+fn songkick_uri(kind: &str, id: u64, name: &str) -> String {
+    let slug: String = name
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    format!("http://www.songkick.com/{}/{}-{}", kind, id, slug)
+}
+
+/// Resource of `json`, which the builders make valid
+fn parse<M: Resource>(json: Value) -> M {
+    M::from_json(&json).unwrap_or_else(|err| panic!("invalid built resource {}: {}", json, err))
+}
+
+/// Builder of the [`Artist`] values of tests, Placebo unless told otherwise
+///
+/// ```rust
+/// use songkick::testing::ArtistBuilder;
+///
+/// let artist = ArtistBuilder::new().id(253846).name("Radiohead").build();
+/// assert_eq!("Radiohead", artist.display_name);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ArtistBuilder {
+    id: u64,
+    name: String,
+    mbids: Vec<String>,
+    on_tour_until: Option<String>,
+}
+
+impl Default for ArtistBuilder {
+    fn default() -> ArtistBuilder {
+        ArtistBuilder::new()
+    }
+}
+
+impl ArtistBuilder {
+    pub fn new() -> ArtistBuilder {
+        ArtistBuilder {
+            id: 324967,
+            name: String::from("Placebo"),
+            mbids: Vec::new(),
+            on_tour_until: None,
+        }
+    }
+
+    pub fn id(mut self, id: u64) -> ArtistBuilder {
+        self.id = id;
+        self
+    }
+
+    pub fn name<T: Into<String>>(mut self, name: T) -> ArtistBuilder {
+        self.name = name.into();
+        self
+    }
+
+    /// Add a MusicBrainz identifier
+    pub fn mbid<T: Into<String>>(mut self, mbid: T) -> ArtistBuilder {
+        self.mbids.push(mbid.into());
+        self
+    }
+
+    /// Date until the artist is on tour, as `YYYY-MM-DD`
+    pub fn on_tour_until<T: Into<String>>(mut self, date: T) -> ArtistBuilder {
+        self.on_tour_until = Some(date.into());
+        self
+    }
+
+    /// The artist as returned by the API
+    pub fn json(&self) -> Value {
+        let identifiers: Vec<Value> = self
+            .mbids
+            .iter()
+            .map(|mbid| {
+                json!({
+                    "href": format!("http://api.songkick.com/api/3.0/artists/mbid:{}.json", mbid),
+                    "mbid": mbid,
+                })
+            })
+            .collect();
+        json!({
+            "id": self.id,
+            "displayName": self.name,
+            "uri": songkick_uri("artists", self.id, &self.name),
+            "identifier": identifiers,
+            "onTourUntil": self.on_tour_until,
+        })
+    }
+
+    pub fn build(&self) -> Artist {
+        parse(self.json())
+    }
+}
+
+/// Builder of the [`Venue`] values of tests, the O2 Academy Brixton of London unless told otherwise
+///
+/// ```rust
+/// use songkick::testing::VenueBuilder;
+///
+/// let venue = VenueBuilder::new().name("Cirkus").metro_area(32252, "Stockholm", "Sweden").build();
+/// assert_eq!("Cirkus, Stockholm", venue.to_string());
+/// ```
+#[derive(Debug, Clone)]
+pub struct VenueBuilder {
+    id: Option<u64>,
+    name: String,
+    metro_area: (u64, String, String),
+    coordinates: Option<(f64, f64)>,
+    capacity: Option<u64>,
+}
+
+impl Default for VenueBuilder {
+    fn default() -> VenueBuilder {
+        VenueBuilder::new()
+    }
+}
+
+impl VenueBuilder {
+    pub fn new() -> VenueBuilder {
+        VenueBuilder {
+            id: Some(17522),
+            name: String::from("O2 Academy Brixton"),
+            metro_area: (24426, String::from("London"), String::from("UK")),
+            coordinates: Some((51.4651252, -0.1150486)),
+            capacity: None,
+        }
+    }
+
+    pub fn id(mut self, id: u64) -> VenueBuilder {
+        self.id = Some(id);
+        self
+    }
+
+    /// Venue unknown to SongKick, without ID nor coordinates
+    pub fn unknown(mut self) -> VenueBuilder {
+        self.id = None;
+        self.coordinates = None;
+        self
+    }
+
+    pub fn name<T: Into<String>>(mut self, name: T) -> VenueBuilder {
+        self.name = name.into();
+        self
+    }
+
+    /// Metro Area of the venue, with the name of its country
+    pub fn metro_area<N: Into<String>, C: Into<String>>(mut self, id: u64, name: N, country: C) -> VenueBuilder {
+        self.metro_area = (id, name.into(), country.into());
+        self
+    }
+
+    pub fn coordinates(mut self, lat: f64, lng: f64) -> VenueBuilder {
+        self.coordinates = Some((lat, lng));
+        self
+    }
+
+    pub fn capacity(mut self, capacity: u64) -> VenueBuilder {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// City of the venue, as in the location of its events
+    fn city(&self) -> String {
+        format!("{}, {}", self.metro_area.1, self.metro_area.2)
+    }
+
+    /// The venue as returned by the API
+    pub fn json(&self) -> Value {
+        let (metro_id, ref metro_name, ref country) = self.metro_area;
+        let mut venue = Map::new();
+        venue.insert(String::from("id"), json!(self.id));
+        venue.insert(String::from("displayName"), json!(self.name));
+        venue.insert(
+            String::from("uri"),
+            json!(self.id.map(|id| songkick_uri("venues", id, &self.name))),
+        );
+        venue.insert(String::from("lat"), json!(self.coordinates.map(|(lat, _)| lat)));
+        venue.insert(String::from("lng"), json!(self.coordinates.map(|(_, lng)| lng)));
+        venue.insert(
+            String::from("metroArea"),
+            json!({
+                "id": metro_id,
+                "displayName": metro_name,
+                "uri": songkick_uri("metro_areas", metro_id, &format!("{} {}", country, metro_name)),
+                "country": { "displayName": country },
+            }),
+        );
+        if let Some(capacity) = self.capacity {
+            venue.insert(String::from("capacity"), json!(capacity));
+        }
+        Value::Object(venue)
+    }
+
+    pub fn build(&self) -> Venue {
+        parse(self.json())
+    }
+}
+
+/// Builder of the [`Event`] values of tests
+///
+/// Unless told otherwise the event is a concert of Placebo at the O2 Academy Brixton, on
+/// the 1st of June 2030: every field can be overridden, and the display name follows the
+/// headliner, the venue and the date.
+///
+/// ```rust
+/// use songkick::testing::{ArtistBuilder, EventBuilder, VenueBuilder};
+///
+/// let event = EventBuilder::new()
+///     .id(42)
+///     .date("2030-07-14")
+///     .headliner(ArtistBuilder::new().name("Radiohead"))
+///     .support(ArtistBuilder::new().id(1).name("Caribou"))
+///     .venue(VenueBuilder::new().name("Roundhouse"))
+///     .status("postponed")
+///     .build();
+/// assert_eq!("Radiohead at Roundhouse (2030-07-14)", event.display_name);
+/// assert_eq!(2, event.performances.len());
+/// ```
+#[derive(Debug, Clone)]
+pub struct EventBuilder {
+    id: u64,
+    event_type: String,
+    display_name: Option<String>,
+    status: String,
+    popularity: f64,
+    age_restriction: Option<String>,
+    date: String,
+    time: Option<String>,
+    end_date: Option<String>,
+    series: Option<String>,
+    venue: VenueBuilder,
+    performers: Vec<ArtistBuilder>,
+}
+
+impl Default for EventBuilder {
+    fn default() -> EventBuilder {
+        EventBuilder::new()
+    }
+}
+
+impl EventBuilder {
+    pub fn new() -> EventBuilder {
+        EventBuilder {
+            id: 1,
+            event_type: String::from("Concert"),
+            display_name: None,
+            status: String::from("ok"),
+            popularity: 0.05,
+            age_restriction: None,
+            date: String::from("2030-06-01"),
+            time: Some(String::from("20:00:00")),
+            end_date: None,
+            series: None,
+            venue: VenueBuilder::new(),
+            performers: vec![ArtistBuilder::new()],
+        }
+    }
+
+    pub fn id(mut self, id: u64) -> EventBuilder {
+        self.id = id;
+        self
+    }
+
+    pub fn display_name<T: Into<String>>(mut self, name: T) -> EventBuilder {
+        self.display_name = Some(name.into());
+        self
+    }
+
+    /// Status of the event, e.g. `ok`, `cancelled` or `postponed`
+    pub fn status<T: Into<String>>(mut self, status: T) -> EventBuilder {
+        self.status = status.into();
+        self
+    }
+
+    pub fn popularity(mut self, popularity: f64) -> EventBuilder {
+        self.popularity = popularity;
+        self
+    }
+
+    pub fn age_restriction<T: Into<String>>(mut self, age_restriction: T) -> EventBuilder {
+        self.age_restriction = Some(age_restriction.into());
+        self
+    }
+
+    /// Day of the event, as `YYYY-MM-DD`
+    pub fn date<T: Into<String>>(mut self, date: T) -> EventBuilder {
+        self.date = date.into();
+        self
+    }
+
+    /// Start time of the event, as `HH:MM:SS`, at the `+0100` offset
+    pub fn time<T: Into<String>>(mut self, time: T) -> EventBuilder {
+        self.time = Some(time.into());
+        self
+    }
+
+    /// Event without a known start time
+    pub fn no_time(mut self) -> EventBuilder {
+        self.time = None;
+        self
+    }
+
+    /// Festival of the `series`, from the date of the event to `end_date`
+    pub fn festival<S: Into<String>, T: Into<String>>(mut self, series: S, end_date: T) -> EventBuilder {
+        self.event_type = String::from("Festival");
+        self.series = Some(series.into());
+        self.end_date = Some(end_date.into());
+        self
+    }
+
+    pub fn venue(mut self, venue: VenueBuilder) -> EventBuilder {
+        self.venue = venue;
+        self
+    }
+
+    /// Replace the performers by `artist`, as the headliner
+    pub fn headliner(mut self, artist: ArtistBuilder) -> EventBuilder {
+        self.performers = vec![artist];
+        self
+    }
+
+    /// Add `artist` as a support act
+    pub fn support(mut self, artist: ArtistBuilder) -> EventBuilder {
+        self.performers.push(artist);
+        self
+    }
+
+    /// The event as returned by the API
+    pub fn json(&self) -> Value {
+        let display_name = self.display_name.clone().unwrap_or_else(|| match self.series {
+            Some(ref series) => series.clone(),
+            None => {
+                let headliner = self.performers.first().map(|artist| artist.name.as_str()).unwrap_or("Unknown");
+                format!("{} at {} ({})", headliner, self.venue.name, self.date)
+            }
+        });
+        let performances: Vec<Value> = self
+            .performers
+            .iter()
+            .enumerate()
+            .map(|(i, artist)| {
+                json!({
+                    "id": self.id * 100 + i as u64,
+                    "displayName": artist.name,
+                    "billing": if i == 0 { "headline" } else { "support" },
+                    "billingIndex": i + 1,
+                    "artist": artist.json(),
+                })
+            })
+            .collect();
+        let venue = self.venue.json();
+        let kind = if self.series.is_some() { "festivals" } else { "concerts" };
+
+        let mut event = Map::new();
+        event.insert(String::from("id"), json!(self.id));
+        event.insert(String::from("type"), json!(self.event_type));
+        event.insert(String::from("status"), json!(self.status));
+        event.insert(String::from("displayName"), json!(display_name));
+        event.insert(String::from("uri"), json!(songkick_uri(kind, self.id, &display_name)));
+        event.insert(String::from("popularity"), json!(self.popularity));
+        event.insert(String::from("ageRestriction"), json!(self.age_restriction));
+        event.insert(
+            String::from("start"),
+            json!({
+                "date": self.date,
+                "time": self.time,
+                "datetime": self.time.as_ref().map(|time| format!("{}T{}+0100", self.date, time)),
+            }),
+        );
+        if let Some(ref end_date) = self.end_date {
+            event.insert(String::from("end"), json!({ "date": end_date, "time": null, "datetime": null }));
+        }
+        if let Some(ref series) = self.series {
+            event.insert(String::from("series"), json!({ "displayName": series }));
+        }
+        event.insert(
+            String::from("location"),
+            json!({ "city": self.venue.city(), "lat": venue["lat"], "lng": venue["lng"] }),
+        );
+        event.insert(String::from("venue"), venue);
+        event.insert(String::from("performance"), Value::Array(performances));
+        Value::Object(event)
+    }
+
+    pub fn build(&self) -> Event {
+        parse(self.json())
+    }
+}
+
+/// Calendar page of the API listing `events`, to serve with a
+/// [`MockTransport`](crate::testing::MockTransport)
+pub fn results_page(events: &[EventBuilder]) -> Value {
+    json!({
+        "resultsPage": {
+            "status": "ok",
+            "results": { "event": events.iter().map(EventBuilder::json).collect::<Vec<_>>() },
+            "perPage": 50,
+            "page": 1,
+            "totalEntries": events.len(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::ids::{ArtistId, EventId, VenueId};
+    use crate::result::SkResultSet;
+
+    #[test]
+    fn default_event() {
+        let event = EventBuilder::new().build();
+        assert_eq!(EventId(1), event.id);
+        assert_eq!("Placebo at O2 Academy Brixton (2030-06-01)", event.display_name);
+        assert_eq!("ok", event.status);
+        assert_eq!(ArtistId(324967), event.performances[0].artist.id);
+        assert_eq!(Some(VenueId(17522)), event.venue.id);
+        assert_eq!("London, UK", event.location.as_ref().unwrap().city);
+        assert!(event.end.is_none());
+        assert!(event.unknown_fields().is_empty());
+    }
+
+    #[test]
+    fn festival_event() {
+        let event = EventBuilder::new()
+            .festival("Reading Festival", "2030-08-30")
+            .date("2030-08-28")
+            .no_time()
+            .support(ArtistBuilder::new().id(595).name("Ash").mbid("b4b3f4d5-8c58-4a1a-9a0b-21c7c8c6b3ce"))
+            .venue(VenueBuilder::new().unknown().name("Little John's Farm").capacity(90000))
+            .build();
+        assert_eq!("Festival", event.event_type);
+        assert_eq!("Reading Festival", event.display_name);
+        assert_eq!("Reading Festival", event.series.unwrap().display_name);
+        assert!(event.start.time.is_none() && event.start.datetime.is_none());
+        assert!(event.end.unwrap().date.is_some());
+        assert_eq!("support", event.performances[1].billing);
+        assert_eq!(1, event.performances[1].artist.identifiers.len());
+        assert_eq!(None, event.venue.id);
+        assert_eq!(Some(90000), event.venue.capacity);
+    }
+
+    #[test]
+    fn page_of_events() {
+        let events = [EventBuilder::new().id(1), EventBuilder::new().id(2).status("cancelled")];
+        let page: Vec<Event> = SkResultSet::from_json(&results_page(&events)).unwrap().collect();
+        assert_eq!(2, page.len());
+        assert_eq!("cancelled", page[1].status);
+    }
+
+    #[test]
+    fn artist_uri() {
+        let artist = ArtistBuilder::new().id(253846).name("Radiohead").on_tour_until("2030-12-31").build();
+        assert_eq!("http://www.songkick.com/artists/253846-radiohead", artist.uri);
+        assert_eq!(Some("2030-12-31"), artist.on_tour_until.as_deref());
+    }
+}
@@ -0,0 +1,250 @@
+use crate::error::{redact_url, SkError};
+use crate::transport::{HttpResponse, Transport};
+use crate::SkResult;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+/// Environment variable forcing [`Cassette::new`] to record, e.g. `SONGKICK_RECORD=1 cargo test`
+pub const RECORD_ENV: &str = "SONGKICK_RECORD";
+
+/// Headers never written to a cassette
+const SKIPPED_HEADERS: &[&str] = &["set-cookie"];
+
+/// A request and its response, as stored in a cassette
+#[derive(Clone, Serialize, Deserialize)]
+struct Interaction {
+    /// Requested URL, with the API key masked
+    url: String,
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Tape {
+    interactions: Vec<Interaction>,
+}
+
+enum Mode {
+    Record(Box<dyn Transport>),
+    /// Whether each interaction was replayed already
+    Replay(Vec<bool>),
+}
+
+struct State {
+    tape: Tape,
+    mode: Mode,
+}
+
+/// [`Transport`] recording responses to a JSON file, then replaying them
+///
+/// While recording, requests go through the wrapped transport and every interaction is
+/// appended to the cassette file, the API key masked out of the URLs and bodies. While
+/// replaying, requests are answered from the file without any network: a URL recorded
+/// several times gets its responses in the recorded order, then the last one again.
+///
+/// ```rust,no_run
+/// use songkick::testing::Cassette;
+/// use songkick::transport::ReqwestTransport;
+/// use songkick::SongKickBuilder;
+///
+/// // Records on the first run, or with SONGKICK_RECORD set, replays afterwards
+/// let cassette = Cassette::new("tests/cassettes/radiohead-calendar.json", ReqwestTransport::new()).unwrap();
+/// let key = std::env::var("SONGKICK_API_KEY").unwrap_or_default();
+/// let sk = SongKickBuilder::new(key).transport(cassette).build();
+///
+/// // RadioHead ID
+/// let events = sk.artist.calendar_all(253846).unwrap();
+/// ```
+pub struct Cassette {
+    path: PathBuf,
+    state: Mutex<State>,
+}
+
+impl Cassette {
+    /// Replay the cassette at `path`, recording it through `transport` when missing or when
+    /// [`RECORD_ENV`] is set
+    pub fn new<P, T>(path: P, transport: T) -> SkResult<Cassette>
+    where
+        P: Into<PathBuf>,
+        T: Transport + 'static,
+    {
+        let path = path.into();
+        let record = env::var_os(RECORD_ENV).is_some_and(|value| !value.is_empty());
+        if record || !path.exists() {
+            Cassette::record(path, transport)
+        } else {
+            Cassette::replay(path)
+        }
+    }
+
+    /// Record a new cassette at `path` through `transport`, replacing any previous one
+    pub fn record<P, T>(path: P, transport: T) -> SkResult<Cassette>
+    where
+        P: Into<PathBuf>,
+        T: Transport + 'static,
+    {
+        let cassette = Cassette {
+            path: path.into(),
+            state: Mutex::new(State {
+                tape: Tape::default(),
+                mode: Mode::Record(Box::new(transport)),
+            }),
+        };
+        if let Some(dir) = cassette.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        cassette.save(&cassette.lock().tape)?;
+        Ok(cassette)
+    }
+
+    /// Replay the cassette at `path`
+    pub fn replay<P: Into<PathBuf>>(path: P) -> SkResult<Cassette> {
+        let path = path.into();
+        let content = fs::read_to_string(&path)
+            .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))?;
+        let tape: Tape = serde_json::from_str(&content)
+            .map_err(|err| SkError::InvalidInput(format!("invalid cassette {}: {}", path.display(), err)))?;
+        let replayed = vec![false; tape.interactions.len()];
+        Ok(Cassette {
+            path,
+            state: Mutex::new(State {
+                tape,
+                mode: Mode::Replay(replayed),
+            }),
+        })
+    }
+
+    /// Path of the cassette file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn save(&self, tape: &Tape) -> SkResult<()> {
+        fs::write(&self.path, serde_json::to_string_pretty(tape)?)?;
+        Ok(())
+    }
+}
+
+impl Transport for Cassette {
+    fn get(&self, url: &str) -> SkResult<HttpResponse> {
+        let mut state = self.lock();
+        let State { tape, mode } = &mut *state;
+        match mode {
+            Mode::Record(transport) => {
+                let response = transport.get(url)?;
+                tape.interactions.push(record(url, &response));
+                self.save(tape)?;
+                Ok(response)
+            }
+            Mode::Replay(replayed) => {
+                let url = redact_url(url);
+                let matching: Vec<usize> = (0..tape.interactions.len())
+                    .filter(|&i| tape.interactions[i].url == url)
+                    .collect();
+                let i = matching
+                    .iter()
+                    .copied()
+                    .find(|&i| !replayed[i])
+                    .or_else(|| matching.last().copied())
+                    .ok_or_else(|| {
+                        SkError::InvalidInput(format!("no response for {} in cassette {}", url, self.path.display()))
+                    })?;
+                replayed[i] = true;
+                let interaction = &tape.interactions[i];
+                Ok(HttpResponse {
+                    status: interaction.status,
+                    headers: interaction.headers.clone(),
+                    body: interaction.body.clone(),
+                })
+            }
+        }
+    }
+}
+
+/// Interaction of `response` to `url`, without the API key of `url`
+fn record(url: &str, response: &HttpResponse) -> Interaction {
+    let key = url::Url::parse(url)
+        .ok()
+        .and_then(|url| url.query_pairs().find(|(name, _)| name == "apikey").map(|(_, key)| key.into_owned()))
+        .filter(|key| !key.is_empty());
+    let scrub = |text: &str| match key {
+        Some(ref key) => text.replace(key.as_str(), "REDACTED"),
+        None => String::from(text),
+    };
+    Interaction {
+        url: redact_url(url),
+        status: response.status,
+        headers: response
+            .headers
+            .iter()
+            .filter(|(name, _)| !SKIPPED_HEADERS.iter().any(|skipped| name.eq_ignore_ascii_case(skipped)))
+            .map(|(name, value)| (name.clone(), scrub(value)))
+            .collect(),
+        body: scrub(&response.body),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockTransport;
+
+    const CALENDAR: &str = "http://api.songkick.com/api/3.0/artists/1/calendar.json?apikey=SECRET&page=1";
+
+    fn cassette_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("songkick-cassette-{}-{}.json", name, std::process::id()))
+    }
+
+    #[test]
+    fn record_then_replay() {
+        let path = cassette_path("replay");
+        let mock = MockTransport::new()
+            .once("calendar", HttpResponse::new(503, "down"))
+            .on("calendar", HttpResponse::json("{\"echo\": \"SECRET\"}").with_header("Set-Cookie", "session=1"));
+        {
+            let cassette = Cassette::record(&path, mock.clone()).unwrap();
+            assert_eq!(503, cassette.get(CALENDAR).unwrap().status);
+            assert_eq!(200, cassette.get(CALENDAR).unwrap().status);
+        }
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("SECRET"));
+        assert!(!content.contains("session"));
+
+        let cassette = Cassette::replay(&path).unwrap();
+        // Any key replays the recorded responses
+        let url = CALENDAR.replace("SECRET", "OTHER");
+        assert_eq!(503, cassette.get(&url).unwrap().status);
+        let replayed = cassette.get(&url).unwrap();
+        assert_eq!("{\"echo\": \"REDACTED\"}", replayed.body);
+        assert_eq!(Some("application/json; charset=utf-8"), replayed.header("content-type"));
+        assert_eq!(200, cassette.get(&url).unwrap().status);
+        assert!(matches!(
+            cassette.get("http://api.songkick.com/api/3.0/events/1.json?apikey=KEY"),
+            Err(SkError::InvalidInput(_))
+        ));
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(2, mock.requests().len());
+    }
+
+    #[test]
+    fn new_records_missing_cassettes() {
+        let path = cassette_path("new");
+        let mock = MockTransport::new().on("calendar", HttpResponse::json("{}"));
+        let _ = fs::remove_file(&path);
+        Cassette::new(&path, mock.clone()).unwrap().get(CALENDAR).unwrap();
+        Cassette::new(&path, mock.clone()).unwrap().get(CALENDAR).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(1, mock.requests().len());
+    }
+}
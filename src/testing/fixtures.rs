@@ -0,0 +1,178 @@
+use crate::resources::TopLevelResource;
+use crate::result::SkResultSet;
+use crate::transport::HttpResponse;
+use crate::SkResult;
+use serde_json::Value;
+
+/// Response of the API bundled with the crate
+///
+/// The fixtures are real responses, trimmed and without personal data, covering every
+/// resource of the crate and the error envelope. They are a ground truth to check parsers
+/// against, these of the crate or others of the raw [`value`](Fixture::value):
+///
+/// ```rust
+/// use songkick::resources::Event;
+/// use songkick::testing::fixtures;
+///
+/// let events = fixtures::ARTIST_GIGOGRAPHY.load::<Event>().unwrap();
+/// assert_eq!(6, events.total_entries);
+///
+/// for fixture in fixtures::all() {
+///     let page = &fixture.value()["resultsPage"];
+///     assert!(page["status"] == "ok" || page["error"]["message"].is_string());
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fixture {
+    name: &'static str,
+    status: u16,
+    json: &'static str,
+}
+
+impl Fixture {
+    /// Name of the file of the fixture, e.g. `event/artist-324967-calendar.json`
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// HTTP status the API answers the fixture with
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Body of the response
+    pub fn json(&self) -> &'static str {
+        self.json
+    }
+
+    /// Body of the response, parsed
+    pub fn value(&self) -> Value {
+        serde_json::from_str(self.json).expect("invalid fixture")
+    }
+
+    /// Resources of the response, an error for the error envelopes
+    pub fn load<M: TopLevelResource>(&self) -> SkResult<SkResultSet<M>> {
+        SkResultSet::from_json(&self.value())
+    }
+
+    /// Response of the API, for a [`MockTransport`](crate::testing::MockTransport)
+    pub fn response(&self) -> HttpResponse {
+        HttpResponse {
+            status: self.status,
+            ..HttpResponse::json(self.json)
+        }
+    }
+}
+
+macro_rules! fixture {
+    ($status:expr, $name:expr) => {
+        Fixture {
+            name: $name,
+            status: $status,
+            json: include_str!(concat!("../../fixtures/", $name)),
+        }
+    };
+}
+
+/// A Placebo, as returned by [`ArtistEndpoint::get`](crate::endpoints::ArtistEndpoint::get)
+pub const ARTIST: Fixture = fixture!(200, "artist/single-artist-324967.json");
+/// Search of `placebo`, 10 artists
+pub const ARTIST_SEARCH: Fixture = fixture!(200, "artist/artist-search-placebo.json");
+/// Upcoming events of Placebo, 33 events
+pub const ARTIST_CALENDAR: Fixture = fixture!(200, "event/artist-324967-calendar.json");
+/// Past events of Placebo, 6 events, some festivals, postponed or cancelled
+pub const ARTIST_GIGOGRAPHY: Fixture = fixture!(200, "event/artist-324967-gigography.json");
+/// A festival, with dozens of performances, as returned by [`EventEndpoint::get`](crate::endpoints::EventEndpoint::get)
+pub const FESTIVAL: Fixture = fixture!(200, "event/single-event-festival-27081999.json");
+/// Search of the events of London, 3 events
+pub const EVENT_SEARCH: Fixture = fixture!(200, "event/event-search-london.json");
+/// Location search of the IP of a client, 2 cities
+pub const LOCATION_SEARCH: Fixture = fixture!(200, "location/location-search-clientip.json");
+/// Upcoming events of the London Metro Area, 4 events
+pub const METRO_AREA_CALENDAR: Fixture = fixture!(200, "metro_area/metro-area-24426-calendar.json");
+/// Calendar of the tracked artists of a user, 3 entries
+pub const USER_CALENDAR: Fixture = fixture!(200, "user/user-calendar-placebo-fan.json");
+/// Full representation of a Venue, with its address and capacity
+pub const VENUE: Fixture = fixture!(200, "venue/venue-17522.json");
+/// Error envelope of a request with an invalid API key
+pub const INVALID_KEY: Fixture = fixture!(401, "error/invalid-key.json");
+/// Error envelope of an unknown artist
+pub const NOT_FOUND: Fixture = fixture!(404, "error/artist-not-found.json");
+
+const ALL: &[Fixture] = &[
+    ARTIST,
+    ARTIST_SEARCH,
+    ARTIST_CALENDAR,
+    ARTIST_GIGOGRAPHY,
+    FESTIVAL,
+    EVENT_SEARCH,
+    LOCATION_SEARCH,
+    METRO_AREA_CALENDAR,
+    USER_CALENDAR,
+    VENUE,
+    INVALID_KEY,
+    NOT_FOUND,
+];
+
+/// Every bundled fixture
+pub fn all() -> &'static [Fixture] {
+    ALL
+}
+
+/// Fixture called `name`, see [`Fixture::name`]
+pub fn get(name: &str) -> Option<Fixture> {
+    ALL.iter().find(|fixture| fixture.name == name).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::ParseMode;
+    use crate::error::SkError;
+    use crate::resources::venue::Venue;
+    use crate::resources::{Artist, CalendarEntry, Event, LocationMatch};
+
+    /// Count of the resources of `fixture`, failing on fields not modelled by `M`
+    fn strict<M: TopLevelResource>(fixture: Fixture) -> SkResult<usize> {
+        SkResultSet::<M>::from_json_with(&fixture.value(), ParseMode::Strict).map(|results| results.count())
+    }
+
+    #[test]
+    fn fixtures_are_fully_modelled() {
+        assert_eq!(1, strict::<Artist>(ARTIST).unwrap());
+        assert_eq!(10, strict::<Artist>(ARTIST_SEARCH).unwrap());
+        assert_eq!(33, strict::<Event>(ARTIST_CALENDAR).unwrap());
+        assert_eq!(6, strict::<Event>(ARTIST_GIGOGRAPHY).unwrap());
+        assert_eq!(1, strict::<Event>(FESTIVAL).unwrap());
+        assert_eq!(3, strict::<Event>(EVENT_SEARCH).unwrap());
+        assert_eq!(2, strict::<LocationMatch>(LOCATION_SEARCH).unwrap());
+        assert_eq!(4, strict::<Event>(METRO_AREA_CALENDAR).unwrap());
+        assert_eq!(3, strict::<CalendarEntry>(USER_CALENDAR).unwrap());
+        assert_eq!(1, strict::<Venue>(VENUE).unwrap());
+    }
+
+    #[test]
+    fn full_venue() {
+        let venue = VENUE.load::<Venue>().unwrap().next().unwrap();
+        assert_eq!(Some(4921), venue.capacity);
+        assert_eq!(Some("SW9 9SL"), venue.zip.as_deref());
+        assert_eq!("London", venue.city.unwrap().display_name);
+    }
+
+    #[test]
+    fn error_envelopes() {
+        for fixture in &[INVALID_KEY, NOT_FOUND] {
+            assert!(!fixture.response().is_success());
+            assert!(matches!(fixture.load::<Artist>(), Err(SkError::Api { .. })));
+        }
+    }
+
+    #[test]
+    fn fixtures_by_name() {
+        assert_eq!(Some(VENUE), get("venue/venue-17522.json"));
+        assert_eq!(None, get("venue/unknown.json"));
+        let response = get("error/invalid-key.json").unwrap().response();
+        assert_eq!(Some("application/json; charset=utf-8"), response.header("content-type"));
+        assert!(all().iter().all(|fixture| fixture.value().get("resultsPage").is_some()));
+    }
+}
@@ -0,0 +1,254 @@
+//! Test doubles of the client, with the `testing` feature
+//!
+//! A [`MockTransport`] answers requests with canned responses, without any network:
+//!
+//! ```rust
+//! use songkick::testing::MockTransport;
+//! use songkick::transport::HttpResponse;
+//! use songkick::{Error, SongKickBuilder};
+//!
+//! let mock = MockTransport::new()
+//!     .once("artists/*/calendar.json", HttpResponse::new(429, "").with_header("Retry-After", "30"))
+//!     .on("artists/*/calendar.json", HttpResponse::json(r#"{"resultsPage": {"status": "ok", "results": {},
+//!         "perPage": 50, "page": 1, "totalEntries": 0}}"#));
+//! let sk = SongKickBuilder::new("API_KEY").transport(mock.clone()).build();
+//!
+//! let err = sk.artist.calendar(253846, None).err().unwrap();
+//! assert!(matches!(err.inner(), Error::RateLimited { .. }));
+//! assert_eq!(0, sk.artist.calendar(253846, None).unwrap().total_entries);
+//! assert_eq!(2, mock.requests().len());
+//! ```
+//!
+//! A [`MockSongKick`] serves such responses over HTTP on a local port, for end-to-end
+//! tests of a client pointed at it. A [`Cassette`] records the responses of the live API
+//! once, its key masked out, and replays them in later runs.
+//!
+//! The [`fixtures`] are responses of the API bundled with the crate, to test parsers against,
+//! and [`EventBuilder`], [`ArtistBuilder`] and [`VenueBuilder`] make resources with sensible
+//! defaults without writing any JSON.
+
+use crate::transport::{HttpResponse, Transport};
+use crate::SkResult;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex, MutexGuard};
+use url::Url;
+
+mod builders;
+mod cassette;
+pub mod fixtures;
+mod server;
+
+pub use crate::testing::builders::{results_page, ArtistBuilder, EventBuilder, VenueBuilder};
+pub use crate::testing::cassette::{Cassette, RECORD_ENV};
+pub use crate::testing::server::{MockSongKick, MOCK_API_KEY};
+
+/// Largest page size accepted by the API, and the default one
+const MAX_PER_PAGE: usize = 50;
+
+/// How a route answers
+#[derive(Clone)]
+enum Reply {
+    Fixed(HttpResponse),
+    /// Results listed under `marker`, split in pages as requested by the URL
+    Pages { marker: String, items: Vec<Value> },
+}
+
+impl Reply {
+    fn response(&self, url: &str) -> HttpResponse {
+        match self {
+            Reply::Fixed(response) => response.clone(),
+            Reply::Pages { marker, items } => page_of(url, marker, items),
+        }
+    }
+}
+
+/// A canned response and the URLs it answers
+struct Route {
+    pattern: String,
+    reply: Reply,
+    once: bool,
+}
+
+#[derive(Default)]
+struct Mock {
+    routes: Vec<Route>,
+    requests: Vec<String>,
+}
+
+/// [`Transport`] answering with canned responses, picked by URL pattern
+///
+/// Patterns match URLs containing them, a `*` standing for any text (e.g.
+/// `artists/*/calendar.json`). Routes are tried in the order they were added, URLs
+/// without any route get a 404 response. Clones share their routes and requests, so a
+/// clone kept by the test can inspect what the client requested.
+#[derive(Clone, Default)]
+pub struct MockTransport {
+    inner: Arc<Mutex<Mock>>,
+}
+
+impl MockTransport {
+    /// Mock without any route
+    pub fn new() -> MockTransport {
+        MockTransport::default()
+    }
+
+    /// Answer the URLs matching `pattern` with `response`
+    pub fn on(self, pattern: &str, response: HttpResponse) -> MockTransport {
+        self.route(pattern, Reply::Fixed(response), false)
+    }
+
+    /// Answer the first URL matching `pattern` with `response`, then remove the route
+    pub fn once(self, pattern: &str, response: HttpResponse) -> MockTransport {
+        self.route(pattern, Reply::Fixed(response), true)
+    }
+
+    /// Answer the URLs matching `pattern` with the page of `items` they request
+    ///
+    /// Pages follow the `page` and `per_page` parameters of the URL, the items are listed
+    /// under `marker` (e.g. `event`) like in the `resultsPage` of the API.
+    pub fn paginated(self, pattern: &str, marker: &str, items: Vec<Value>) -> MockTransport {
+        let reply = Reply::Pages {
+            marker: String::from(marker),
+            items,
+        };
+        self.route(pattern, reply, false)
+    }
+
+    /// URLs requested so far, in order
+    pub fn requests(&self) -> Vec<String> {
+        self.lock().requests.clone()
+    }
+
+    fn route(self, pattern: &str, reply: Reply, once: bool) -> MockTransport {
+        self.lock().routes.push(Route {
+            pattern: String::from(pattern),
+            reply,
+            once,
+        });
+        self
+    }
+
+    /// Response of the first route matching `url`, without recording the request
+    fn respond(&self, url: &str) -> Option<HttpResponse> {
+        let mut mock = self.lock();
+        let i = mock.routes.iter().position(|route| matches(&route.pattern, url))?;
+        let response = mock.routes[i].reply.response(url);
+        if mock.routes[i].once {
+            mock.routes.remove(i);
+        }
+        Some(response)
+    }
+
+    fn record(&self, url: &str) {
+        self.lock().requests.push(String::from(url));
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Mock> {
+        // A test panicking while holding the lock leaves consistent data
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Transport for MockTransport {
+    fn get(&self, url: &str) -> SkResult<HttpResponse> {
+        self.record(url);
+        Ok(self.respond(url).unwrap_or_else(|| not_found(url)))
+    }
+}
+
+/// Response to the URLs without route
+fn not_found(url: &str) -> HttpResponse {
+    HttpResponse::new(404, format!("no mock response for {}", url))
+}
+
+/// `resultsPage` of the page of `items` requested by `url`
+fn page_of(url: &str, marker: &str, items: &[Value]) -> HttpResponse {
+    let param = |name: &str| {
+        Url::parse(url)
+            .ok()?
+            .query_pairs()
+            .find(|(key, _)| key == name)
+            .and_then(|(_, value)| value.parse::<usize>().ok())
+    };
+    let page = param("page").unwrap_or(1).max(1);
+    let per_page = param("per_page").unwrap_or(MAX_PER_PAGE).clamp(1, MAX_PER_PAGE);
+    let start = (page - 1).saturating_mul(per_page).min(items.len());
+    let end = start.saturating_add(per_page).min(items.len());
+    let results = match &items[start..end] {
+        [] => json!({}),
+        slice => json!({ marker: slice }),
+    };
+    let body = json!({
+        "resultsPage": {
+            "status": "ok",
+            "results": results,
+            "perPage": per_page,
+            "page": page,
+            "totalEntries": items.len(),
+        }
+    });
+    HttpResponse::json(body.to_string())
+}
+
+/// Whether `url` contains the parts of `pattern` between its `*`, in order
+fn matches(pattern: &str, url: &str) -> bool {
+    let mut rest = url;
+    for part in pattern.split('*') {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_patterns() {
+        let url = "http://api.songkick.com/api/3.0/artists/253846/calendar.json?apikey=KEY&page=2";
+        assert!(matches("artists/253846/calendar.json", url));
+        assert!(matches("artists/*/calendar.json", url));
+        assert!(matches("*page=2", url));
+        assert!(matches("", url));
+        assert!(!matches("artists/*/gigography.json", url));
+        assert!(!matches("calendar.json*artists", url));
+    }
+
+    #[test]
+    fn canned_responses() {
+        let mock = MockTransport::new()
+            .once("calendar", HttpResponse::new(503, "down"))
+            .on("calendar", HttpResponse::json("{}"))
+            .on("gigography", HttpResponse::new(500, ""));
+
+        assert_eq!(503, mock.get("artists/1/calendar.json").unwrap().status);
+        assert_eq!(200, mock.get("artists/1/calendar.json").unwrap().status);
+        assert_eq!(200, mock.get("artists/1/calendar.json").unwrap().status);
+        assert_eq!(404, mock.get("artists/1.json").unwrap().status);
+        assert_eq!(4, mock.requests().len());
+        assert_eq!("artists/1.json", mock.requests()[3]);
+    }
+
+    #[test]
+    fn paginated_responses() {
+        let items: Vec<Value> = (1..=7).map(|id| json!({ "id": id })).collect();
+        let mock = MockTransport::new().paginated("gigography", "event", items);
+        let page = |url: &str| -> Value { serde_json::from_str(&mock.get(url).unwrap().body).unwrap() };
+
+        let first = page("http://localhost/artists/1/gigography.json?apikey=KEY&page=1&per_page=3");
+        assert_eq!(json!([{"id": 1}, {"id": 2}, {"id": 3}]), first["resultsPage"]["results"]["event"]);
+        assert_eq!(json!(7), first["resultsPage"]["totalEntries"]);
+
+        let last = page("http://localhost/artists/1/gigography.json?apikey=KEY&page=3&per_page=3");
+        assert_eq!(json!([{"id": 7}]), last["resultsPage"]["results"]["event"]);
+
+        let after = page("http://localhost/artists/1/gigography.json?apikey=KEY&page=9&per_page=3");
+        assert_eq!(json!({}), after["resultsPage"]["results"]);
+
+        let default = page("http://localhost/artists/1/gigography.json?apikey=KEY");
+        assert_eq!(7, default["resultsPage"]["results"]["event"].as_array().unwrap().len());
+    }
+}
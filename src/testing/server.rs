@@ -0,0 +1,244 @@
+use crate::client::{SongKick, SongKickBuilder};
+use crate::testing::fixtures::{self, Fixture};
+use crate::testing::{not_found, MockTransport};
+use crate::transport::HttpResponse;
+use serde_json::Value;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+/// API key of the clients of [`MockSongKick::client`]
+pub const MOCK_API_KEY: &str = "MOCK_API_KEY";
+
+/// Local HTTP server mimicking the SongKick API
+///
+/// Out of the box it answers every endpoint of the crate with realistic fixtures, whatever
+/// the requested IDs: the 33 events of a Placebo calendar for the calendars and gigographies,
+/// paginated as requested, a Placebo search, the calendar of a user, and so on. Routes added
+/// with [`on`](MockSongKick::on), [`once`](MockSongKick::once) and
+/// [`paginated`](MockSongKick::paginated) come before the fixtures.
+///
+/// The server stops when dropped.
+///
+/// ```rust
+/// use songkick::testing::MockSongKick;
+/// use songkick::transport::HttpResponse;
+/// use songkick::Error;
+///
+/// let server = MockSongKick::start();
+/// server.once("artists/1/calendar.json", HttpResponse::new(503, "Service Unavailable"));
+/// let sk = server.client();
+///
+/// let events = sk.artist.calendar_pages(324967).per_page(10).items();
+/// assert_eq!(33, events.map(Result::unwrap).count());
+///
+/// let err = sk.artist.calendar(1, None).err().unwrap();
+/// assert!(matches!(err.inner(), Error::Http { status: 503, .. }));
+/// assert_eq!(5, server.requests().len());
+/// ```
+pub struct MockSongKick {
+    addr: SocketAddr,
+    routes: MockTransport,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockSongKick {
+    /// Server answering with the fixtures
+    pub fn start() -> MockSongKick {
+        MockSongKick::with_fallback(fixtures())
+    }
+
+    /// Server without any route, answering 404 until some are added
+    pub fn empty() -> MockSongKick {
+        MockSongKick::with_fallback(MockTransport::new())
+    }
+
+    fn with_fallback(fallback: MockTransport) -> MockSongKick {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind the mock server");
+        let addr = listener.local_addr().expect("failed to read the address of the mock server");
+        let routes = MockTransport::new();
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let routes = routes.clone();
+            let stop = stop.clone();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        // A client hanging up is its own problem
+                        let _ = serve(stream, addr, &routes, &fallback);
+                    }
+                }
+            })
+        };
+        MockSongKick {
+            addr,
+            routes,
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// Root of the API on the server, to pass to [`SongKickBuilder::base_path`]
+    pub fn base_path(&self) -> String {
+        format!("http://{}/api/3.0", self.addr)
+    }
+
+    /// Client sending its requests to the server, with [`MOCK_API_KEY`]
+    pub fn client(&self) -> SongKick {
+        SongKickBuilder::new(MOCK_API_KEY).base_path(self.base_path()).build()
+    }
+
+    /// Answer the URLs matching `pattern` with `response`, see [`MockTransport`] for the patterns
+    pub fn on(&self, pattern: &str, response: HttpResponse) -> &MockSongKick {
+        self.routes.clone().on(pattern, response);
+        self
+    }
+
+    /// Answer the first URL matching `pattern` with `response`
+    pub fn once(&self, pattern: &str, response: HttpResponse) -> &MockSongKick {
+        self.routes.clone().once(pattern, response);
+        self
+    }
+
+    /// Answer the URLs matching `pattern` with the page of `items` they request
+    pub fn paginated(&self, pattern: &str, marker: &str, items: Vec<Value>) -> &MockSongKick {
+        self.routes.clone().paginated(pattern, marker, items);
+        self
+    }
+
+    /// URLs requested so far, in order
+    pub fn requests(&self) -> Vec<String> {
+        self.routes.requests()
+    }
+}
+
+impl Drop for MockSongKick {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // Wake up the server blocked on accept
+        let _ = TcpStream::connect(self.addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Routes of the fixtures, the more specific patterns first
+fn fixtures() -> MockTransport {
+    let events = results(fixtures::ARTIST_CALENDAR, "event");
+    MockTransport::new()
+        .on("/search/artists.json", fixtures::ARTIST_SEARCH.response())
+        .on("/search/locations.json", fixtures::LOCATION_SEARCH.response())
+        .paginated("/artists/*/calendar.json", "event", events.clone())
+        .paginated("/artists/*/gigography.json", "event", events.clone())
+        .paginated("/metro_areas/*/calendar.json", "event", events.clone())
+        .paginated("/venues/*/calendar.json", "event", events)
+        .paginated("/users/*/calendar.json", "calendarEntry", results(fixtures::USER_CALENDAR, "calendarEntry"))
+        .on("/artists/*.json", fixtures::ARTIST.response())
+        .on("/events/*.json", fixtures::FESTIVAL.response())
+        .on("/venues/*.json", fixtures::VENUE.response())
+}
+
+/// Results listed under `marker` in the `resultsPage` of `fixture`
+fn results(fixture: Fixture, marker: &str) -> Vec<Value> {
+    let mut data = fixture.value();
+    match data["resultsPage"]["results"][marker].take() {
+        Value::Array(items) => items,
+        _ => Vec::new(),
+    }
+}
+
+/// Answer the request on `stream`
+fn serve(mut stream: TcpStream, addr: SocketAddr, routes: &MockTransport, fallback: &MockTransport) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // The requests are GETs, without body
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim_end().is_empty() {
+            break;
+        }
+    }
+    let target = request_line.split_whitespace().nth(1).unwrap_or("/");
+    let url = format!("http://{}{}", addr, target);
+
+    routes.record(&url);
+    let response = routes
+        .respond(&url)
+        .or_else(|| fallback.respond(&url))
+        .unwrap_or_else(|| not_found(&url));
+
+    write!(stream, "HTTP/1.1 {} {}\r\n", response.status, reason(response.status))?;
+    for (name, value) in &response.headers {
+        write!(stream, "{}: {}\r\n", name, value)?;
+    }
+    write!(stream, "Content-Length: {}\r\nConnection: close\r\n\r\n", response.body.len())?;
+    stream.write_all(response.body.as_bytes())?;
+    stream.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Mock Response",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endpoints::CalendarReason;
+    use crate::error::SkError;
+
+    #[test]
+    fn serve_fixtures() {
+        let server = MockSongKick::start();
+        let sk = server.client();
+
+        assert_eq!("Placebo", sk.artist.get(324967).unwrap().next().unwrap().display_name);
+        assert_eq!(10, sk.artist.search_by_name("placebo").unwrap().count());
+        assert_eq!(33, sk.artist.gigography_pages(324967).per_page(7).items().count());
+        assert_eq!(3, sk.user.calendar_all("fan", CalendarReason::TrackedArtist).unwrap().len());
+        assert_eq!(24426, sk.metro_area.locate("clientip").unwrap().next().unwrap().metro_area.id.0);
+        assert!(sk.validate_key().is_ok());
+
+        let requests = server.requests();
+        assert_eq!(1 + 1 + 5 + 1 + 1 + 1, requests.len());
+        assert!(requests[0].ends_with("/api/3.0/artists/324967.json?apikey=MOCK_API_KEY"));
+    }
+
+    #[test]
+    fn routes_before_fixtures() {
+        let server = MockSongKick::start();
+        server
+            .once("artists/*/calendar.json", HttpResponse::new(429, "").with_header("Retry-After", "5"))
+            .on("events/1.json", HttpResponse::new(404, ""));
+        let sk = server.client();
+
+        let err = sk.artist.calendar(324967, None).err().unwrap();
+        assert!(matches!(err.inner(), SkError::RateLimited { .. }));
+        assert_eq!(33, sk.artist.calendar_all(324967).unwrap().len());
+        assert!(matches!(sk.event.get(1).err().unwrap().inner(), SkError::NotFound { .. }));
+        assert_eq!(1, sk.event.get(2).unwrap().count());
+    }
+
+    #[test]
+    fn empty_server() {
+        let server = MockSongKick::empty();
+        let err = server.client().artist.get(1).err().unwrap();
+        assert!(matches!(err.inner(), SkError::NotFound { .. }));
+    }
+}
@@ -0,0 +1,726 @@
+//! HTTP layer of the client
+//!
+//! Requests go through a [`Transport`], [`ReqwestTransport`] by default. Another one can
+//! be set with [`SongKickBuilder::transport`](crate::SongKickBuilder::transport), e.g. a
+//! `MockTransport` of the `testing` module in unit tests.
+
+use crate::error::SkError;
+use crate::SkResult;
+use reqwest::blocking::Client;
+use std::io::{self, BufRead, Cursor, Read};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+/// Response of a [`Transport`], whatever its status
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct HttpResponse {
+    pub status: u16,
+    /// Header names and values, in the order of the response
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// Response with `status` and `body`, without headers
+    pub fn new<T: Into<String>>(status: u16, body: T) -> HttpResponse {
+        HttpResponse {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Successful response of JSON `body`
+    pub fn json<T: Into<String>>(body: T) -> HttpResponse {
+        HttpResponse::new(200, body).with_header("Content-Type", "application/json; charset=utf-8")
+    }
+
+    /// Add a header
+    pub fn with_header<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> HttpResponse {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Value of the first header called `name`, ignoring case
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether the status is 2xx
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Drop the response, handing its body back to the [`BufferPool`] of the bodies
+    pub fn recycle(self) {
+        BufferPool::bodies().put(self.body.into_bytes());
+    }
+}
+
+/// Headers of a response kept with its results, see
+/// [`SkResultSet::headers`](crate::SkResultSet::headers)
+///
+/// Only the headers useful to monitor the use of the API are kept: the rate limit
+/// counters (`X-RateLimit-*`, `RateLimit-*`, `Retry-After`), the request IDs (`*-Request-Id`),
+/// the cache status (`X-Cache`, `*-Cache-Status`, `Age`) and the `Date`.
+///
+/// ```rust,no_run
+/// use songkick::SongKick;
+///
+/// let sk = SongKick::new("API_KEY");
+/// // RadioHead ID
+/// let events = sk.artist.calendar(253846, None).unwrap();
+/// if let Some(rate_limit) = events.headers().rate_limit() {
+///     println!("{:?} requests left", rate_limit.remaining);
+/// }
+/// println!("request {:?}", events.headers().request_id());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResponseHeaders {
+    headers: Vec<(String, String)>,
+}
+
+/// Rate limit counters of a response, as sent by the API or the proxies in front of it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RateLimit {
+    /// Requests allowed in the current window
+    pub limit: Option<u64>,
+    /// Requests left in the current window
+    pub remaining: Option<u64>,
+    /// End of the current window, in seconds, either from now or since the Unix epoch
+    /// depending on the server
+    pub reset: Option<u64>,
+}
+
+impl ResponseHeaders {
+    /// Headers of `headers` worth keeping
+    pub(crate) fn select(headers: &[(String, String)]) -> ResponseHeaders {
+        ResponseHeaders {
+            headers: headers.iter().filter(|(name, _)| is_kept(name)).cloned().collect(),
+        }
+    }
+
+    /// Value of the first kept header called `name`, ignoring case
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Kept header names and values, in the order of the response
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.headers.iter().map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// ID of the request, `X-Request-Id` or another `*-Request-Id` header
+    pub fn request_id(&self) -> Option<&str> {
+        self.get("x-request-id").or_else(|| self.find(|name| name.ends_with("request-id")))
+    }
+
+    /// Cache status, e.g. `HIT` or `MISS`, from `X-Cache` or a `*-Cache-Status` header
+    pub fn cache_status(&self) -> Option<&str> {
+        self.get("x-cache").or_else(|| self.find(|name| name.ends_with("cache-status")))
+    }
+
+    /// Rate limit counters, `None` without any
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        let counter = |name: &str| {
+            self.get(&format!("x-ratelimit-{}", name))
+                .or_else(|| self.get(&format!("ratelimit-{}", name)))
+                .and_then(|value| value.trim().parse().ok())
+        };
+        let rate_limit = RateLimit {
+            limit: counter("limit"),
+            remaining: counter("remaining"),
+            reset: counter("reset"),
+        };
+        if rate_limit.limit.is_none() && rate_limit.remaining.is_none() && rate_limit.reset.is_none() {
+            return None;
+        }
+        Some(rate_limit)
+    }
+
+    fn find<F: Fn(&str) -> bool>(&self, matches: F) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(name, _)| matches(&name.to_ascii_lowercase()))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Whether the header called `name` is kept in the [`ResponseHeaders`]
+fn is_kept(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.starts_with("x-ratelimit-")
+        || name.starts_with("ratelimit")
+        || name.ends_with("request-id")
+        || name.ends_with("cache-status")
+        || matches!(name.as_str(), "retry-after" | "x-cache" | "x-cache-hits" | "age" | "date")
+}
+
+/// Buffers reused from a response to the next
+///
+/// Reading a body into a fresh buffer grows it several times up to the size of the page,
+/// for every request. The pool keeps a few buffers of past bodies instead, already at the
+/// size of a typical page, so that bulk fetches reuse them. Buffers much larger than
+/// typical pages are not kept.
+///
+/// [`Transport::get_streamed`] bodies read by the client take their buffer from
+/// [`BufferPool::bodies`], or the [pool of the client](crate::SongKickBuilder::buffer_pool),
+/// and give it back once decoded: the pages decoded as they are read go through a pooled
+/// read buffer, the others are read whole into a pooled buffer. Transports overriding
+/// [`Transport::get`] can use it as well, [`HttpResponse::recycle`] giving a body back.
+pub struct BufferPool {
+    buffers: Mutex<Vec<Vec<u8>>>,
+    max_buffers: usize,
+    buffer_size: usize,
+}
+
+/// Pool of [`BufferPool::bodies`]
+static BODIES: BufferPool = BufferPool::new(8, 128 * 1024);
+
+impl BufferPool {
+    /// Pool keeping up to `max_buffers` buffers, of `buffer_size` bytes at least
+    pub const fn new(max_buffers: usize, buffer_size: usize) -> BufferPool {
+        BufferPool {
+            buffers: Mutex::new(Vec::new()),
+            max_buffers,
+            buffer_size,
+        }
+    }
+
+    /// Pool of the response bodies, shared by the clients
+    pub fn bodies() -> &'static BufferPool {
+        &BODIES
+    }
+
+    /// Empty buffer, a pooled one if any
+    pub fn take(&self) -> Vec<u8> {
+        self.lock().pop().unwrap_or_else(|| Vec::with_capacity(self.buffer_size))
+    }
+
+    /// Give `buffer` back to the pool, it is dropped if the pool is full or if it is too large
+    pub fn put(&self, mut buffer: Vec<u8>) {
+        if buffer.capacity() < self.buffer_size || buffer.capacity() > 8 * self.buffer_size {
+            return;
+        }
+        buffer.clear();
+        let mut buffers = self.lock();
+        if buffers.len() < self.max_buffers {
+            buffers.push(buffer);
+        }
+    }
+
+    /// Buffered reader of `reader`, over a buffer of the pool given back once dropped
+    pub fn reader<R: Read>(&self, reader: R) -> PooledReader<'_, R> {
+        let mut buffer = self.take();
+        // Zeroed once, the reads fill it from the start
+        buffer.resize(buffer.capacity().max(self.buffer_size), 0);
+        PooledReader {
+            inner: reader,
+            pool: self,
+            buffer,
+            pos: 0,
+            filled: 0,
+        }
+    }
+
+    /// Count of the buffers in the pool
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether the pool has no buffer
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Vec<u8>>> {
+        self.buffers.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Reader buffered by a buffer of a [`BufferPool`], see [`BufferPool::reader`]
+pub struct PooledReader<'a, R> {
+    inner: R,
+    pool: &'a BufferPool,
+    buffer: Vec<u8>,
+    pos: usize,
+    filled: usize,
+}
+
+impl<R: Read> Read for PooledReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // Large reads skip the buffer
+        if self.pos == self.filled && buf.len() >= self.buffer.len() {
+            return self.inner.read(buf);
+        }
+        let available = self.fill_buf()?;
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl<R: Read> BufRead for PooledReader<'_, R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos == self.filled {
+            self.filled = self.inner.read(&mut self.buffer)?;
+            self.pos = 0;
+        }
+        Ok(&self.buffer[self.pos..self.filled])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.pos = (self.pos + amount).min(self.filled);
+    }
+}
+
+impl<R> Drop for PooledReader<'_, R> {
+    fn drop(&mut self) {
+        self.pool.put(std::mem::take(&mut self.buffer));
+    }
+}
+
+/// Response of a [`Transport`] whose body is read as it comes
+pub struct StreamedResponse {
+    pub status: u16,
+    /// Header names and values, in the order of the response
+    pub headers: Vec<(String, String)>,
+    pub body: Box<dyn Read + Send>,
+}
+
+impl StreamedResponse {
+    /// Value of the first header called `name`, ignoring case
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether the status is 2xx
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Read the whole body into a buffer of the [`BufferPool`] of the bodies, replacing the
+    /// invalid UTF-8 sequences
+    pub fn into_response(self) -> SkResult<HttpResponse> {
+        self.into_response_in(BufferPool::bodies())
+    }
+
+    /// [`into_response`](StreamedResponse::into_response) reading into a buffer of `pool`
+    pub fn into_response_in(mut self, pool: &BufferPool) -> SkResult<HttpResponse> {
+        let mut body = pool.take();
+        if let Err(err) = self.body.read_to_end(&mut body) {
+            pool.put(body);
+            return Err(read_error(err));
+        }
+        let body = match String::from_utf8(body) {
+            Ok(body) => body,
+            Err(err) => {
+                let body = String::from_utf8_lossy(err.as_bytes()).into_owned();
+                pool.put(err.into_bytes());
+                body
+            }
+        };
+        Ok(HttpResponse {
+            status: self.status,
+            headers: self.headers,
+            body,
+        })
+    }
+
+    /// Response whose body fails to read past `limit` bytes, and the flag set when it does
+    pub(crate) fn limited(self, limit: u64) -> (StreamedResponse, Arc<AtomicBool>) {
+        let exceeded = Arc::new(AtomicBool::new(false));
+        let body = LimitedBody {
+            body: self.body,
+            remaining: limit,
+            exceeded: exceeded.clone(),
+        };
+        let response = StreamedResponse {
+            status: self.status,
+            headers: self.headers,
+            body: Box::new(body),
+        };
+        (response, exceeded)
+    }
+}
+
+/// Body failing to read past a limit
+struct LimitedBody {
+    body: Box<dyn Read + Send>,
+    remaining: u64,
+    exceeded: Arc<AtomicBool>,
+}
+
+impl Read for LimitedBody {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.remaining == 0 {
+            // The body may end right at the limit
+            if buf.is_empty() || self.body.read(&mut [0])? == 0 {
+                return Ok(0);
+            }
+            self.exceeded.store(true, Ordering::SeqCst);
+            return Err(io::Error::other("response body too large"));
+        }
+        let len = (buf.len() as u64).min(self.remaining) as usize;
+        let read = self.body.read(&mut buf[..len])?;
+        self.remaining -= read as u64;
+        Ok(read)
+    }
+}
+
+impl From<HttpResponse> for StreamedResponse {
+    fn from(response: HttpResponse) -> StreamedResponse {
+        StreamedResponse {
+            status: response.status,
+            headers: response.headers,
+            body: Box::new(Cursor::new(response.body.into_bytes())),
+        }
+    }
+}
+
+/// Error reading a body, the reqwest errors (e.g. timeouts) unwrapped from their I/O error
+fn read_error(err: io::Error) -> SkError {
+    if err.get_ref().is_some_and(|inner| inner.is::<reqwest::Error>()) {
+        if let Some(Ok(err)) = err.into_inner().map(|inner| inner.downcast::<reqwest::Error>()) {
+            return SkError::Transport(*err);
+        }
+        unreachable!("checked to be a reqwest error");
+    }
+    SkError::Io(err)
+}
+
+/// Sender of the GET requests of the client
+pub trait Transport: Send + Sync {
+    /// Response to a GET of `url`, errors are failures to get any response
+    fn get(&self, url: &str) -> SkResult<HttpResponse>;
+
+    /// Response to a GET of `url`, its body left to read
+    ///
+    /// The client decodes the results of the pages as they are read. Transports able to
+    /// hand their body as it arrives should override the default, which reads it with
+    /// [`get`](Transport::get) first.
+    fn get_streamed(&self, url: &str) -> SkResult<StreamedResponse> {
+        self.get(url).map(StreamedResponse::from)
+    }
+}
+
+/// [`Transport`] sending the requests with reqwest
+///
+/// All the requests go through a single [`reqwest::blocking::Client`], reusing its pool
+/// of connections instead of opening one per request. The client is created on the first
+/// request and shared by the clones of the transport.
+///
+/// The requests go through the proxies of the `HTTP_PROXY` and `HTTPS_PROXY` environment
+/// variables (or their lower case names), except for the hosts of `NO_PROXY`, unless
+/// [`system_proxy`](ReqwestTransport::system_proxy) is disabled. The variables are read
+/// once per process, on the first request of any transport.
+///
+/// ```rust,no_run
+/// use songkick::transport::ReqwestTransport;
+/// use songkick::SongKickBuilder;
+///
+/// // Straight to the API, whatever the environment
+/// let sk = SongKickBuilder::new("API_KEY").transport(ReqwestTransport::new().system_proxy(false)).build();
+/// ```
+///
+/// Three timeouts bound the requests: the [connection](ReqwestTransport::connect_timeout),
+/// unbounded by default, each [wait for data](ReqwestTransport::read_timeout),
+/// [`DEFAULT_READ_TIMEOUT`] by default, and the [whole request](ReqwestTransport::deadline),
+/// unbounded by default. A request timing out fails with an error whose
+/// [`is_timeout`](SkError::is_timeout) is true.
+///
+/// ```rust,no_run
+/// use songkick::transport::ReqwestTransport;
+/// use std::time::Duration;
+///
+/// // Give up quickly on dead hosts, not on large pages still arriving
+/// let transport = ReqwestTransport::new()
+///     .connect_timeout(Duration::from_secs(3))
+///     .read_timeout(Duration::from_secs(10))
+///     .deadline(Duration::from_secs(120));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: Arc<OnceLock<Client>>,
+    config: ClientConfig,
+}
+
+/// Default largest wait for data of a [`ReqwestTransport`], the one of reqwest
+pub const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Settings of the client created by a [`ReqwestTransport`]
+#[derive(Debug, Clone)]
+struct ClientConfig {
+    no_system_proxy: bool,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    deadline: Option<Duration>,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig {
+            no_system_proxy: false,
+            connect_timeout: None,
+            read_timeout: Some(DEFAULT_READ_TIMEOUT),
+            deadline: None,
+        }
+    }
+}
+
+impl ClientConfig {
+    fn build(&self) -> Client {
+        // The timeout of the blocking client bounds each wait, for the response then for
+        // each read of its body
+        let mut builder = Client::builder()
+            .timeout(self.read_timeout)
+            .connect_timeout(self.connect_timeout);
+        if self.no_system_proxy {
+            builder = builder.no_proxy();
+        }
+        // Same failure as `Client::new`, the TLS backend cannot be initialized
+        builder.build().expect("Client::new()")
+    }
+}
+
+impl ReqwestTransport {
+    pub fn new() -> ReqwestTransport {
+        ReqwestTransport::default()
+    }
+
+    /// Transport sending the requests with `client`, e.g. one with a custom proxy
+    ///
+    /// The settings of the client, such as [`system_proxy`](ReqwestTransport::system_proxy),
+    /// don't apply to `client`. The [`deadline`](ReqwestTransport::deadline) still applies,
+    /// being set on each request.
+    pub fn with_client(client: Client) -> ReqwestTransport {
+        ReqwestTransport {
+            client: Arc::new(OnceLock::from(client)),
+            config: ClientConfig::default(),
+        }
+    }
+
+    /// Whether the requests go through the proxies of the environment, enabled by default
+    pub fn system_proxy(mut self, enabled: bool) -> ReqwestTransport {
+        self.config.no_system_proxy = !enabled;
+        self
+    }
+
+    /// Largest time to connect to the API, TLS handshake included, unbounded by default
+    pub fn connect_timeout(mut self, timeout: Duration) -> ReqwestTransport {
+        self.config.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Largest wait for the response, then for each chunk of its body, [`DEFAULT_READ_TIMEOUT`]
+    /// by default, `None` to wait forever
+    ///
+    /// A slow response still arriving doesn't time out, a stalled one does.
+    pub fn read_timeout<T: Into<Option<Duration>>>(mut self, timeout: T) -> ReqwestTransport {
+        self.config.read_timeout = timeout.into();
+        self
+    }
+
+    /// Largest time of a whole request, from connecting to the end of the body, unbounded
+    /// by default
+    ///
+    /// Unlike the other settings, it applies to the client of [`with_client`](ReqwestTransport::with_client)
+    /// as well.
+    pub fn deadline(mut self, deadline: Duration) -> ReqwestTransport {
+        self.config.deadline = Some(deadline);
+        self
+    }
+
+    /// Client sending the requests
+    pub fn client(&self) -> &Client {
+        self.client.get_or_init(|| self.config.build())
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn get(&self, url: &str) -> SkResult<HttpResponse> {
+        self.get_streamed(url)?.into_response()
+    }
+
+    fn get_streamed(&self, url: &str) -> SkResult<StreamedResponse> {
+        let mut request = self.client().get(url);
+        if let Some(deadline) = self.config.deadline {
+            request = request.timeout(deadline);
+        }
+        let resp = request.send()?;
+        let status = resp.status().as_u16();
+        let headers = resp
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        Ok(StreamedResponse {
+            status,
+            headers,
+            body: Box::new(resp),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn response_headers() {
+        let resp = HttpResponse::json("{}").with_header("Retry-After", "30");
+        assert_eq!(Some("application/json; charset=utf-8"), resp.header("content-type"));
+        assert_eq!(Some("30"), resp.header("RETRY-AFTER"));
+        assert_eq!(None, resp.header("x-ratelimit-remaining"));
+        assert!(resp.is_success());
+        assert!(!HttpResponse::new(429, "").is_success());
+    }
+
+    #[test]
+    fn without_system_proxy() {
+        let server = crate::testing::MockSongKick::start();
+        let transport = ReqwestTransport::new().system_proxy(false);
+        let url = format!("{}/artists/324967.json?apikey=KEY", server.base_path());
+        assert_eq!(200, transport.get(&url).unwrap().status);
+        // The clones share the client
+        assert!(std::ptr::eq(transport.client(), transport.clone().client()));
+    }
+
+    /// Server answering each connection with `head`, then `body` a byte every `pause`
+    fn slow_server(head: &'static str, body: &'static str, pause: Duration) -> String {
+        use std::io::Write;
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => return,
+                };
+                std::thread::spawn(move || {
+                    let mut request = [0; 1024];
+                    let _ = stream.read(&mut request);
+                    let _ = stream.write_all(head.as_bytes());
+                    for byte in body.as_bytes() {
+                        std::thread::sleep(pause);
+                        if stream.write_all(&[*byte]).is_err() {
+                            return;
+                        }
+                    }
+                    std::thread::sleep(Duration::from_secs(2));
+                });
+            }
+        });
+        format!("http://{}/events.json", address)
+    }
+
+    #[test]
+    fn timeouts() {
+        let head = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 12\r\n\r\n";
+        let url = slow_server(head, "{\"page\": 1 }", Duration::from_millis(40));
+        let transport = |configure: fn(ReqwestTransport) -> ReqwestTransport| {
+            configure(ReqwestTransport::new().system_proxy(false).connect_timeout(Duration::from_secs(5)))
+        };
+
+        // Progressing, each read within the read timeout
+        let progressing = transport(|t| t.read_timeout(Duration::from_millis(400)));
+        assert_eq!("{\"page\": 1 }", progressing.get(&url).unwrap().body);
+
+        let stalled = transport(|t| t.read_timeout(Duration::from_millis(10)));
+        let err = stalled.get(&url).unwrap_err();
+        assert!(err.is_timeout(), "{}", err);
+
+        let late = transport(|t| t.deadline(Duration::from_millis(200)));
+        let err = late.get(&url).unwrap_err();
+        assert!(err.is_timeout() && err.is_retriable(), "{}", err);
+    }
+
+    #[test]
+    fn selected_headers() {
+        let headers: Vec<(String, String)> = [
+            ("Content-Type", "application/json"),
+            ("X-RateLimit-Limit", "100"),
+            ("X-RateLimit-Remaining", " 42"),
+            ("Date", "Wed, 14 Oct 2026 10:00:00 GMT"),
+            ("X-Amzn-Request-Id", "abc"),
+            ("CF-Cache-Status", "HIT"),
+            ("Set-Cookie", "session=1"),
+        ]
+        .iter()
+        .map(|&(name, value)| (String::from(name), String::from(value)))
+        .collect();
+        let headers = ResponseHeaders::select(&headers);
+
+        assert_eq!(5, headers.iter().count());
+        assert_eq!(None, headers.get("set-cookie"));
+        assert_eq!(Some("abc"), headers.request_id());
+        assert_eq!(Some("HIT"), headers.cache_status());
+        let rate_limit = headers.rate_limit().unwrap();
+        assert_eq!((Some(100), Some(42), None), (rate_limit.limit, rate_limit.remaining, rate_limit.reset));
+        assert_eq!(None, ResponseHeaders::default().rate_limit());
+    }
+
+    #[test]
+    fn pooled_buffers() {
+        let pool = BufferPool::new(2, 1024);
+        let buffer = pool.take();
+        assert!(buffer.capacity() >= 1024);
+        let address = buffer.as_ptr();
+        pool.put(buffer);
+        assert_eq!(1, pool.len());
+        let reused = pool.take();
+        assert_eq!(address, reused.as_ptr());
+        assert!(reused.is_empty());
+
+        pool.put(Vec::with_capacity(16));
+        pool.put(Vec::with_capacity(1024 * 1024));
+        assert!(pool.is_empty());
+        for _ in 0..3 {
+            pool.put(Vec::with_capacity(1024));
+        }
+        assert_eq!(2, pool.len());
+    }
+
+    #[test]
+    fn bodies_in_pooled_buffers() {
+        let resp = StreamedResponse::from(HttpResponse::json("{\"café\": 1}")).into_response().unwrap();
+        assert_eq!("{\"café\": 1}", resp.body);
+        resp.recycle();
+
+        let invalid = StreamedResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: Box::new(Cursor::new(b"caf\xe9".to_vec())),
+        };
+        assert_eq!("caf\u{fffd}", invalid.into_response().unwrap().body);
+    }
+
+    #[test]
+    fn shared_client() {
+        let transport = ReqwestTransport::new();
+        let clone = transport.clone();
+        assert!(std::ptr::eq(transport.client(), clone.client()));
+
+        let server = crate::testing::MockSongKick::start();
+        let url = format!("{}/artists/1.json?apikey=KEY", server.base_path());
+        for _ in 0..3 {
+            assert!(clone.get(&url).unwrap().is_success());
+        }
+        assert_eq!(3, server.requests().len());
+    }
+}
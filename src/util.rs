@@ -3,11 +3,33 @@ use url::percent_encoding::{utf8_percent_encode, QUERY_ENCODE_SET};
 use url::define_encode_set;
 
 define_encode_set! {
-    pub SK_ENCODE_SET = [QUERY_ENCODE_SET] | {'-'}
+    /// Encode set of every name and value of the query string, separators included
+    pub SK_ENCODE_SET = [QUERY_ENCODE_SET] | {'-', '%', '&', '=', '+', ';'}
 }
 
+/// Append `src`, percent-encoded as a whole name or value of the query string, to `buf`
+pub(crate) fn encode_into(buf: &mut String, src: &str) {
+    buf.extend(utf8_percent_encode(src, SK_ENCODE_SET));
+}
+
+/// `src` percent-encoded like [`encode_into`]
 pub fn encode(src: &str) -> String {
-    utf8_percent_encode(src, SK_ENCODE_SET).collect::<String>()
+    let mut encoded = String::with_capacity(src.len());
+    encode_into(&mut encoded, src);
+    encoded
+}
+
+/// Mean Earth radius in meters
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// Great-circle distance in meters between two coordinates, using the haversine formula
+pub fn haversine(lat1: f64, lng1: f64, lat2: f64, lng2: f64) -> f64 {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let d_phi = (lat2 - lat1).to_radians();
+    let d_lambda = (lng2 - lng1).to_radians();
+
+    let a = (d_phi / 2.0).sin().powi(2) + phi1.cos() * phi2.cos() * (d_lambda / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS * a.sqrt().asin()
 }
 
 pub mod json {
@@ -15,41 +37,112 @@ pub mod json {
     use crate::SkResult;
     use serde_json::value::Map;
     use serde_json::Value;
+    use std::collections::HashMap;
+
+    /// Sorted names of the fields captured in `extra`
+    pub fn unknown_fields(extra: &HashMap<String, Value>) -> Vec<String> {
+        let mut fields: Vec<String> = extra.keys().cloned().collect();
+        fields.sort();
+        fields
+    }
+
+    /// Prefix the unknown `fields` of a nested resource with its `path`
+    pub fn nested_fields(path: &str, fields: Vec<String>) -> Vec<String> {
+        fields
+            .into_iter()
+            .map(|field| format!("{}.{}", path, field))
+            .collect()
+    }
+
+    /// Collect the fields of `obj` not listed in `known`
+    pub fn extra_fields(obj: &Map<String, Value>, known: &[&str]) -> HashMap<String, Value> {
+        obj.iter()
+            .filter(|(key, _)| !known.contains(&key.as_str()))
+            .map(|(key, val)| (key.clone(), val.clone()))
+            .collect()
+    }
+
+    /// Extension to attach the JSON path of a nested value to decoding errors
+    pub trait AtPath<T> {
+        fn at(self, segment: &str) -> SkResult<T>;
+    }
+
+    impl<T> AtPath<T> for SkResult<T> {
+        fn at(self, segment: &str) -> SkResult<T> {
+            self.map_err(|err| err.at(segment))
+        }
+    }
+
+    fn kind(val: &Value) -> &'static str {
+        match *val {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
+
+    fn field_error(field: &str, val: Option<&Value>, expected: &str) -> SkError {
+        SkError::Decode {
+            path: String::from(field),
+            message: match val {
+                None => String::from("missing field"),
+                Some(val) => format!("invalid type: {}, expected {}", kind(val), expected),
+            },
+            source: None,
+        }
+    }
+
+    pub fn get_value<'a>(obj: &'a Map<String, Value>, field: &str) -> SkResult<&'a Value> {
+        obj.get(field).ok_or_else(|| field_error(field, None, "a value"))
+    }
+
+    pub fn get_obj<'a>(obj: &'a Map<String, Value>, field: &str) -> SkResult<&'a Map<String, Value>> {
+        obj.get(field)
+            .and_then(|val| val.as_object())
+            .ok_or_else(|| field_error(field, obj.get(field), "an object"))
+    }
 
     pub fn get_str(obj: &Map<String, Value>, field: &str) -> SkResult<String> {
         obj.get(field)
             .and_then(|val| val.as_str())
             .map(String::from)
-            .ok_or(SkError::JsonError(format!(
-                "Failed to deserialize JSON artist object: missing field {}",
-                field
-            )))
+            .ok_or_else(|| field_error(field, obj.get(field), "a string"))
+    }
+
+    pub fn get_opt_str(obj: &Map<String, Value>, field: &str) -> SkResult<Option<String>> {
+        match obj.get(field) {
+            None | Some(Value::Null) => Ok(None),
+            Some(val) => val
+                .as_str()
+                .map(|s| Some(String::from(s)))
+                .ok_or_else(|| field_error(field, Some(val), "a string")),
+        }
+    }
+
+    pub fn get_bool(obj: &Map<String, Value>, field: &str) -> SkResult<bool> {
+        obj.get(field)
+            .and_then(|val| val.as_bool())
+            .ok_or_else(|| field_error(field, obj.get(field), "a boolean"))
     }
 
     pub fn get_u64(obj: &Map<String, Value>, field: &str) -> SkResult<u64> {
         obj.get(field)
             .and_then(|val| val.as_u64())
-            .ok_or(SkError::JsonError(format!(
-                "Failed to deserialize JSON artist object: missing field {}",
-                field
-            )))
+            .ok_or_else(|| field_error(field, obj.get(field), "an unsigned integer"))
     }
 
     pub fn get_f64(obj: &Map<String, Value>, field: &str) -> SkResult<f64> {
         obj.get(field)
             .and_then(|val| val.as_f64())
-            .ok_or(SkError::JsonError(format!(
-                "Failed to deserialize JSON artist object: missing field {}",
-                field
-            )))
+            .ok_or_else(|| field_error(field, obj.get(field), "a number"))
     }
 
     pub fn get_arr<'a>(obj: &'a Map<String, Value>, field: &str) -> SkResult<&'a Vec<Value>> {
         obj.get(field)
             .and_then(|val| val.as_array())
-            .ok_or(SkError::JsonError(format!(
-                "Failed to deserialize JSON artist object: missing field {}",
-                field
-            )))
+            .ok_or_else(|| field_error(field, obj.get(field), "an array"))
     }
 }
@@ -0,0 +1,692 @@
+//! Polling of calendars, reporting their changes
+//!
+//! A [`Watcher`] refreshes the calendars of its targets every poll interval and
+//! calls back with what changed since the previous refresh. The first refresh of
+//! a target only takes its snapshot.
+//!
+//! Requests are spaced by a pause, rate-limited targets wait for the `Retry-After`
+//! delay of the API, and targets failing with other retriable errors are retried
+//! at the next interval.
+//!
+//! ```rust,no_run
+//! use songkick::SongKick;
+//! use songkick::diff::Change;
+//! use songkick::watch::Watcher;
+//! use std::time::Duration;
+//!
+//! let sk = SongKick::new("API_KEY");
+//! let mut watcher = Watcher::new(&sk, Duration::from_secs(3600))
+//!     // RadioHead ID
+//!     .artist(253846)
+//!     // London ID
+//!     .metro_area(24426);
+//!
+//! watcher
+//!     .run(|target, changes| {
+//!         for change in changes {
+//!             if let Change::Added(event) = change {
+//!                 println!("{}: new event {}", target, event);
+//!             }
+//!         }
+//!     })
+//!     .unwrap();
+//! ```
+//!
+//! With the `tokio` feature the changes can be delivered as [`ChangeEvent`]s over a
+//! tokio channel instead, the watcher polling on its own thread:
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "tokio")]
+//! # async fn notify() {
+//! use songkick::SongKick;
+//! use songkick::watch::{self, ChangeEvent};
+//! use std::time::Duration;
+//! use tokio::sync::mpsc;
+//!
+//! let (sender, mut receiver) = mpsc::channel(64);
+//! let watcher = watch::spawn(SongKick::new("API_KEY"), Duration::from_secs(3600), sender, |watcher| {
+//!     // RadioHead ID
+//!     watcher.artist(253846)
+//! });
+//!
+//! while let Some(change) = receiver.recv().await {
+//!     if let ChangeEvent::Cancelled { event, .. } = change {
+//!         println!("Cancelled: {}", event);
+//!         break;
+//!     }
+//! }
+//! // Unblock the watcher if it is waiting for room in the channel
+//! drop(receiver);
+//! watcher.shutdown().await.unwrap();
+//! # }
+//! ```
+//!
+//! A [`Shutdown`] stops a watcher running on another thread, at its next pause or once
+//! the refresh under way is over. The watchers keep no state on disk, the snapshots of
+//! the calendars are dropped with them.
+
+use crate::diff::{diff, Change};
+use crate::endpoints::CalendarReason;
+use crate::error::SkError;
+use crate::resources::event::Event;
+use crate::resources::ids::{ArtistId, MetroAreaId};
+use crate::{SkResult, SongKick};
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+#[cfg(feature = "tokio")]
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Default pause between two refreshes
+pub const DEFAULT_PAUSE: Duration = Duration::from_secs(1);
+
+/// Wait before trying again to send to a full channel
+#[cfg(feature = "tokio")]
+const SEND_RETRY: Duration = Duration::from_millis(10);
+
+/// Signal stopping the [`Watcher`]s it is given to, shared by its clones
+///
+/// ```rust,no_run
+/// use songkick::SongKick;
+/// use songkick::watch::{Shutdown, Watcher};
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let shutdown = Shutdown::new();
+/// let signal = shutdown.clone();
+/// let worker = thread::spawn(move || {
+///     let sk = SongKick::new("API_KEY");
+///     // RadioHead ID
+///     let mut watcher = Watcher::new(&sk, Duration::from_secs(3600)).artist(253846).until(signal);
+///     watcher.run(|target, changes| println!("{}: {} changes", target, changes.len()))
+/// });
+/// // On termination of the service
+/// shutdown.shutdown();
+/// worker.join().unwrap().unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Shutdown {
+    stopped: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl Shutdown {
+    /// Signal not given yet
+    pub fn new() -> Shutdown {
+        Shutdown::default()
+    }
+
+    /// Stop the watchers of the signal, waking them up from their sleep
+    pub fn shutdown(&self) {
+        let (stopped, wake) = &*self.stopped;
+        *stopped.lock().unwrap_or_else(PoisonError::into_inner) = true;
+        wake.notify_all();
+    }
+
+    /// Whether the signal was given
+    pub fn is_shutdown(&self) -> bool {
+        *self.stopped.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Sleep for `duration`, returns false when woken up by the signal
+    fn sleep(&self, duration: Duration) -> bool {
+        let (stopped, wake) = &*self.stopped;
+        let stopped = stopped.lock().unwrap_or_else(PoisonError::into_inner);
+        let (stopped, _) = wake
+            .wait_timeout_while(stopped, duration, |stopped| !*stopped)
+            .unwrap_or_else(PoisonError::into_inner);
+        !*stopped
+    }
+}
+
+/// A calendar polled by a [`Watcher`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Target {
+    /// Upcoming events of the artist
+    Artist(ArtistId),
+    /// Upcoming events in the metro area
+    MetroArea(MetroAreaId),
+    /// Events of the calendar of the user
+    User(String, CalendarReason),
+}
+
+impl Target {
+    fn fetch(&self, sk: &SongKick) -> SkResult<Vec<Event>> {
+        match *self {
+            Target::Artist(id) => sk.artist.calendar_all(id),
+            Target::MetroArea(id) => sk.metro_area.calendar_all(id),
+            Target::User(ref username, reason) => Ok(sk
+                .user
+                .calendar_all(username, reason)?
+                .into_iter()
+                .map(|entry| entry.event)
+                .collect()),
+        }
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Target::Artist(id) => write!(f, "artist {}", id),
+            Target::MetroArea(id) => write!(f, "metro area {}", id),
+            Target::User(ref username, _) => write!(f, "user {}", username),
+        }
+    }
+}
+
+struct Watched {
+    target: Target,
+    snapshot: Option<Vec<Event>>,
+    due: Instant,
+}
+
+impl Watched {
+    /// Handle the result of a refresh at `now`, calling back when a previous snapshot changed
+    fn update<F>(&mut self, fetched: SkResult<Vec<Event>>, now: Instant, interval: Duration, on_change: &mut F) -> SkResult<()>
+    where
+        F: FnMut(&Target, &[Change]),
+    {
+        match fetched {
+            Ok(events) => {
+                if let Some(ref snapshot) = self.snapshot {
+                    let changes = diff(snapshot, &events);
+                    if !changes.is_empty() {
+                        on_change(&self.target, &changes);
+                    }
+                }
+                self.snapshot = Some(events);
+                self.due = now + interval;
+                Ok(())
+            }
+            Err(err) => {
+                let delay = match *err.inner() {
+                    SkError::RateLimited { retry_after, .. } => retry_after.unwrap_or(interval),
+                    _ if err.is_retriable() => interval,
+                    _ => return Err(err),
+                };
+                #[cfg(feature = "tracing")]
+                tracing::warn!(watched = %self.target, error = %err, retry_in_s = delay.as_secs(), "refresh failed, retrying later");
+                #[cfg(feature = "log")]
+                log::warn!("refresh of {} failed, retrying in {}s: {}", self.target, delay.as_secs(), err);
+                self.due = now + delay;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A change of a watched calendar, owning the events
+#[derive(Clone)]
+#[non_exhaustive]
+pub enum ChangeEvent {
+    /// Event announced since the previous refresh
+    New { target: Target, event: Event },
+    /// Event cancelled since the previous refresh
+    Cancelled { target: Target, event: Event },
+    /// Event moved to another date or venue since the previous refresh, `before` is its previous version
+    Rescheduled {
+        target: Target,
+        before: Box<Event>,
+        after: Event,
+    },
+}
+
+impl ChangeEvent {
+    /// Owned copy of `change`, events removed from the calendar have none
+    pub fn from_change(target: &Target, change: &Change) -> Option<ChangeEvent> {
+        let target = target.clone();
+        match *change {
+            Change::Added(event) => Some(ChangeEvent::New {
+                target,
+                event: event.clone(),
+            }),
+            Change::Cancelled(event) => Some(ChangeEvent::Cancelled {
+                target,
+                event: event.clone(),
+            }),
+            Change::Rescheduled { before, after } => Some(ChangeEvent::Rescheduled {
+                target,
+                before: Box::new(before.clone()),
+                after: after.clone(),
+            }),
+            Change::Removed(_) => None,
+        }
+    }
+
+    /// The watched calendar
+    pub fn target(&self) -> &Target {
+        match *self {
+            ChangeEvent::New { ref target, .. }
+            | ChangeEvent::Cancelled { ref target, .. }
+            | ChangeEvent::Rescheduled { ref target, .. } => target,
+        }
+    }
+}
+
+/// Periodic refresh of calendars
+pub struct Watcher<'a> {
+    sk: &'a SongKick,
+    interval: Duration,
+    pause: Duration,
+    targets: Vec<Watched>,
+    shutdown: Shutdown,
+}
+
+impl<'a> Watcher<'a> {
+    /// Watcher refreshing each target every `interval`
+    pub fn new(sk: &'a SongKick, interval: Duration) -> Watcher<'a> {
+        Watcher {
+            sk,
+            interval,
+            pause: DEFAULT_PAUSE,
+            targets: Vec::new(),
+            shutdown: Shutdown::new(),
+        }
+    }
+
+    /// Pause between two refreshes, [`DEFAULT_PAUSE`] by default
+    pub fn pause(mut self, pause: Duration) -> Watcher<'a> {
+        self.pause = pause;
+        self
+    }
+
+    /// Stop polling once `shutdown` is signalled
+    pub fn until(mut self, shutdown: Shutdown) -> Watcher<'a> {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Watch `target`
+    pub fn watch(mut self, target: Target) -> Watcher<'a> {
+        self.targets.push(Watched {
+            target,
+            snapshot: None,
+            due: Instant::now(),
+        });
+        self
+    }
+
+    /// Watch the upcoming events of the artist
+    pub fn artist<I: Into<ArtistId>>(self, id: I) -> Watcher<'a> {
+        self.watch(Target::Artist(id.into()))
+    }
+
+    /// Watch the upcoming events in the metro area
+    pub fn metro_area<I: Into<MetroAreaId>>(self, id: I) -> Watcher<'a> {
+        self.watch(Target::MetroArea(id.into()))
+    }
+
+    /// Watch the upcoming events of the artists tracked by the user
+    pub fn user<T: Into<String>>(self, username: T) -> Watcher<'a> {
+        self.watch(Target::User(username.into(), CalendarReason::TrackedArtist))
+    }
+
+    /// Refresh the targets that are due, calling `on_change` with the changes of each
+    ///
+    /// Returns the first error that is not retriable, and early once [shut down](Watcher::until).
+    pub fn poll<F>(&mut self, mut on_change: F) -> SkResult<()>
+    where
+        F: FnMut(&Target, &[Change]),
+    {
+        let mut first = true;
+        for watched in &mut self.targets {
+            if watched.due > Instant::now() {
+                continue;
+            }
+            if (!first && !self.shutdown.sleep(self.pause)) || self.shutdown.is_shutdown() {
+                break;
+            }
+            first = false;
+            let fetched = watched.target.fetch(self.sk);
+            watched.update(fetched, Instant::now(), self.interval, &mut on_change)?;
+        }
+        Ok(())
+    }
+
+    /// Poll forever, sleeping until the next target is due
+    ///
+    /// Returns the first error that is not retriable, or once [shut down](Watcher::until).
+    pub fn run<F>(&mut self, mut on_change: F) -> SkResult<()>
+    where
+        F: FnMut(&Target, &[Change]),
+    {
+        self.run_while(|target, changes| {
+            on_change(target, changes);
+            true
+        })
+    }
+
+    /// Poll forever, sending the changes to `sender`
+    ///
+    /// Returns when the receiver is dropped, once [shut down](Watcher::until), or at the first
+    /// error that is not retriable. Must not be called from an async context, as sending blocks
+    /// while the channel is full, until there is room or the watcher is shut down.
+    #[cfg(feature = "tokio")]
+    pub fn run_channel(&mut self, sender: &tokio::sync::mpsc::Sender<ChangeEvent>) -> SkResult<()> {
+        let shutdown = self.shutdown.clone();
+        self.run_while(|target, changes| events(target, changes).all(|event| send(sender, event, &shutdown)))
+    }
+
+    /// Poll forever, broadcasting the changes with `sender`
+    ///
+    /// Returns when every receiver is dropped, once [shut down](Watcher::until), or at the
+    /// first error that is not retriable.
+    #[cfg(feature = "tokio")]
+    pub fn run_broadcast(&mut self, sender: &tokio::sync::broadcast::Sender<ChangeEvent>) -> SkResult<()> {
+        self.run_while(|target, changes| events(target, changes).all(|event| sender.send(event).is_ok()))
+    }
+
+    /// Poll until `on_change` returns false or until shut down
+    fn run_while<F>(&mut self, mut on_change: F) -> SkResult<()>
+    where
+        F: FnMut(&Target, &[Change]) -> bool,
+    {
+        let mut open = true;
+        loop {
+            self.poll(|target, changes| open = open && on_change(target, changes))?;
+            if !open {
+                return Ok(());
+            }
+            let sleep = match self.targets.iter().map(|watched| watched.due).min() {
+                Some(due) => due.saturating_duration_since(Instant::now()).max(self.pause),
+                None => self.interval,
+            };
+            if !self.shutdown.sleep(sleep) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Owned events of `changes`
+#[cfg(feature = "tokio")]
+fn events<'a>(target: &'a Target, changes: &'a [Change]) -> impl Iterator<Item = ChangeEvent> + 'a {
+    changes.iter().filter_map(move |change| ChangeEvent::from_change(target, change))
+}
+
+/// Send `event`, waiting while the channel is full, false once the receiver is dropped or shut down
+#[cfg(feature = "tokio")]
+fn send(sender: &tokio::sync::mpsc::Sender<ChangeEvent>, mut event: ChangeEvent, shutdown: &Shutdown) -> bool {
+    use tokio::sync::mpsc::error::TrySendError;
+
+    loop {
+        match sender.try_send(event) {
+            Ok(()) => return true,
+            Err(TrySendError::Closed(_)) => return false,
+            Err(TrySendError::Full(unsent)) => {
+                if !shutdown.sleep(SEND_RETRY) {
+                    return false;
+                }
+                event = unsent;
+            }
+        }
+    }
+}
+
+/// Watcher running on its own thread, started by [`spawn`]
+#[cfg(feature = "tokio")]
+pub struct WatchHandle {
+    shutdown: Shutdown,
+    thread: thread::JoinHandle<()>,
+    result: tokio::sync::oneshot::Receiver<SkResult<()>>,
+}
+
+#[cfg(feature = "tokio")]
+impl WatchHandle {
+    /// Stop the watcher and wait for its thread to end, without blocking the runtime
+    ///
+    /// Returns the error that ended the watcher earlier, if any. A refresh under way is
+    /// completed first, the changes not sent yet because the channel is full are dropped.
+    pub async fn shutdown(self) -> SkResult<()> {
+        self.shutdown.shutdown();
+        match self.result.await {
+            Ok(result) => result,
+            Err(_) => match self.thread.join() {
+                Err(panic) => std::panic::resume_unwind(panic),
+                Ok(()) => unreachable!("the watcher sends its result before ending"),
+            },
+        }
+    }
+
+    /// Block until the thread of the watcher ends, returns its result
+    pub fn join(mut self) -> thread::Result<SkResult<()>> {
+        self.thread.join()?;
+        // Sent before the thread ended
+        Ok(self.result.try_recv().unwrap_or(Ok(())))
+    }
+}
+
+/// Run a watcher of `sk` on a new thread, sending its changes to `sender`
+///
+/// `targets` adds the targets to the watcher. The thread ends when the receiver is
+/// dropped, once [shut down](WatchHandle::shutdown), or at the first error that is not
+/// retriable, which it returns.
+#[cfg(feature = "tokio")]
+pub fn spawn<F>(sk: SongKick, interval: Duration, sender: tokio::sync::mpsc::Sender<ChangeEvent>, targets: F) -> WatchHandle
+where
+    F: for<'w> FnOnce(Watcher<'w>) -> Watcher<'w> + Send + 'static,
+{
+    let shutdown = Shutdown::new();
+    let signal = shutdown.clone();
+    let (done, result) = tokio::sync::oneshot::channel();
+    let thread = thread::spawn(move || {
+        let result = targets(Watcher::new(&sk, interval).until(signal)).run_channel(&sender);
+        let _ = done.send(result);
+    });
+    WatchHandle {
+        shutdown,
+        thread,
+        result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::SkResultSet;
+    use serde_json::Value;
+    use std::thread;
+
+    fn calendar() -> Value {
+        serde_json::from_str(&std::fs::read_to_string("fixtures/event/artist-324967-calendar.json").unwrap())
+            .unwrap()
+    }
+
+    fn events(data: &Value) -> SkResult<Vec<Event>> {
+        Ok(SkResultSet::<Event>::from_json(data)?.collect())
+    }
+
+    fn watched() -> Watched {
+        Watched {
+            target: Target::Artist(ArtistId(324967)),
+            snapshot: None,
+            due: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn first_refresh_takes_snapshot() {
+        let mut watched = watched();
+        let now = Instant::now();
+        let mut calls = 0;
+        watched
+            .update(events(&calendar()), now, Duration::from_secs(60), &mut |_: &Target, _: &[Change]| calls += 1)
+            .unwrap();
+        assert_eq!(0, calls);
+        assert_eq!(now + Duration::from_secs(60), watched.due);
+
+        watched
+            .update(events(&calendar()), now, Duration::from_secs(60), &mut |_: &Target, _: &[Change]| calls += 1)
+            .unwrap();
+        assert_eq!(0, calls);
+    }
+
+    #[test]
+    fn refresh_reports_changes() {
+        let mut watched = watched();
+        let now = Instant::now();
+        let interval = Duration::from_secs(60);
+        let mut noop = |_: &Target, _: &[Change]| ();
+        watched.update(events(&calendar()), now, interval, &mut noop).unwrap();
+
+        let mut data = calendar();
+        data["resultsPage"]["results"]["event"][0]["status"] = Value::from("cancelled");
+        let mut cancelled = Vec::new();
+        watched
+            .update(events(&data), now, interval, &mut |target: &Target, changes: &[Change]| {
+                assert_eq!(&Target::Artist(ArtistId(324967)), target);
+                cancelled.extend(changes.iter().map(|change| change.event().id));
+            })
+            .unwrap();
+        assert_eq!(1, cancelled.len());
+    }
+
+    #[test]
+    fn rate_limited_refresh_waits() {
+        let mut watched = watched();
+        let now = Instant::now();
+        let interval = Duration::from_secs(60);
+        let mut noop = |_: &Target, _: &[Change]| ();
+
+        let limited = Err(SkError::from_status(429, Some("120"), "").with_request("artists.calendar", ""));
+        watched.update(limited, now, interval, &mut noop).unwrap();
+        assert_eq!(now + Duration::from_secs(120), watched.due);
+
+        watched.update(Err(SkError::from_status(503, None, "")), now, interval, &mut noop).unwrap();
+        assert_eq!(now + interval, watched.due);
+
+        assert!(watched.update(Err(SkError::from_status(404, None, "")), now, interval, &mut noop).is_err());
+    }
+
+    #[test]
+    fn owned_change_events() {
+        let before = events(&calendar()).unwrap();
+        let mut data = calendar();
+        data["resultsPage"]["results"]["event"][0]["status"] = Value::from("cancelled");
+        data["resultsPage"]["results"]["event"].as_array_mut().unwrap().remove(1);
+        let after = events(&data).unwrap();
+        let target = Target::Artist(ArtistId(324967));
+
+        let changes = diff(&before, &after);
+        assert_eq!(2, changes.len());
+        let owned: Vec<ChangeEvent> = changes
+            .iter()
+            .filter_map(|change| ChangeEvent::from_change(&target, change))
+            .collect();
+        assert_eq!(1, owned.len());
+        assert!(matches!(owned[0], ChangeEvent::Cancelled { ref event, .. } if event.id == before[0].id));
+        assert_eq!(&target, owned[0].target());
+    }
+
+    fn watched_client() -> (SongKick, crate::testing::MockTransport) {
+        use crate::testing::{fixtures, MockTransport};
+
+        let mock = MockTransport::new().on("artists/324967/calendar.json", fixtures::ARTIST_CALENDAR.response());
+        (crate::SongKickBuilder::new("KEY").transport(mock.clone()).build(), mock)
+    }
+
+    #[test]
+    fn shutdown_stops_run() {
+        let (sk, mock) = watched_client();
+        let shutdown = Shutdown::new();
+        let started = Instant::now();
+        thread::scope(|scope| {
+            let signal = shutdown.clone();
+            let worker = scope.spawn(|| {
+                let mut watcher = Watcher::new(&sk, Duration::from_secs(3600)).artist(324967).until(signal);
+                watcher.run(|_, _| ())
+            });
+            thread::sleep(Duration::from_millis(50));
+            shutdown.shutdown();
+            worker.join().unwrap().unwrap();
+        });
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(shutdown.is_shutdown());
+        assert_eq!(1, mock.requests().len());
+
+        // Nothing polled once shut down
+        Watcher::new(&sk, Duration::from_secs(3600)).artist(324967).until(shutdown).poll(|_, _| ()).unwrap();
+        assert_eq!(1, mock.requests().len());
+    }
+
+    /// Run `future` on the current thread, without a runtime
+    #[cfg(feature = "tokio")]
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        use std::pin::pin;
+        use std::task::{Context, Poll, Wake, Waker};
+
+        struct Unpark(thread::Thread);
+        impl Wake for Unpark {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut context = Context::from_waker(&waker);
+        let mut future = pin!(future);
+        loop {
+            match future.as_mut().poll(&mut context) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn shutdown_spawned_watcher() {
+        let (sk, mock) = watched_client();
+        let (sender, _receiver) = tokio::sync::mpsc::channel(64);
+        let watcher = spawn(sk, Duration::from_secs(3600), sender, |watcher| watcher.artist(324967));
+        thread::sleep(Duration::from_millis(50));
+        block_on(watcher.shutdown()).unwrap();
+        assert_eq!(1, mock.requests().len());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn shutdown_watcher_blocked_on_full_channel() {
+        use crate::testing::{fixtures, MockTransport};
+        use crate::transport::HttpResponse;
+
+        let empty = HttpResponse::json(r#"{"resultsPage": {"status": "ok", "results": {}, "totalEntries": 0}}"#);
+        let mock = MockTransport::new()
+            .once("artists/324967/calendar.json", empty)
+            .on("artists/324967/calendar.json", fixtures::ARTIST_CALENDAR.response());
+        let sk = crate::SongKickBuilder::new("KEY").transport(mock.clone()).build();
+        // Never drained, the second refresh finds more new events than it can hold
+        let (sender, receiver) = tokio::sync::mpsc::channel(1);
+        let watcher = spawn(sk, Duration::from_millis(1), sender, |watcher| {
+            watcher.artist(324967).pause(Duration::from_millis(1))
+        });
+        while mock.requests().len() < 2 {
+            thread::sleep(Duration::from_millis(10));
+        }
+        thread::sleep(Duration::from_millis(50));
+
+        let started = Instant::now();
+        block_on(watcher.shutdown()).unwrap();
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert_eq!(1, receiver.len());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn changes_over_channel() {
+        let before = events(&calendar()).unwrap();
+        let after = events(&calendar()).unwrap();
+        let target = Target::Artist(ArtistId(324967));
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(64);
+
+        let sent = super::events(&target, &diff(&before[1..], &after))
+            .all(|event| sender.blocking_send(event).is_ok());
+        assert!(sent);
+        assert!(matches!(receiver.try_recv(), Ok(ChangeEvent::New { ref event, .. }) if event.id == before[0].id));
+        assert!(receiver.try_recv().is_err());
+
+        drop(receiver);
+        assert!(sender.blocking_send(ChangeEvent::from_change(&target, &Change::Added(&after[0])).unwrap()).is_err());
+    }
+}